@@ -0,0 +1,40 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Window};
+use egui_animation::{animated_window, AnimatedPanel};
+
+pub fn main() -> eframe::Result<()> {
+    let mut side_panel_open = true;
+    let mut bottom_panel_open = false;
+    let mut window_open = false;
+
+    eframe::run_simple_native(
+        "Window and Panel Animation Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            AnimatedPanel::left("side_panel", 200.0).show(ctx, side_panel_open, |ui| {
+                ui.heading("Side Panel");
+                ui.label("The content slides in and out with the panel.");
+            });
+
+            AnimatedPanel::bottom("bottom_panel", 80.0).show(ctx, bottom_panel_open, |ui| {
+                ui.label("Bottom Panel");
+            });
+
+            animated_window(
+                ctx,
+                "window",
+                Window::new("Animated Window"),
+                &mut window_open,
+                |ui| {
+                    ui.label("Close me via the button in the title bar.");
+                },
+            );
+
+            CentralPanel::default().show(ctx, |ui| {
+                ui.checkbox(&mut side_panel_open, "Side panel");
+                ui.checkbox(&mut bottom_panel_open, "Bottom panel");
+                ui.checkbox(&mut window_open, "Window");
+            });
+        },
+    )
+}
@@ -0,0 +1,103 @@
+use crate::animate_position;
+use crate::transform::EASING;
+use egui::emath::TSTransform;
+use egui::layers::{PaintList, ShapeIdx};
+use egui::{Id, InnerResponse, Rect, Ui, Vec2};
+use std::hash::Hash;
+
+struct FlipItem {
+    id: Id,
+    start: ShapeIdx,
+    end: ShapeIdx,
+    rect: Rect,
+}
+
+/// Collects the items of an [`animate_reorder`] container.
+pub struct FlipItems {
+    id: Id,
+    items: Vec<FlipItem>,
+}
+
+impl FlipItems {
+    /// Add an item to the container. The id must be stable across frames
+    /// (e.g. the id of your data, not the index in the list).
+    pub fn item<R>(
+        &mut self,
+        ui: &mut Ui,
+        id: impl Hash + Sized,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let layer_id = ui.layer_id();
+        let next_idx = |ui: &Ui| {
+            ui.ctx()
+                .graphics(|g| g.get(layer_id).map_or(ShapeIdx(0), PaintList::next_idx))
+        };
+
+        let start = next_idx(ui);
+        let response = ui.scope(content);
+        let end = next_idx(ui);
+
+        self.items.push(FlipItem {
+            id: self.id.with(id),
+            start,
+            end,
+            rect: response.response.rect,
+        });
+
+        response
+    }
+}
+
+/// Animates items into place whenever their position changes, e.g. when a list is sorted or
+/// an item is moved (also known as a FLIP animation).
+///
+/// Add the items via [`FlipItems::item`]. The position of each item is remembered across frames,
+/// and when it changes, the item will be animated from its previous to its new position.
+/// This only affects the visuals, inputs will not be transformed.
+///
+/// Example:
+/// ```no_run
+/// # use egui_animation::animate_reorder;
+/// # fn ui(ui: &mut egui::Ui, items: &mut Vec<u32>) {
+/// if ui.button("Sort").clicked() {
+///     items.sort();
+/// }
+/// animate_reorder(ui, "list", |ui, flip| {
+///     for item in items.iter() {
+///         flip.item(ui, item, |ui| {
+///             ui.label(format!("Item {item}"));
+///         });
+///     }
+/// });
+/// # }
+/// ```
+pub fn animate_reorder<R>(
+    ui: &mut Ui,
+    id: impl Hash + Sized,
+    content: impl FnOnce(&mut Ui, &mut FlipItems) -> R,
+) -> InnerResponse<R> {
+    let mut items = FlipItems {
+        id: Id::new(id),
+        items: Vec::new(),
+    };
+
+    let response = ui.scope(|ui| content(ui, &mut items));
+
+    let time = ui.style().animation_time;
+    let layer_id = ui.layer_id();
+    for item in items.items {
+        let position = animate_position(ui, item.id, item.rect.min, time, EASING, true);
+        let offset = position - item.rect.min;
+        if offset != Vec2::ZERO {
+            ui.ctx().graphics_mut(|g| {
+                g.entry(layer_id).transform_range(
+                    item.start,
+                    item.end,
+                    TSTransform::from_translation(offset),
+                );
+            });
+        }
+    }
+
+    response
+}
@@ -3,6 +3,11 @@
 #![warn(missing_docs)]
 
 mod collapse;
+mod flip;
+mod number;
+mod presence;
+mod response;
+mod transform;
 
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -15,7 +20,12 @@ pub mod easing {
 
 pub use collapse::*;
 use egui::{Context, Id, Pos2, Rect, Sense, Ui, UiBuilder, Vec2};
+pub use flip::{animate_reorder, FlipItems};
 use hello_egui_utils::current_scroll_delta;
+pub use number::*;
+pub use presence::*;
+pub use response::*;
+pub use transform::*;
 
 #[derive(Debug, Clone)]
 struct AnimationState {
@@ -25,6 +35,22 @@ struct AnimationState {
 
 type Easing = fn(f32) -> f32;
 
+fn reduce_motion_id() -> Id {
+    Id::new("egui_animation_reduce_motion")
+}
+
+/// Set whether motion should be reduced, e.g. because the user prefers reduced motion.
+/// If set, the animations in this crate (and in crates respecting [`reduce_motion`], like
+/// `egui_router`) will jump to their target immediately.
+pub fn set_reduce_motion(ctx: &Context, reduce_motion: bool) {
+    ctx.data_mut(|data| data.insert_temp(reduce_motion_id(), reduce_motion));
+}
+
+/// Returns true if motion should be reduced. See [`set_reduce_motion`].
+pub fn reduce_motion(ctx: &Context) -> bool {
+    ctx.data(|data| data.get_temp(reduce_motion_id()).unwrap_or(false))
+}
+
 /// Same as [`Context::animate_bool_with_time`] but with an easing function.
 pub fn animate_bool_eased(
     ctx: &Context,
@@ -33,6 +59,7 @@ pub fn animate_bool_eased(
     easing: Easing,
     time: f32,
 ) -> f32 {
+    let time = if reduce_motion(ctx) { 0.0 } else { time };
     let x = ctx.animate_bool_with_time(Id::new(id), bool, time);
     easing(x)
 }
@@ -60,6 +87,7 @@ pub fn animate_eased(
         (state.source, state.target)
     });
 
+    let time = if reduce_motion(ctx) { 0.0 } else { time };
     let x = ctx.animate_value_with_time(id, value, time);
 
     if target == source {
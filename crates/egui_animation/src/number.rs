@@ -0,0 +1,128 @@
+use crate::transform::EASING;
+use crate::{reduce_motion, Easing};
+use egui::{Context, Id, Label, Response, Ui, Widget, WidgetText};
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy)]
+struct NumberState {
+    from: f64,
+    to: f64,
+    start_time: f64,
+}
+
+/// Tween a number towards `value` over `time` seconds, starting from the currently shown value
+/// whenever `value` changes. Like [`animate_eased`](crate::animate_eased), but with `f64`
+/// precision, so large numbers (e.g. currency amounts) don't lose their decimals.
+pub fn animate_number(
+    ctx: &Context,
+    id: impl Hash + Sized,
+    value: f64,
+    time: f32,
+    easing: Easing,
+) -> f64 {
+    let id = Id::new(id).with("animate_number");
+    let time = if reduce_motion(ctx) { 0.0 } else { time };
+    let now = ctx.input(|i| i.time);
+
+    let current = |state: &NumberState| {
+        let progress = if time <= 0.0 {
+            1.0
+        } else {
+            ((now - state.start_time) as f32 / time).clamp(0.0, 1.0)
+        };
+        (
+            progress,
+            state.from + (state.to - state.from) * f64::from(easing(progress)),
+        )
+    };
+
+    let (progress, current) = ctx.data_mut(|data| {
+        let state = data.get_temp_mut_or_insert_with(id, || NumberState {
+            from: value,
+            to: value,
+            start_time: now,
+        });
+        if state.to != value {
+            *state = NumberState {
+                from: current(state).1,
+                to: value,
+                start_time: now,
+            };
+        }
+        current(state)
+    });
+
+    if progress < 1.0 {
+        ctx.request_repaint();
+        current
+    } else {
+        value
+    }
+}
+
+/// A label showing a number that counts up (or down) to its new value when it changes, e.g. for
+/// counters, dashboards or prices. See [`animate_number`].
+///
+/// ```
+/// # use egui_animation::AnimatedNumber;
+/// fn balance(ui: &mut egui::Ui, balance: f64) {
+///     ui.add(AnimatedNumber::new("balance", balance).format(|value| format!("${value:.2}")));
+/// }
+/// ```
+pub struct AnimatedNumber<'a> {
+    id: Id,
+    value: f64,
+    duration: f32,
+    easing: Easing,
+    format: Box<dyn Fn(f64) -> WidgetText + 'a>,
+}
+
+impl<'a> AnimatedNumber<'a> {
+    /// Show `value`, `id` has to be unique.
+    /// By default, the number is rounded to an integer, see [`Self::decimals`] and [`Self::format`].
+    pub fn new(id: impl Into<Id>, value: f64) -> Self {
+        Self {
+            id: id.into(),
+            value,
+            duration: 0.5,
+            easing: EASING,
+            format: Box::new(|value| format!("{value:.0}").into()),
+        }
+    }
+
+    /// Customize the duration of the animation in seconds. The default is 0.5.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Customize the easing of the animation. The default is `cubic_in_out`.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Show the number with a fixed number of decimals.
+    pub fn decimals(self, decimals: usize) -> Self {
+        self.format(move |value| format!("{value:.decimals$}"))
+    }
+
+    /// Format the number, e.g. to add a currency symbol or thousands separators.
+    /// Return a [`egui::RichText`] to style the text.
+    pub fn format<T: Into<WidgetText>>(mut self, format: impl Fn(f64) -> T + 'a) -> Self {
+        self.format = Box::new(move |value| format(value).into());
+        self
+    }
+
+    /// The value that is currently shown
+    pub fn current(&self, ctx: &Context) -> f64 {
+        animate_number(ctx, self.id, self.value, self.duration, self.easing)
+    }
+}
+
+impl Widget for AnimatedNumber<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let value = self.current(ui.ctx());
+        ui.add(Label::new((self.format)(value)))
+    }
+}
@@ -0,0 +1,186 @@
+use crate::transform::EASING;
+use crate::{animate_bool_eased, Easing};
+use egui::emath::TSTransform;
+use egui::{
+    Context, Frame, Id, InnerResponse, Rect, SidePanel, TopBottomPanel, Ui, UiBuilder, Vec2, Window,
+};
+use std::hash::Hash;
+
+/// The scale a window is shown at when it starts opening or finishes closing
+const WINDOW_CLOSED_SCALE: f32 = 0.9;
+
+/// Animate something that is shown and hidden, like a window or a panel.
+/// Returns the eased progress (0.0 = hidden, 1.0 = shown) while it should be drawn, and `None`
+/// once it is fully hidden. This way the close animation can finish before the content stops
+/// being drawn.
+pub fn animate_presence(
+    ctx: &Context,
+    id: impl Hash + Sized,
+    visible: bool,
+    time: f32,
+    easing: Easing,
+) -> Option<f32> {
+    let x = animate_bool_eased(
+        ctx,
+        Id::new(id).with("animate_presence"),
+        visible,
+        |x| x,
+        time,
+    );
+    (x > 0.0).then(|| easing(x))
+}
+
+/// Show a [`Window`] that scales in when `open` becomes true and scales out when it becomes
+/// false (or the window's close button is clicked), fading with egui's built-in window fade.
+/// The window is drawn until the close animation finished.
+///
+/// `id` is used as the id of the window.
+pub fn animated_window<R>(
+    ctx: &Context,
+    id: impl Hash + Sized,
+    window: Window<'_>,
+    open: &mut bool,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> Option<InnerResponse<Option<R>>> {
+    let id = Id::new(id);
+    let time = ctx.style().animation_time;
+    let progress = animate_presence(ctx, id.with("animated_window"), *open, time, EASING)?;
+
+    let response = window.id(id).open(open).show(ctx, add_contents)?;
+
+    let scale = WINDOW_CLOSED_SCALE + (1.0 - WINDOW_CLOSED_SCALE) * progress;
+    let center = response.response.rect.center().to_vec2();
+    ctx.set_transform_layer(
+        response.response.layer_id,
+        TSTransform::from_translation(center)
+            * TSTransform::from_scaling(scale)
+            * TSTransform::from_translation(-center),
+    );
+
+    Some(response)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PanelEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// A panel that slides in and out at the edge of the screen.
+///
+/// Unlike [`SidePanel::show_animated`], the content is shown while the panel is animating,
+/// sliding in and out with it. The panel has a fixed size and isn't resizable.
+#[derive(Debug, Clone)]
+pub struct AnimatedPanel {
+    edge: PanelEdge,
+    id: Id,
+    size: f32,
+    frame: Option<Frame>,
+    duration: Option<f32>,
+}
+
+impl AnimatedPanel {
+    fn new(edge: PanelEdge, id: impl Into<Id>, size: f32) -> Self {
+        Self {
+            edge,
+            id: id.into(),
+            size,
+            frame: None,
+            duration: None,
+        }
+    }
+
+    /// A panel at the left edge with the given width
+    pub fn left(id: impl Into<Id>, width: f32) -> Self {
+        Self::new(PanelEdge::Left, id, width)
+    }
+
+    /// A panel at the right edge with the given width
+    pub fn right(id: impl Into<Id>, width: f32) -> Self {
+        Self::new(PanelEdge::Right, id, width)
+    }
+
+    /// A panel at the top edge with the given height
+    pub fn top(id: impl Into<Id>, height: f32) -> Self {
+        Self::new(PanelEdge::Top, id, height)
+    }
+
+    /// A panel at the bottom edge with the given height
+    pub fn bottom(id: impl Into<Id>, height: f32) -> Self {
+        Self::new(PanelEdge::Bottom, id, height)
+    }
+
+    /// Customize the frame of the panel
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// Customize the duration of the animation in seconds.
+    /// The default is the style's animation time.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Show the panel if `open` is true, sliding it in and out when `open` changes.
+    /// Returns `None` while the panel is fully closed.
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        open: bool,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        let time = self.duration.unwrap_or_else(|| ctx.style().animation_time);
+        let progress = animate_presence(ctx, self.id.with("animated_panel"), open, time, EASING)?;
+
+        let shown = self.size * progress;
+        // How far the content is moved out of the panel
+        let hidden = self.size - shown;
+        let edge = self.edge;
+        let frame = self
+            .frame
+            .unwrap_or_else(|| Frame::side_top_panel(&ctx.style()));
+        let content = move |ui: &mut Ui| {
+            let rect = ui.max_rect();
+            let rect = match edge {
+                PanelEdge::Left => Rect::from_min_max(rect.min - Vec2::X * hidden, rect.max),
+                PanelEdge::Right => Rect::from_min_max(rect.min, rect.max + Vec2::X * hidden),
+                PanelEdge::Top => Rect::from_min_max(rect.min - Vec2::Y * hidden, rect.max),
+                PanelEdge::Bottom => Rect::from_min_max(rect.min, rect.max + Vec2::Y * hidden),
+            };
+            // A child ui, so the content doesn't grow the panel
+            add_contents(&mut ui.new_child(UiBuilder::new().max_rect(rect).layout(*ui.layout())))
+        };
+
+        let response = match edge {
+            PanelEdge::Left | PanelEdge::Right => {
+                let panel = if edge == PanelEdge::Left {
+                    SidePanel::left(self.id)
+                } else {
+                    SidePanel::right(self.id)
+                };
+                panel
+                    .resizable(false)
+                    .exact_width(shown)
+                    .frame(frame)
+                    .show(ctx, content)
+            }
+            PanelEdge::Top | PanelEdge::Bottom => {
+                let panel = if edge == PanelEdge::Top {
+                    TopBottomPanel::top(self.id)
+                } else {
+                    TopBottomPanel::bottom(self.id)
+                };
+                panel
+                    .resizable(false)
+                    .exact_height(shown)
+                    .frame(frame)
+                    .show(ctx, content)
+            }
+        };
+        Some(response)
+    }
+}
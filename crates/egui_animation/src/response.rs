@@ -0,0 +1,100 @@
+use crate::transform::EASING;
+use crate::{reduce_motion, Easing};
+use egui::{Context, Id, Response, Ui};
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy)]
+struct InOutState {
+    progress: f32,
+    time: f64,
+}
+
+/// Like [`animate_bool_eased`](crate::animate_bool_eased), but with separate durations for
+/// animating in (towards `true`) and out (towards `false`).
+/// E.g. a hover highlight that appears quickly but fades out slowly.
+pub fn animate_bool_in_out(
+    ctx: &Context,
+    id: impl Hash + Sized,
+    value: bool,
+    in_time: f32,
+    out_time: f32,
+    easing: Easing,
+) -> f32 {
+    let id = Id::new(id).with("animate_bool_in_out");
+    let target = if value { 1.0 } else { 0.0 };
+    let time = if reduce_motion(ctx) {
+        0.0
+    } else if value {
+        in_time
+    } else {
+        out_time
+    };
+    let now = ctx.input(|i| i.time);
+
+    let progress = ctx.data_mut(|data| {
+        let state = data.get_temp_mut_or_insert_with(id, || InOutState {
+            progress: target,
+            time: now,
+        });
+
+        let step = if time <= 0.0 {
+            1.0
+        } else {
+            (now - state.time) as f32 / time
+        };
+        state.progress = if value {
+            (state.progress + step).min(target)
+        } else {
+            (state.progress - step).max(target)
+        };
+        state.time = now;
+        state.progress
+    });
+
+    if progress != target {
+        ctx.request_repaint();
+    }
+
+    easing(progress)
+}
+
+/// Animate towards 1.0 while the response is hovered and back to 0.0 when it isn't.
+/// `in_time` and `out_time` are the durations in seconds.
+///
+/// Useful for styling custom widgets, e.g. to fade in a hover background.
+pub fn animate_hover(ui: &Ui, response: &Response, in_time: f32, out_time: f32) -> f32 {
+    animate_bool_in_out(
+        ui.ctx(),
+        response.id.with("animate_hover"),
+        response.hovered(),
+        in_time,
+        out_time,
+        EASING,
+    )
+}
+
+/// Animate towards 1.0 while the response is pressed and back to 0.0 when it's released.
+/// See [`animate_hover`].
+pub fn animate_press(ui: &Ui, response: &Response, in_time: f32, out_time: f32) -> f32 {
+    animate_bool_in_out(
+        ui.ctx(),
+        response.id.with("animate_press"),
+        response.is_pointer_button_down_on(),
+        in_time,
+        out_time,
+        EASING,
+    )
+}
+
+/// Animate towards 1.0 while the response has keyboard focus and back to 0.0 when it loses it.
+/// See [`animate_hover`].
+pub fn animate_focus(ui: &Ui, response: &Response, in_time: f32, out_time: f32) -> f32 {
+    animate_bool_in_out(
+        ui.ctx(),
+        response.id.with("animate_focus"),
+        response.has_focus(),
+        in_time,
+        out_time,
+        EASING,
+    )
+}
@@ -0,0 +1,85 @@
+use crate::{animate_eased, Easing};
+use egui::emath::TSTransform;
+use egui::layers::{PaintList, ShapeIdx};
+use egui::{Id, InnerResponse, Rect, Ui, Vec2};
+use std::hash::Hash;
+
+pub(crate) const EASING: Easing = simple_easing::cubic_in_out;
+
+/// Fade the content in or out. The opacity will be animated towards `target` (0.0 - 1.0).
+/// This only affects the visuals, the content will still be interactive.
+pub fn animated_opacity<R>(
+    ui: &mut Ui,
+    id: impl Hash + Sized,
+    target: f32,
+    content: impl FnOnce(&mut Ui) -> R,
+) -> InnerResponse<R> {
+    let time = ui.style().animation_time;
+    let opacity = animate_eased(ui.ctx(), Id::new(id), target, time, EASING);
+
+    ui.scope(|ui| {
+        ui.multiply_opacity(opacity);
+        content(ui)
+    })
+}
+
+/// Slide the content by an offset. The offset will be animated towards `target`.
+/// This only affects the visuals, inputs will not be transformed.
+pub fn animated_offset<R>(
+    ui: &mut Ui,
+    id: impl Hash + Sized,
+    target: Vec2,
+    content: impl FnOnce(&mut Ui) -> R,
+) -> InnerResponse<R> {
+    let id = Id::new(id);
+    let time = ui.style().animation_time;
+    let offset = Vec2::new(
+        animate_eased(ui.ctx(), id.with("x"), target.x, time, EASING),
+        animate_eased(ui.ctx(), id.with("y"), target.y, time, EASING),
+    );
+
+    with_transform(ui, content, |_| TSTransform::from_translation(offset))
+}
+
+/// Scale the content around its center. The scale will be animated towards `target`.
+/// This only affects the visuals, inputs will not be transformed.
+pub fn animated_scale<R>(
+    ui: &mut Ui,
+    id: impl Hash + Sized,
+    target: f32,
+    content: impl FnOnce(&mut Ui) -> R,
+) -> InnerResponse<R> {
+    let time = ui.style().animation_time;
+    let scale = animate_eased(ui.ctx(), Id::new(id), target, time, EASING);
+
+    with_transform(ui, content, |rect| {
+        let center = rect.center().to_vec2();
+        TSTransform::from_translation(center)
+            * TSTransform::from_scaling(scale)
+            * TSTransform::from_translation(-center)
+    })
+}
+
+/// Similar to [`Ui::with_visual_transform`], but the transform is created after the content
+/// was added, so it can depend on the rect of the content.
+fn with_transform<R>(
+    ui: &mut Ui,
+    content: impl FnOnce(&mut Ui) -> R,
+    transform: impl FnOnce(Rect) -> TSTransform,
+) -> InnerResponse<R> {
+    let layer_id = ui.layer_id();
+    let start_idx = ui
+        .ctx()
+        .graphics(|g| g.get(layer_id).map_or(ShapeIdx(0), PaintList::next_idx));
+
+    let response = ui.scope(content);
+
+    let transform = transform(response.response.rect);
+    ui.ctx().graphics_mut(|g| {
+        let list = g.entry(layer_id);
+        let end_idx = list.next_idx();
+        list.transform_range(start_idx, end_idx, transform);
+    });
+
+    response
+}
@@ -0,0 +1,91 @@
+use eframe::NativeOptions;
+use egui::{CentralPanel, DragValue, ScrollArea, SidePanel};
+
+use egui_dnd::dnd;
+
+/// Drag items quickly over the list and compare the cpu time per frame and the number of gap
+/// moves with and without throttling and smoothing.
+fn main() -> eframe::Result<()> {
+    let mut count = 2000;
+    let mut items: Vec<u32> = (0..count).collect();
+    let mut throttle = true;
+    let mut throttle_interval = 0.05;
+    let mut smoothing = true;
+    let mut smoothing_time = 0.05;
+    let mut cpu_usage = 0.0;
+    let mut gap_moves = 0;
+    let mut last_target = None;
+
+    eframe::run_simple_native(
+        "dnd benchmark",
+        NativeOptions::default(),
+        move |ctx, frame| {
+            // Exponential moving average of the cpu time of the last frames
+            if let Some(usage) = frame.info().cpu_usage {
+                cpu_usage = cpu_usage * 0.95 + usage * 0.05;
+            }
+
+            SidePanel::left("settings").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Items");
+                    if ui
+                        .add(DragValue::new(&mut count).range(1..=100_000))
+                        .changed()
+                    {
+                        items = (0..count).collect();
+                    }
+                });
+
+                ui.checkbox(&mut throttle, "Throttle gap updates");
+                ui.add_enabled(
+                    throttle,
+                    DragValue::new(&mut throttle_interval)
+                        .range(0.0..=0.5)
+                        .speed(0.005)
+                        .suffix(" s"),
+                );
+                ui.checkbox(&mut smoothing, "Smooth drag pointer");
+                ui.add_enabled(
+                    smoothing,
+                    DragValue::new(&mut smoothing_time)
+                        .range(0.0..=0.5)
+                        .speed(0.005)
+                        .suffix(" s"),
+                );
+
+                ui.separator();
+                ui.label(format!("CPU time per frame: {:.2} ms", cpu_usage * 1000.0));
+                ui.label(format!("Gap moves: {gap_moves}"));
+                if ui.button("Reset").clicked() {
+                    gap_moves = 0;
+                }
+            });
+
+            CentralPanel::default().show(ctx, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    let mut dnd = dnd(ui, "benchmark");
+                    if throttle {
+                        dnd = dnd.with_gap_throttle(throttle_interval);
+                    }
+                    if smoothing {
+                        dnd = dnd.with_drag_smoothing(smoothing_time);
+                    }
+                    let response = dnd.show_vec(&mut items, |ui, item, handle, _state| {
+                        ui.horizontal(|ui| {
+                            handle.ui(ui, |ui| {
+                                ui.label("grab");
+                            });
+                            ui.label(format!("Item {item}"));
+                        });
+                    });
+
+                    let target = response.update.map(|update| update.to);
+                    if target.is_some() && target != last_target {
+                        gap_moves += 1;
+                    }
+                    last_target = target;
+                });
+            });
+        },
+    )
+}
@@ -11,7 +11,6 @@ pub struct Item<'a> {
     pub state: ItemState,
     dnd_state: &'a mut DragDropUi,
     hovering_over_any_handle: &'a mut bool,
-    easing: fn(f32) -> f32,
 }
 
 impl<'a> Item<'a> {
@@ -26,7 +25,6 @@ impl<'a> Item<'a> {
             state,
             dnd_state,
             hovering_over_any_handle,
-            easing: simple_easing::linear,
         }
     }
 
@@ -58,6 +56,7 @@ impl<'a> Item<'a> {
         let id = self.id;
         let index = self.state.index;
         let last_pointer_pos = self.dnd_state.detection_state.last_pointer_pos();
+        let simulating = self.dnd_state.is_simulating();
         if let DragDetectionState::Dragging {
             id: dragging_id,
             offset,
@@ -70,23 +69,37 @@ impl<'a> Item<'a> {
 
                 let _layer_id = LayerId::new(Order::Tooltip, id);
 
-                let pointer_pos = ui
-                    .ctx()
-                    .pointer_hover_pos()
-                    .or(last_pointer_pos)
-                    .unwrap_or_else(|| ui.next_widget_position());
-                let position = pointer_pos + *offset;
+                let pointer_pos = if simulating {
+                    None
+                } else {
+                    ui.ctx().pointer_hover_pos()
+                }
+                .or(last_pointer_pos)
+                .unwrap_or_else(|| ui.next_widget_position());
+                let old_offset = *offset;
+                let position = pointer_pos + old_offset;
 
                 // We animate so the animated position is updated, even though we don't use it here.
                 animate_position(
                     ui,
                     id,
                     position,
-                    self.dnd_state.swap_animation_time,
-                    self.easing,
+                    self.dnd_state.swap_animation_time(),
+                    self.dnd_state.animation_easing,
                     false,
                 );
 
+                let resized = self.dnd_state.resized_while_dragging;
+                let old_size = self.dnd_state.detection_state.dragged_item_size();
+
+                // If the list was resized, let the item take the new available width so
+                // we can measure its new size
+                let floating_size = if resized {
+                    Some(size.unwrap_or(Vec2::new(ui.available_width(), f32::INFINITY)))
+                } else {
+                    size
+                };
+
                 let InnerResponse { inner: rect, .. } = Self::draw_floating_at_position(
                     self.state,
                     self.dnd_state,
@@ -94,11 +107,27 @@ impl<'a> Item<'a> {
                     id,
                     position,
                     hovering_over_any_handle,
-                    size,
+                    floating_size,
                     *ui.layout(),
                     drag_body,
                 );
 
+                if resized {
+                    if let DragDetectionState::Dragging {
+                        offset,
+                        dragged_item_size,
+                        ..
+                    } = &mut self.dnd_state.detection_state
+                    {
+                        // Scale the offset so the item stays anchored to the pointer at the
+                        // same relative position
+                        if let Some(old_size) = old_size.filter(|s| s.x > 0.0 && s.y > 0.0) {
+                            *offset = old_offset * (rect.size() / old_size);
+                        }
+                        *dragged_item_size = rect.size();
+                    }
+                }
+
                 ui.allocate_space(rect.size());
 
                 let rect = Rect::from_min_size(ui.next_widget_position(), rect.size());
@@ -122,7 +151,7 @@ impl<'a> Item<'a> {
                     id,
                     end_pos,
                     self.dnd_state.return_animation_time,
-                    self.easing,
+                    self.dnd_state.animation_easing,
                     false,
                 );
 
@@ -165,8 +194,8 @@ impl<'a> Item<'a> {
                 ui,
                 id,
                 rect.min,
-                self.dnd_state.swap_animation_time,
-                self.easing,
+                self.dnd_state.swap_animation_time(),
+                self.dnd_state.animation_easing,
                 true,
             );
 
@@ -203,8 +232,8 @@ impl<'a> Item<'a> {
                 ui,
                 id,
                 position,
-                self.dnd_state.swap_animation_time,
-                self.easing,
+                self.dnd_state.swap_animation_time(),
+                self.dnd_state.animation_easing,
                 true,
             );
 
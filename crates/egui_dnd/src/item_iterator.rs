@@ -13,6 +13,8 @@ pub struct ItemIterator<'a> {
     set_next_item_as_hovering_above: bool,
     pub(crate) hovering_last_item: bool,
     pub(crate) last_item: Option<(usize, Id, Pos2)>,
+    /// Position of the hovered item in the current frame
+    pub(crate) hovering_item_pos: Option<Pos2>,
 
     pub(crate) mark_next_as_closest_item: Option<(f32, Pos2)>,
 
@@ -55,6 +57,7 @@ impl<'a> ItemIterator<'a> {
             mark_next_as_closest_item: None,
             hovering_last_item,
             last_item: None,
+            hovering_item_pos: None,
 
             is_after_dragged_item: false,
             is_after_hovered_item: false,
@@ -128,6 +131,13 @@ impl<'a> ItemIterator<'a> {
             content(ui, item).0
         };
 
+        if let Some((_, anchor)) = self.state.pending_drag.filter(|(index, _)| *index == idx) {
+            self.state.pending_drag = None;
+            self.state
+                .start_dragging(ui.ctx(), id, idx, rect.min, rect.min + anchor, rect.size());
+            self.hovering_over_any_handle = true;
+        }
+
         if dragging != self.state.detection_state.is_dragging() {
             self.set_next_item_as_hovering_above = true;
         }
@@ -155,6 +165,13 @@ impl<'a> ItemIterator<'a> {
             self.source_item = Some((idx, id));
         }
 
+        if self
+            .hovering_item
+            .is_some_and(|(hovering_id, _)| hovering_id == id)
+        {
+            self.hovering_item_pos = Some(rect.min);
+        }
+
         self.last_item = Some((idx, id, rect.min));
     }
 
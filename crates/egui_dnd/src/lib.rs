@@ -6,12 +6,17 @@ use egui::{Id, Ui};
 pub use state::{DragDropConfig, DragDropItem, DragDropResponse, DragUpdate, Handle};
 
 pub use crate::item_iterator::ItemIterator;
-use crate::state::DragDropUi;
+pub use crate::payload::{drop_zone, DragPayload, DropZoneResponse};
+use crate::state::{DragDropUi, StableDrag};
 use std::hash::Hash;
 
 mod item;
 mod item_iterator;
+mod payload;
 mod state;
+/// Utilities to test lists that use drag and drop, see [`test_util::DragSimulation`]
+#[cfg(feature = "test_util")]
+pub mod test_util;
 /// Helper functions to support the drag and drop functionality
 pub mod utils;
 
@@ -20,6 +25,7 @@ pub struct Dnd<'a> {
     id: Id,
     ui: &'a mut Ui,
     drag_drop_ui: DragDropUi,
+    stable_id: Option<Id>,
 }
 
 /// Main entry point for the drag and drop functionality.
@@ -58,11 +64,15 @@ pub fn dnd(ui: &mut Ui, id_source: impl Hash) -> Dnd {
 
     dnd_ui.return_animation_time = ui.style().animation_time;
     dnd_ui.swap_animation_time = ui.style().animation_time;
+    dnd_ui.animation_easing = simple_easing::linear;
+    dnd_ui.gap_throttle = None;
+    dnd_ui.drag_smoothing = 0.0;
 
     Dnd {
         id,
         ui,
         drag_drop_ui: dnd_ui,
+        stable_id: None,
     }
 }
 
@@ -118,6 +128,90 @@ impl<'a> Dnd<'a> {
         self
     }
 
+    /// Disables all animations, so items jump to their new position.
+    /// Useful for lists with many items, where the animations would be too expensive.
+    /// This is the same as calling [`Dnd::with_animation_time`] with 0.
+    #[must_use]
+    pub fn without_animations(self) -> Self {
+        self.with_animation_time(0.0)
+    }
+
+    /// Sets the easing function used for all animations, e.g. one from the `simple_easing` crate.
+    /// The default is linear.
+    #[must_use]
+    pub fn with_animation_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.drag_drop_ui.animation_easing = easing;
+        self
+    }
+
+    /// Limits how often the gap of the dragged item can move to another item, to at most once
+    /// every `interval` seconds. If frames take longer than 1/60s, the interval is increased
+    /// to two frames, so slow frames aren't slowed down further by re-layouting the list.
+    ///
+    /// During fast drags over long lists (1000+ items), every pointer movement would otherwise
+    /// move the gap and animate all items in between. Combine this with
+    /// [`Dnd::with_drag_smoothing`] to keep the CPU usage low. By default there is no throttling.
+    /// Check the `benchmark` example to compare the settings.
+    #[must_use]
+    pub fn with_gap_throttle(mut self, interval: f32) -> Self {
+        self.drag_drop_ui.gap_throttle = Some(interval.max(0.0));
+        self
+    }
+
+    /// Smooths the pointer position that is used to find where the dragged item should be
+    /// inserted, so the gap follows fast pointer movements with a delay instead of jumping over
+    /// every item in between. `time` is roughly how long in seconds it takes to catch up with the
+    /// pointer. The dragged item itself still follows the pointer directly.
+    /// The default is 0 (no smoothing).
+    #[must_use]
+    pub fn with_drag_smoothing(mut self, time: f32) -> Self {
+        self.drag_drop_ui.drag_smoothing = time.max(0.0);
+        self
+    }
+
+    /// Start dragging the item at `index` from code, e.g. when a long-press was detected on a
+    /// custom widget, or to automate a demo or tutorial.
+    /// `anchor` is the position of the pointer relative to the top left corner of the item.
+    ///
+    /// This skips the usual drag detection (delay and click threshold). The drag then continues
+    /// like a regular drag: the item follows the pointer and is dropped when the pointer is
+    /// released, so a pointer button should be held down.
+    /// If there is no item at `index`, no drag is started.
+    #[must_use]
+    pub fn start_drag(mut self, index: usize, anchor: egui::Vec2) -> Self {
+        self.drag_drop_ui.pending_drag = Some((index, anchor));
+        self
+    }
+
+    /// Keep an ongoing drag alive when the id of the list changes, e.g. when `id_source` is
+    /// derived from the surrounding ui (like `ui.id()`) and the list is wrapped in a new container
+    /// or moved to another panel mid-drag. Without this, the list with the new id starts with a
+    /// fresh state and the drag is cancelled.
+    ///
+    /// While a drag is ongoing, its state is also stored under `stable_id`, and a list with the
+    /// same `stable_id` but another id continues the drag. Like `id_source`, `stable_id` has to be
+    /// unique, but it must not depend on the layout.
+    #[must_use]
+    pub fn with_stable_id(mut self, stable_id: impl Hash) -> Self {
+        let stable_id = Id::new(stable_id).with("dnd_stable");
+        if self.drag_drop_ui.detection_state.is_idle() {
+            let id = self.id;
+            let previous = self.ui.data_mut(|data| {
+                let drag = data
+                    .get_temp::<StableDrag>(stable_id)
+                    .filter(|drag| drag.owner != id)?;
+                // The drag continues here, so the old list mustn't show it if it reappears
+                data.remove::<DragDropUi>(drag.owner);
+                Some(drag.state)
+            });
+            if let Some(previous) = previous {
+                self.drag_drop_ui.continue_drag(previous);
+            }
+        }
+        self.stable_id = Some(stable_id);
+        self
+    }
+
     /// Display the drag and drop UI.
     /// `items` should be an iterator over items that should be sortable.
     /// Each item needs to implement [`DragDropItem`]. This is automatically implement for every type that implements [Hash].
@@ -223,11 +317,25 @@ impl<'a> Dnd<'a> {
             id,
             ui,
             mut drag_drop_ui,
+            stable_id,
         } = self;
 
         let response = inner_fn(id, ui, &mut drag_drop_ui);
 
-        ui.ctx().data_mut(|data| data.insert_temp(id, drag_drop_ui));
+        ui.ctx().data_mut(|data| {
+            if let Some(stable_id) = stable_id {
+                if drag_drop_ui.detection_state.is_idle() {
+                    data.remove::<StableDrag>(stable_id);
+                } else {
+                    let drag = StableDrag {
+                        owner: id,
+                        state: drag_drop_ui.clone(),
+                    };
+                    data.insert_temp(stable_id, drag);
+                }
+            }
+            data.insert_temp(id, drag_drop_ui);
+        });
 
         response
     }
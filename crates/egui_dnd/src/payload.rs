@@ -0,0 +1,90 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use egui::{DragAndDrop, Frame, Id, InnerResponse, Response, Ui};
+
+/// A typed payload attached to a dragged item via [`crate::Handle::payload`].
+/// It can be received by a [`drop_zone`] that accepts payloads of type `T`.
+#[derive(Debug, Clone)]
+pub struct DragPayload<T> {
+    /// The id of the dragged item.
+    pub item_id: Id,
+    /// The index of the dragged item in its source list.
+    /// Use this to e.g. remove the item from its source list when it is dropped.
+    pub index: usize,
+    /// The payload data.
+    pub data: T,
+}
+
+impl<T: Any + Send + Sync> DragPayload<T> {
+    /// Returns the payload of type `T` that is currently being dragged, if any.
+    pub fn dragged(ui: &Ui) -> Option<Arc<Self>> {
+        DragAndDrop::payload::<Self>(ui.ctx())
+    }
+
+    /// Returns true if a payload of type `T` is currently being dragged.
+    pub fn is_dragged(ui: &Ui) -> bool {
+        DragAndDrop::has_payload_of_type::<Self>(ui.ctx())
+    }
+}
+
+/// Response of a [`drop_zone`].
+pub struct DropZoneResponse<T, R> {
+    /// The response of the drop zone.
+    pub response: Response,
+    /// The return value of the content closure.
+    pub inner: R,
+    /// True if a payload of type `T` is being dragged, so the drop zone would accept it.
+    pub can_accept: bool,
+    /// True if a payload of type `T` is being dragged and hovers the drop zone.
+    pub hovered: bool,
+    /// The payload that was dropped on the drop zone this frame, if any.
+    pub dropped: Option<Arc<DragPayload<T>>>,
+}
+
+/// A drop zone that accepts [`DragPayload`]s of type `T`.
+///
+/// While an item with an accepted payload is dragged, the drop zone is highlighted when hovered.
+/// While something else is dragged, it is grayed out to show that it can't be dropped here.
+/// Only payloads of type `T` can be dropped, so you get typed access to the payload on drop.
+///
+/// Example:
+/// ```no_run
+/// # use egui::{Frame, Ui};
+/// # use egui_dnd::{dnd, drop_zone};
+/// fn ui(ui: &mut Ui, items: &mut Vec<String>, trash: &mut Vec<String>) {
+///     dnd(ui, "items").show_vec(items, |ui, item, handle, _state| {
+///         handle.payload(item.clone()).ui(ui, |ui| {
+///             ui.label(item.as_str());
+///         });
+///     });
+///
+///     let response = drop_zone::<String, _>(ui, Frame::group(ui.style()), |ui| {
+///         ui.label("Drop here to delete");
+///     });
+///     if let Some(payload) = response.dropped {
+///         // The index is from when the drag started, so check that it still points at the item
+///         if items.get(payload.index) == Some(&payload.data) {
+///             trash.push(items.remove(payload.index));
+///         }
+///     }
+/// }
+/// ```
+pub fn drop_zone<T: Any + Send + Sync, R>(
+    ui: &mut Ui,
+    frame: Frame,
+    content: impl FnOnce(&mut Ui) -> R,
+) -> DropZoneResponse<T, R> {
+    let can_accept = DragPayload::<T>::is_dragged(ui);
+
+    let (InnerResponse { inner, response }, dropped) =
+        ui.dnd_drop_zone::<DragPayload<T>, R>(frame, content);
+
+    DropZoneResponse {
+        hovered: can_accept && response.contains_pointer(),
+        response,
+        inner,
+        can_accept,
+        dropped,
+    }
+}
@@ -1,14 +1,17 @@
+use std::any::Any;
+use std::collections::VecDeque;
 use std::hash::Hash;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, SystemTime};
 
-use egui::{CursorIcon, Id, Pos2, Rect, Sense, Ui, Vec2};
+use egui::{Context, CursorIcon, DragAndDrop, Id, Pos2, Rect, Sense, Ui, Vec2};
 
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, SystemTime};
 
 use crate::item_iterator::ItemIterator;
 use crate::utils::shift_vec;
+use crate::DragPayload;
 
 /// Item that can be reordered using drag and drop
 pub trait DragDropItem {
@@ -111,6 +114,47 @@ pub struct DragDropUi {
     mouse_config: DragDropConfig,
     pub(crate) swap_animation_time: f32,
     pub(crate) return_animation_time: f32,
+    pub(crate) animation_easing: fn(f32) -> f32,
+    /// The available width of the list in the last frame, used to detect resizes during a drag
+    last_width: Option<f32>,
+    /// True if the list was resized during a drag in the current frame
+    pub(crate) resized_while_dragging: bool,
+    /// Index and pointer anchor of a drag started via [`crate::Dnd::start_drag`]
+    pub(crate) pending_drag: Option<(usize, Vec2)>,
+    /// Minimum time in seconds between two changes of the gap, see [`crate::Dnd::with_gap_throttle`]
+    pub(crate) gap_throttle: Option<f32>,
+    /// Time constant in seconds of the pointer smoothing, see [`crate::Dnd::with_drag_smoothing`]
+    pub(crate) drag_smoothing: f32,
+    /// The smoothed pointer position used to find the item the dragged item is hovering
+    smoothed_pointer_pos: Option<Pos2>,
+    /// The time (in egui's input time) the gap was last moved to another item
+    last_gap_change: f64,
+    /// The remaining steps of a drag simulated via `test_util::DragSimulation`, one per frame
+    pub(crate) simulation: VecDeque<SimulationStep>,
+    /// True while a simulated drag holds the pointer down
+    simulated_pointer_down: bool,
+}
+
+/// The state of an ongoing drag, stored under the id passed to [`crate::Dnd::with_stable_id`]
+#[derive(Clone, Debug)]
+pub(crate) struct StableDrag {
+    /// The id of the list that showed the drag last
+    pub owner: Id,
+    pub state: DragDropUi,
+}
+
+/// A step of a simulated drag, see `test_util::DragSimulation`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(feature = "test_util"), allow(dead_code))]
+pub(crate) enum SimulationStep {
+    /// Press the pointer at `anchor` relative to the top left corner of the item at `index`
+    Press { index: usize, anchor: Vec2 },
+    /// Move the pointer by the delta
+    Move(Vec2),
+    /// Keep the pointer where it is for a frame
+    Wait,
+    /// Release the pointer
+    Release,
 }
 
 impl Default for DragDropUi {
@@ -121,10 +165,23 @@ impl Default for DragDropUi {
             mouse_config: DragDropConfig::mouse(),
             swap_animation_time: 0.2,
             return_animation_time: 0.2,
+            animation_easing: simple_easing::linear,
+            last_width: None,
+            resized_while_dragging: false,
+            pending_drag: None,
+            gap_throttle: None,
+            drag_smoothing: 0.0,
+            smoothed_pointer_pos: None,
+            last_gap_change: 0.0,
+            simulation: VecDeque::new(),
+            simulated_pointer_down: false,
         }
     }
 }
 
+/// The time a frame may take to still reach 60 fps
+const FRAME_BUDGET: f32 = 1.0 / 60.0;
+
 /// [`Handle::ui`] is used to draw the drag handle
 pub struct Handle<'a> {
     id: Id,
@@ -137,8 +194,11 @@ pub struct Handle<'a> {
     sense: Option<Sense>,
     show_drag_cursor_on_hover: bool,
     disable_selectable_labels: bool,
+    set_payload: Option<SetPayloadFn<'a>>,
 }
 
+type SetPayloadFn<'a> = Box<dyn FnOnce(&Context) + 'a>;
+
 #[derive(Debug, Default, Clone)]
 pub(crate) enum DragDetectionState {
     #[default]
@@ -180,6 +240,14 @@ impl DragDetectionState {
         matches!(self, DragDetectionState::Dragging { .. })
     }
 
+    /// True if no drag is being evaluated, ongoing or animating back
+    pub(crate) fn is_idle(&self) -> bool {
+        matches!(
+            self,
+            DragDetectionState::None | DragDetectionState::Cancelled(_)
+        )
+    }
+
     fn dragged_item(&self) -> Option<Id> {
         match self {
             DragDetectionState::Dragging { id, .. } => Some(*id),
@@ -232,6 +300,7 @@ impl<'a> Handle<'a> {
             sense: None,
             show_drag_cursor_on_hover: true,
             disable_selectable_labels: true,
+            set_payload: None,
         }
     }
 
@@ -262,6 +331,25 @@ impl<'a> Handle<'a> {
         self
     }
 
+    /// Attach a typed payload to the item. While the item is being dragged, it can be
+    /// received by a [`crate::drop_zone`] accepting `T`, as a [`DragPayload<T>`].
+    #[must_use]
+    pub fn payload<T: Any + Send + Sync>(mut self, data: T) -> Self {
+        let item_id = self.id;
+        let index = self.idx;
+        self.set_payload = Some(Box::new(move |ctx| {
+            DragAndDrop::set_payload(
+                ctx,
+                DragPayload {
+                    item_id,
+                    index,
+                    data,
+                },
+            );
+        }));
+        self
+    }
+
     /// Draw the drag handle. Use [`Handle::sense`] to add a click sense.
     /// You can also add buttons in the handle, but they won't be interactive if you pass `Sense::click`
     pub fn ui(mut self, ui: &mut Ui, contents: impl FnOnce(&mut Ui)) -> egui::Response {
@@ -321,12 +409,6 @@ impl<'a> Handle<'a> {
             *self.hovering_over_any_handle = true;
         }
 
-        let offset = self.item_pos.to_vec2()
-            - ui.ctx()
-                .input(|i| i.pointer.hover_pos())
-                .unwrap_or_default()
-                .to_vec2();
-
         let drag_distance = ui.input(|i| {
             (i.pointer.hover_pos().unwrap_or_default()
                 - i.pointer.press_origin().unwrap_or_default())
@@ -362,21 +444,23 @@ impl<'a> Handle<'a> {
                 DragDetectionState::CouldBeValidDrag
             )
         {
-            self.state.detection_state = DragDetectionState::Dragging {
-                id: self.id,
-                offset,
-                // We set this in the Item
-                dragged_item_size: Vec2::default(),
-                closest_item: (self.id, self.item_pos),
-                source_idx: self.idx,
-                hovering_idx: self.idx,
-                last_pointer_pos: ui
-                    .ctx()
+            self.state.start_dragging(
+                ui.ctx(),
+                self.id,
+                self.idx,
+                self.item_pos,
+                ui.ctx()
                     .input(|i| i.pointer.hover_pos())
                     .unwrap_or_default(),
-                hovering_last_item: false,
-            };
-            ui.ctx().set_dragged_id(self.id);
+                // We set this in the Item
+                Vec2::default(),
+            );
+        }
+
+        if self.state.detection_state.is_dragging_item(self.id) {
+            if let Some(set_payload) = self.set_payload.take() {
+                set_payload(ui.ctx());
+            }
         }
 
         response
@@ -460,6 +544,97 @@ impl DragDropUi {
         self
     }
 
+    /// Continues the drag of `other`, the state of the list before its id changed, see
+    /// [`crate::Dnd::with_stable_id`]. The config of this list is kept.
+    pub(crate) fn continue_drag(&mut self, other: DragDropUi) {
+        self.detection_state = other.detection_state;
+        self.last_width = other.last_width;
+        self.smoothed_pointer_pos = other.smoothed_pointer_pos;
+        self.last_gap_change = other.last_gap_change;
+        self.simulation = other.simulation;
+        self.simulated_pointer_down = other.simulated_pointer_down;
+    }
+
+    /// Items should jump to their new position instead of animating when the list was resized
+    pub(crate) fn swap_animation_time(&self) -> f32 {
+        if self.resized_while_dragging {
+            0.0
+        } else {
+            self.swap_animation_time
+        }
+    }
+
+    /// Start dragging the item at `item_pos`, with the pointer at `pointer_pos`
+    pub(crate) fn start_dragging(
+        &mut self,
+        ctx: &Context,
+        id: Id,
+        idx: usize,
+        item_pos: Pos2,
+        pointer_pos: Pos2,
+        dragged_item_size: Vec2,
+    ) {
+        self.detection_state = DragDetectionState::Dragging {
+            id,
+            offset: item_pos - pointer_pos,
+            dragged_item_size,
+            closest_item: (id, item_pos),
+            source_idx: idx,
+            hovering_idx: idx,
+            last_pointer_pos: pointer_pos,
+            hovering_last_item: false,
+        };
+        ctx.set_dragged_id(id);
+    }
+
+    /// Moves the smoothed pointer towards the actual pointer and returns it.
+    /// The dragged item itself still follows the actual pointer, only the detection of the
+    /// hovered item is smoothed, so the gap doesn't jump around during fast drags.
+    fn smooth_pointer_pos(&mut self, ctx: &Context, pointer_pos: Option<Pos2>) -> Option<Pos2> {
+        let (Some(pointer_pos), true) = (pointer_pos, self.detection_state.is_dragging()) else {
+            self.smoothed_pointer_pos = None;
+            return pointer_pos;
+        };
+        if self.drag_smoothing <= 0.0 || self.resized_while_dragging {
+            self.smoothed_pointer_pos = Some(pointer_pos);
+            return Some(pointer_pos);
+        }
+
+        let dt = ctx.input(|i| i.unstable_dt);
+        let current = self.smoothed_pointer_pos.unwrap_or(pointer_pos);
+        let mut smoothed = current.lerp(pointer_pos, 1.0 - (-dt / self.drag_smoothing).exp());
+        if (smoothed - pointer_pos).length() < 0.5 {
+            smoothed = pointer_pos;
+        } else {
+            // Keep going until the smoothed pointer caught up
+            ctx.request_repaint();
+        }
+        self.smoothed_pointer_pos = Some(smoothed);
+        Some(smoothed)
+    }
+
+    /// Returns true if the gap may move to another item in this frame.
+    /// If the last frames took longer than the frame budget, the gap is moved less often, so
+    /// re-layouting long lists doesn't slow things down even further.
+    fn can_move_gap(&self, ctx: &Context) -> bool {
+        let Some(throttle) = self.gap_throttle else {
+            return true;
+        };
+        let (time, dt) = ctx.input(|i| (i.time, i.unstable_dt));
+        let interval = if dt > FRAME_BUDGET {
+            throttle.max(dt * 2.0)
+        } else {
+            throttle
+        };
+        let remaining = f64::from(interval) - (time - self.last_gap_change);
+        if remaining > 0.0 {
+            ctx.request_repaint_after_secs(remaining as f32);
+            false
+        } else {
+            true
+        }
+    }
+
     fn config(&self, ui: &Ui) -> &DragDropConfig {
         if ui.input(egui::InputState::any_touches) {
             self.touch_config.as_ref().unwrap_or(&self.mouse_config)
@@ -468,6 +643,38 @@ impl DragDropUi {
         }
     }
 
+    /// Applies the next step of a simulated drag. Returns true if the pointer was released.
+    fn simulate_step(&mut self, ctx: &Context) -> bool {
+        let Some(step) = self.simulation.pop_front() else {
+            return false;
+        };
+        // Keep going until all steps are applied
+        ctx.request_repaint();
+        match step {
+            SimulationStep::Press { index, anchor } => {
+                self.pending_drag = Some((index, anchor));
+                self.simulated_pointer_down = true;
+            }
+            SimulationStep::Move(delta) => {
+                if let DragDetectionState::Dragging {
+                    last_pointer_pos, ..
+                } = &mut self.detection_state
+                {
+                    *last_pointer_pos += delta;
+                }
+            }
+            SimulationStep::Wait => {}
+            // The pointer counts as down until the end of the frame, like a real release
+            SimulationStep::Release => return self.simulated_pointer_down,
+        }
+        false
+    }
+
+    /// True while a simulated drag holds the pointer down, so the actual pointer is ignored
+    pub(crate) fn is_simulating(&self) -> bool {
+        self.simulated_pointer_down
+    }
+
     /// Draw the items and handle drag & drop stuff
     #[allow(clippy::too_many_lines)] // TODO: refactor this to reduce the number of lines
     pub fn ui(
@@ -478,9 +685,13 @@ impl DragDropUi {
         // During the first frame, we check if the pointer is actually over any of the item handles and cancel the drag if it isn't
         let mut first_frame = false;
         let config = self.config(ui).clone();
+        let simulated_release = self.simulate_step(ui.ctx());
+        let simulating = self.simulated_pointer_down;
+        // A drag started via Dnd::start_drag skips the drag detection
+        let has_pending_drag = self.pending_drag.is_some();
 
         ui.input(|i| {
-            if i.pointer.any_down() {
+            if i.pointer.any_down() && !has_pending_drag && !simulating {
                 if matches!(self.detection_state, DragDetectionState::None)
                     || matches!(
                         self.detection_state,
@@ -527,9 +738,25 @@ impl DragDropUi {
             }
         });
 
-        let pointer_pos = ui
-            .input(|i| i.pointer.hover_pos())
-            .or_else(|| self.detection_state.last_pointer_pos());
+        // If the list is resized during a drag (e.g. the window is resized or a panel collapses),
+        // the item positions from the last frame are outdated, so we have to recompute them
+        let width = ui.available_width();
+        self.resized_while_dragging = self.detection_state.is_dragging()
+            && self
+                .last_width
+                .is_some_and(|last_width| last_width != width);
+        self.last_width = Some(width);
+        if self.resized_while_dragging {
+            ui.ctx().request_repaint();
+        }
+
+        let pointer_pos = if simulating {
+            self.detection_state.last_pointer_pos()
+        } else {
+            ui.input(|i| i.pointer.hover_pos())
+                .or_else(|| self.detection_state.last_pointer_pos())
+        };
+        let detection_pointer_pos = self.smooth_pointer_pos(ui.ctx(), pointer_pos);
 
         let dragged_item_rect = if let DragDetectionState::Dragging {
             offset,
@@ -538,7 +765,7 @@ impl DragDropUi {
         } = &self.detection_state
         {
             Some(Rect::from_min_size(
-                pointer_pos.unwrap_or_default() + *offset,
+                detection_pointer_pos.unwrap_or_default() + *offset,
                 *dragged_item_size,
             ))
         } else {
@@ -555,8 +782,11 @@ impl DragDropUi {
             mark_next_as_closest_item,
             last_item,
             hovering_last_item,
+            hovering_item_pos,
             ..
         } = item_iter;
+        // If the item wasn't found, the drag is not started
+        self.pending_drag = None;
 
         // This is only some if we're hoving over the last item
         let hovering_last_item = if mark_next_as_closest_item.is_some() {
@@ -568,7 +798,7 @@ impl DragDropUi {
             false
         };
 
-        let pointer_released = ui.input(|i| i.pointer.any_released());
+        let pointer_released = ui.input(|i| i.pointer.any_released()) || simulated_release;
         let should_update = closest_item.is_some_and(|i| i.1.is_some());
 
         // The cursor is not hovering over any item, so cancel
@@ -580,6 +810,21 @@ impl DragDropUi {
         let drag_phase_changed_this_frame = false;
 
         let hovering_item = closest_item.and_then(|i| i.1);
+        let moves_gap = match (&self.detection_state, hovering_item) {
+            (
+                DragDetectionState::Dragging {
+                    hovering_idx,
+                    hovering_last_item: was_hovering_last_item,
+                    ..
+                },
+                Some((idx, _, _)),
+            ) => *hovering_idx != idx || *was_hovering_last_item != hovering_last_item,
+            _ => false,
+        };
+        let throttled = moves_gap && !self.can_move_gap(ui.ctx());
+        if moves_gap && !throttled {
+            self.last_gap_change = ui.input(|i| i.time);
+        }
 
         if let DragDetectionState::Dragging {
             closest_item: closest_out,
@@ -590,7 +835,18 @@ impl DragDropUi {
             ..
         } = &mut self.detection_state
         {
-            if let Some((hovering_idx, hovering_id, pos)) = hovering_item {
+            if self.resized_while_dragging {
+                // The closest item was calculated with outdated positions, so we keep the
+                // current insertion index and only update the position of the hovered item
+                if let Some(pos) = hovering_item_pos {
+                    closest_out.1 = pos;
+                }
+            } else if throttled {
+                // Keep the gap where it is for now, it is moved once the throttle interval passed
+                if let Some(pos) = hovering_item_pos {
+                    closest_out.1 = pos;
+                }
+            } else if let Some((hovering_idx, hovering_id, pos)) = hovering_item {
                 *closest_out = (hovering_id, pos);
                 *hovering_idx_out = hovering_idx;
                 *hovering_last_item_out = hovering_last_item;
@@ -637,7 +893,7 @@ impl DragDropUi {
                 }),
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
-                has_changed: should_update,
+                has_changed: should_update && !self.resized_while_dragging && !throttled,
             }
         } else {
             DragDropResponse {
@@ -660,8 +916,13 @@ impl DragDropUi {
             }
         }
 
+        if simulated_release {
+            self.simulated_pointer_down = false;
+        }
+
         ui.input(|input| {
             if !input.pointer.any_down()
+                && !self.simulated_pointer_down
                 && !matches!(
                     self.detection_state,
                     DragDetectionState::TransitioningBackAfterDragFinished { .. }
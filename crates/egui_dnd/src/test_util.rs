@@ -0,0 +1,109 @@
+use std::hash::Hash;
+
+use egui::{Context, Id, Vec2};
+
+use crate::state::{DragDropUi, SimulationStep};
+
+/// Simulates a drag in a [`crate::dnd`] list without pointer events, so tests of your reorder
+/// logic are deterministic (e.g. with `egui_kittest` or by running an [`egui::Context`] by
+/// hand). The simulation drives the same state machine as a real drag, but skips the drag
+/// detection (delay and click threshold), like [`crate::Dnd::start_drag`].
+///
+/// Build the steps, call [`DragSimulation::start`] and then run at least
+/// [`DragSimulation::frames`] frames. Each step is applied in its own frame, while the pointer
+/// is held down by the simulation the actual pointer is ignored.
+///
+/// Example:
+/// ```
+/// use egui::{CentralPanel, Context, RawInput, Vec2};
+/// use egui_dnd::dnd;
+/// use egui_dnd::test_util::DragSimulation;
+///
+/// let ctx = Context::default();
+/// let mut items = vec!["a", "b", "c"];
+///
+/// // Drag "a" down past the center of "b"
+/// let simulation = DragSimulation::new("list")
+///     .press(0, Vec2::splat(2.0))
+///     .move_by(Vec2::new(0.0, 20.0))
+///     .release();
+/// let frames = simulation.frames();
+/// simulation.start(&ctx);
+///
+/// for _ in 0..frames {
+///     let _ = ctx.run(RawInput::default(), |ctx| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             dnd(ui, "list")
+///                 .without_animations()
+///                 .show_vec(&mut items, |ui, item, handle, _state| {
+///                     handle.ui(ui, |ui| {
+///                         ui.set_min_size(Vec2::new(100.0, 15.0));
+///                     });
+///                 });
+///         });
+///     });
+/// }
+///
+/// assert_eq!(items, ["b", "a", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DragSimulation {
+    id: Id,
+    steps: Vec<SimulationStep>,
+}
+
+impl DragSimulation {
+    /// Create a simulation for the list with the same `id_source` as passed to [`crate::dnd`].
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source).with("dnd"),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Press the pointer on the item at `index`, at `anchor` relative to the item's top left
+    /// corner, which starts dragging the item. If there is no item at `index`, no drag is started.
+    #[must_use]
+    pub fn press(mut self, index: usize, anchor: Vec2) -> Self {
+        self.steps.push(SimulationStep::Press { index, anchor });
+        self
+    }
+
+    /// Move the pointer (and the dragged item) by `delta`.
+    #[must_use]
+    pub fn move_by(mut self, delta: Vec2) -> Self {
+        self.steps.push(SimulationStep::Move(delta));
+        self
+    }
+
+    /// Keep the pointer where it is for `frames` frames, e.g. to let a gap throttle pass.
+    #[must_use]
+    pub fn wait(mut self, frames: usize) -> Self {
+        self.steps
+            .extend(std::iter::repeat_n(SimulationStep::Wait, frames));
+        self
+    }
+
+    /// Release the pointer, which drops the dragged item.
+    #[must_use]
+    pub fn release(mut self) -> Self {
+        self.steps.push(SimulationStep::Release);
+        self
+    }
+
+    /// The number of frames it takes to apply all steps.
+    pub fn frames(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Start the simulation, the steps are applied in the next frames.
+    /// Steps of a simulation that is still running are applied first.
+    pub fn start(self, ctx: &Context) {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<DragDropUi>(self.id)
+                .simulation
+                .extend(self.steps);
+        });
+        ctx.request_repaint();
+    }
+}
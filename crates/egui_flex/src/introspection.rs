@@ -0,0 +1,76 @@
+use crate::FlexDirection;
+use egui::{Context, Id, Rect, Vec2};
+
+/// The layout of a flex container, as computed in the last pass it was shown.
+///
+/// This is useful to assert the layout math in tests, without having to render snapshots.
+/// Load it via [`FlexLayoutInfo::load`], with the id of the [`egui::Response`] returned by
+/// [`crate::Flex::show`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexLayoutInfo {
+    /// The direction of the flex container.
+    pub direction: FlexDirection,
+    /// The rect of the flex container.
+    pub rect: Rect,
+    /// The rows of the flex container. If the container doesn't wrap, there is a single row.
+    pub rows: Vec<FlexRowInfo>,
+}
+
+/// The layout of a single row in a [`FlexLayoutInfo`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexRowInfo {
+    /// The rect of the row.
+    pub rect: Rect,
+    /// The summed up basis of all items, including the gaps between them.
+    pub total_size: f32,
+    /// The summed up grow factors of all items.
+    pub total_grow: f32,
+    /// The space that was distributed between the items.
+    /// Negative if the items overflow the row and a shrinking item was shrunk.
+    pub extra_space: f32,
+    /// The items in this row.
+    pub items: Vec<FlexItemInfo>,
+}
+
+/// The layout of a single item in a [`FlexRowInfo`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexItemInfo {
+    /// The id of the item.
+    pub id: Id,
+    /// The size of the item in the main direction, before growing or shrinking (including margin).
+    /// This is either the [`crate::FlexItem::basis`] or the size of the content.
+    pub basis: f32,
+    /// The min size of the item (including margin).
+    pub min_size: Vec2,
+    /// The grow factor of the item.
+    pub grow: f32,
+    /// The length the item grew by in the main direction.
+    pub grown: f32,
+    /// The length the item was shrunk by in the main direction.
+    pub shrunk: f32,
+    /// The final rect of the item (including margin).
+    pub rect: Rect,
+}
+
+impl FlexItemInfo {
+    /// The final size of the item (including margin).
+    pub fn size(&self) -> Vec2 {
+        self.rect.size()
+    }
+}
+
+impl FlexLayoutInfo {
+    /// Load the layout of the flex container with the given response id.
+    /// Returns `None` if the container wasn't shown yet.
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data(|data| data.get_temp(Self::id(id)))
+    }
+
+    pub(crate) fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(id), self));
+    }
+
+    fn id(id: Id) -> Id {
+        id.with("egui_flex_layout_info")
+    }
+}
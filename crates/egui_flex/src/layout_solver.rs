@@ -0,0 +1,395 @@
+use crate::{Flex, FlexAlign, FlexAlignContent, FlexDirection, FlexJustify};
+use egui::emath::GuiRounding;
+use egui::{Align, Align2, Pos2, Rect, Vec2};
+use std::ops::Range;
+
+/// The measured size and flex settings of an item, the input of [`Flex::layout`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LayoutItem {
+    /// The size of the item's content (including margin).
+    pub size: Vec2,
+    /// The size of the item in the main direction, before growing or shrinking
+    /// (including margin). If `None`, the size of the content is used.
+    pub basis: Option<f32>,
+    /// The grow factor, see [`crate::FlexItem::grow`].
+    pub grow: f32,
+    /// See [`crate::FlexItem::shrink`].
+    pub shrink: bool,
+    /// See [`crate::FlexItem::push_end`].
+    pub push_end: bool,
+    /// See [`crate::FlexItem::align_self`].
+    pub align_self: FlexAlign,
+}
+
+impl LayoutItem {
+    /// Create a new item with the given content size (including margin).
+    pub fn new(size: impl Into<Vec2>) -> Self {
+        Self {
+            size: size.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the basis (including margin).
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Set the grow factor.
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Allow the item to shrink.
+    pub fn shrink(mut self) -> Self {
+        self.shrink = true;
+        self
+    }
+
+    /// Push this item to the end of the row.
+    pub fn push_end(mut self) -> Self {
+        self.push_end = true;
+        self
+    }
+
+    /// Set the alignment in the cross direction.
+    pub fn align_self(mut self, align_self: FlexAlign) -> Self {
+        self.align_self = align_self;
+        self
+    }
+
+    fn length(&self, direction: usize) -> f32 {
+        self.basis.unwrap_or(self.size[direction])
+    }
+}
+
+/// The result of [`Flex::layout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolvedLayout {
+    /// The rows of the layout. If the flex doesn't wrap, there is a single row.
+    pub rows: Vec<SolvedRow>,
+    /// The items, in the same order as they were passed in.
+    pub items: Vec<SolvedItem>,
+}
+
+/// A row in a [`SolvedLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolvedRow {
+    /// The rect of the row.
+    pub rect: Rect,
+    /// The indices of the items in this row.
+    pub items: Range<usize>,
+    /// The summed up basis of all items, including the gaps between them.
+    pub total_size: f32,
+    /// The summed up grow factors of all items.
+    pub total_grow: f32,
+    /// The space that is distributed between the items.
+    /// Negative if the items overflow the row and a shrinking item has to be shrunk.
+    pub extra_space: f32,
+    /// The space added before the first item, because of [`Flex::justify`].
+    pub extra_start_gap: f32,
+    /// The space added between the items (in addition to the gap), because of [`Flex::justify`].
+    pub extra_gap: f32,
+    /// The space added before the first item with [`crate::FlexItem::push_end`].
+    pub push_end_gap: f32,
+    /// The size of the largest item in the cross direction.
+    pub content_cross_size: f32,
+}
+
+/// An item in a [`SolvedLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolvedItem {
+    /// The index of the row the item is in.
+    pub row: usize,
+    /// The final rect of the item (including margin).
+    pub rect: Rect,
+    /// The length the item grew by in the main direction.
+    pub grown: f32,
+    /// The length the item was shrunk by in the main direction.
+    pub shrunk: f32,
+}
+
+/// The length an item with the given grow factor grows by.
+pub(crate) fn grow_length(extra_space: f32, grow: f32, total_grow: f32) -> f32 {
+    if grow > 0.0 && total_grow > 0.0 {
+        f32::max(extra_space * grow / total_grow, 0.0)
+    } else {
+        0.0
+    }
+}
+
+impl Flex {
+    /// Calculate the layout of the given items without showing anything, e.g. to test layout
+    /// outcomes or to reuse the flex math for a custom layout.
+    ///
+    /// The layout starts at [`Pos2::ZERO`]. [`Flex::width`] and [`Flex::height`] are resolved
+    /// against `available_size`, if they are not set the container is sized based on the content.
+    /// If [`Flex::gap`] is not set, egui's default item spacing is used.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui::Vec2;
+    /// # use egui_flex::{Flex, LayoutItem};
+    /// let layout = Flex::horizontal().width(340.0).gap(Vec2::ZERO).layout(
+    ///     Vec2::new(340.0, 100.0),
+    ///     &[
+    ///         LayoutItem::new([20.0, 20.0]).grow(1.0),
+    ///         LayoutItem::new([20.0, 20.0]).grow(2.0),
+    ///     ],
+    /// );
+    /// // The extra 300 points are split 1:2
+    /// assert_eq!(layout.items[0].rect.width(), 120.0);
+    /// assert_eq!(layout.items[1].rect.width(), 220.0);
+    /// ```
+    pub fn layout(&self, available_size: Vec2, items: &[LayoutItem]) -> SolvedLayout {
+        let direction = usize::from(self.direction == FlexDirection::Vertical);
+        let cross_direction = 1 - direction;
+        let gap = self
+            .gap
+            .unwrap_or_else(|| egui::Spacing::default().item_spacing);
+        let (width, height) = self.resolved_size();
+        let size = [
+            width.map(|w| w.get(available_size.x).round_ui()),
+            height.map(|h| h.get(available_size.y).round_ui()),
+        ];
+
+        let rows = self.solve_rows(items, available_size, size, gap, direction, Pos2::ZERO);
+
+        let mut solved_items = Vec::with_capacity(items.len());
+        for (row_idx, row) in rows.iter().enumerate() {
+            let row_items = &items[row.items.clone()];
+            let push_end_index = row_items.iter().position(|item| item.push_end);
+            let mut position = row.rect.min[direction];
+
+            for (idx, item) in row_items.iter().enumerate() {
+                position += if idx == 0 {
+                    row.extra_start_gap
+                } else {
+                    gap[direction] + row.extra_gap
+                };
+                if push_end_index == Some(idx) {
+                    position += row.push_end_gap;
+                }
+
+                let grown = grow_length(row.extra_space, item.grow, row.total_grow);
+                let shrunk = if item.shrink && row.extra_space < 0.0 {
+                    f32::min(-row.extra_space, item.length(direction) + grown)
+                } else {
+                    0.0
+                };
+
+                let mut item_size = item.size;
+                item_size[direction] = item.length(direction) + grown - shrunk;
+                // If everything is wrapped, the item is limited to the container's size
+                if self.wrap && row_items.len() == 1 {
+                    item_size[direction] =
+                        f32::min(item_size[direction], available_size[direction]);
+                }
+
+                let mut cell = row.rect;
+                cell.min[direction] = position;
+                cell.max[direction] = position + item_size[direction];
+                let rect = match item.align_self {
+                    FlexAlign::Stretch => cell,
+                    align => {
+                        let mut align2 = Align2::LEFT_TOP;
+                        align2[cross_direction] = match align {
+                            FlexAlign::Start => Align::Min,
+                            FlexAlign::End => Align::Max,
+                            _ => Align::Center,
+                        };
+                        align2.align_size_within_rect(item_size, cell)
+                    }
+                };
+
+                position += item_size[direction];
+                solved_items.push(SolvedItem {
+                    row: row_idx,
+                    rect,
+                    grown,
+                    shrunk,
+                });
+            }
+        }
+
+        SolvedLayout {
+            rows,
+            items: solved_items,
+        }
+    }
+
+    /// Break the items into rows and distribute the extra space of each row.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn solve_rows(
+        &self,
+        items: &[LayoutItem],
+        available_size: Vec2,
+        size: [Option<f32>; 2],
+        gap: Vec2,
+        direction: usize,
+        min_position: Pos2,
+    ) -> Vec<SolvedRow> {
+        let cross_direction = 1 - direction;
+
+        let available_length = size[direction].unwrap_or(available_size[direction]);
+        let gap_direction = gap[direction];
+
+        let mut rows = vec![];
+        let mut current_row = SolvedRow::empty(0);
+
+        let mut has_shrink = false;
+
+        for (idx, item) in items.iter().enumerate() {
+            if item.shrink && !self.wrap {
+                debug_assert!(!has_shrink, "Only one item may have shrink set to true");
+                has_shrink = true;
+            }
+
+            let item_length = item.length(direction);
+
+            if item_length + gap_direction + current_row.total_size > available_length
+                && !current_row.items.is_empty()
+                && self.wrap
+            {
+                rows.push(std::mem::replace(&mut current_row, SolvedRow::empty(idx)));
+            }
+
+            current_row.total_size += item_length;
+            if !current_row.items.is_empty() {
+                current_row.total_size += gap_direction;
+            }
+            current_row.total_grow += item.grow;
+            current_row.items.end = idx + 1;
+            if item.size[cross_direction] > current_row.content_cross_size {
+                current_row.content_cross_size = item.size[cross_direction];
+            }
+        }
+
+        if !current_row.items.is_empty() {
+            rows.push(current_row);
+        }
+
+        let target_cross_size = size[cross_direction];
+        let total_cross_size = rows.iter().map(|row| row.content_cross_size).sum::<f32>()
+            + (rows.len().max(1) - 1) as f32 * gap[cross_direction];
+        let extra_cross_space = target_cross_size.map_or(0.0, |target_cross_size| {
+            f32::max(target_cross_size - total_cross_size, 0.0)
+        });
+
+        let mut extra_cross_gap_start = 0.0;
+        let mut extra_cross_gap = 0.0;
+        let mut _extra_cross_gap_end = 0.0; // TODO: How to handle extra end space?
+        let mut extra_cross_space_per_row = 0.0;
+
+        let align_content = if self.wrap {
+            self.align_content
+        } else {
+            FlexAlignContent::Stretch
+        };
+        match align_content {
+            FlexAlignContent::Start => {
+                _extra_cross_gap_end = extra_cross_space;
+            }
+            FlexAlignContent::Stretch => {
+                extra_cross_space_per_row = extra_cross_space / rows.len() as f32;
+            }
+            FlexAlignContent::End => {
+                extra_cross_gap_start = extra_cross_space;
+            }
+            FlexAlignContent::Center => {
+                extra_cross_gap_start = extra_cross_space / 2.0;
+                _extra_cross_gap_end = extra_cross_space / 2.0;
+            }
+            FlexAlignContent::SpaceBetween => {
+                extra_cross_gap = extra_cross_space / (rows.len() as f32 - 1.0);
+            }
+            FlexAlignContent::SpaceAround => {
+                extra_cross_gap = extra_cross_space / rows.len() as f32;
+                extra_cross_gap_start = extra_cross_gap / 2.0;
+                _extra_cross_gap_end = extra_cross_gap / 2.0;
+            }
+        }
+
+        let mut row_position = min_position;
+
+        row_position[cross_direction] += extra_cross_gap_start;
+
+        let row_count = rows.len();
+        for row in &mut rows {
+            let mut row_size = Vec2::ZERO;
+            row_size[direction] = available_length;
+            row_size[cross_direction] = row.content_cross_size + extra_cross_space_per_row;
+            // TODO: Should there be an option to also limit in the cross dir?
+            // if size[cross_direction].is_some() {
+            //     row_size[cross_direction] =
+            //         f32::min(row_size[cross_direction], available_size[cross_direction]);
+            // }
+
+            row.rect = Rect::from_min_size(row_position, row_size);
+
+            row_position[cross_direction] +=
+                row_size[cross_direction] + gap[cross_direction] + extra_cross_gap;
+
+            let diff = available_length - row.total_size;
+            // Only grow items if a explicit size is set or if we wrapped
+            // If diff is < 0.0, we also set extra_space so we can shrink
+            if size[direction].is_some() || row_count > 1 || diff < 0.0 {
+                row.extra_space = diff;
+            }
+            // The space before the pushed item absorbs all remaining space, before it could be
+            // distributed to growing items
+            if items[row.items.clone()].iter().any(|item| item.push_end) && row.extra_space > 0.0 {
+                row.push_end_gap = row.extra_space.round_ui();
+                row.extra_space = 0.0;
+            }
+            if row.total_grow == 0.0 && row.extra_space > 0.0
+                // If size is none, the flex container should be sized based on the content and
+                // justify doesn't apply
+                && size[direction].is_some()
+            {
+                let item_count = row.items.len() as f32;
+                match self.justify {
+                    FlexJustify::Start => {}
+                    FlexJustify::End => {
+                        row.extra_start_gap = row.extra_space;
+                    }
+                    FlexJustify::Center => {
+                        row.extra_start_gap = row.extra_space / 2.0;
+                    }
+                    FlexJustify::SpaceBetween => {
+                        row.extra_gap = row.extra_space / (item_count - 1.0);
+                    }
+                    FlexJustify::SpaceAround => {
+                        row.extra_gap = row.extra_space / item_count;
+                        row.extra_start_gap = row.extra_gap / 2.0;
+                    }
+                    FlexJustify::SpaceEvenly => {
+                        row.extra_gap = row.extra_space / (item_count + 1.0);
+                        row.extra_start_gap = row.extra_gap;
+                    }
+                }
+                row.extra_gap = f32::max(row.extra_gap.round_ui(), 0.0);
+                row.extra_start_gap = f32::max(row.extra_start_gap.round_ui(), 0.0);
+            }
+        }
+        rows
+    }
+}
+
+impl SolvedRow {
+    fn empty(start: usize) -> Self {
+        Self {
+            rect: Rect::NOTHING,
+            items: start..start,
+            total_size: 0.0,
+            total_grow: 0.0,
+            extra_space: 0.0,
+            extra_start_gap: 0.0,
+            extra_gap: 0.0,
+            push_end_gap: 0.0,
+            content_cross_size: 0.0,
+        }
+    }
+}
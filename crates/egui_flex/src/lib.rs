@@ -3,12 +3,19 @@
 #![warn(missing_docs)]
 
 mod flex_widget;
+#[cfg(feature = "introspection")]
+mod introspection;
+mod layout_solver;
 
 pub use crate::flex_widget::FlexWidget;
+#[cfg(feature = "introspection")]
+pub use crate::introspection::{FlexItemInfo, FlexLayoutInfo, FlexRowInfo};
+pub use crate::layout_solver::{LayoutItem, SolvedItem, SolvedLayout, SolvedRow};
 use egui::emath::{GuiRounding, TSTransform};
+use egui::layers::ShapeIdx;
 use egui::{
-    Align, Align2, Direction, Frame, Id, InnerResponse, Layout, Margin, Pos2, Rect, Response,
-    Sense, Ui, UiBuilder, Vec2, Widget,
+    Align, Align2, Context, Direction, Frame, Id, InnerResponse, LayerId, Layout, Margin, Pos2,
+    Rect, Response, Sense, Shape, Ui, UiBuilder, Vec2, Widget,
 };
 use std::fmt::Debug;
 use std::mem;
@@ -89,6 +96,7 @@ impl Size {
 pub struct Flex {
     id_salt: Option<Id>,
     direction: FlexDirection,
+    auto_direction: bool,
     justify: FlexJustify,
     align_content: FlexAlignContent,
     gap: Option<Vec2>,
@@ -96,6 +104,7 @@ pub struct Flex {
     wrap: bool,
     width: Option<Size>,
     height: Option<Size>,
+    cross_size: Option<Size>,
 }
 
 type FrameBuilder<'a> = Box<dyn FnOnce(&Ui, &Response) -> (Frame, TSTransform) + 'a>;
@@ -128,12 +137,16 @@ struct FlexItemInner {
     basis: Option<f32>,
     align_self: Option<FlexAlign>,
     align_content: Option<Align2>,
+    /// Set by [`FlexItem::content_align`]: widgets keep their intrinsic size
+    intrinsic_content: bool,
     shrink: bool,
+    push_end: bool,
     frame: Option<Frame>,
     transform: Option<TSTransform>,
     content_id: Option<Id>,
     sense: Option<Sense>,
     min_size: [Option<f32>; 2],
+    z: Option<i32>,
 }
 
 /// Only the things that are relevant on the next frame
@@ -142,6 +155,7 @@ struct FlexItemState {
     grow: Option<f32>,
     basis: Option<f32>,
     shrink: bool,
+    push_end: bool,
     margin: Margin,
     content_id: Option<Id>,
 }
@@ -153,7 +167,9 @@ impl FlexItemInner {
             basis: self.basis.or(b.basis),
             align_self: self.align_self.or(b.align_self),
             align_content: self.align_content.or(b.align_content),
+            intrinsic_content: self.intrinsic_content || b.intrinsic_content,
             shrink: self.shrink || b.shrink,
+            push_end: self.push_end || b.push_end,
             frame: self.frame.or(b.frame),
             transform: self.transform.or(b.transform),
             content_id: self.content_id.or(b.content_id),
@@ -162,6 +178,7 @@ impl FlexItemInner {
                 self.min_size[0].or(b.min_size[0]),
                 self.min_size[1].or(b.min_size[1]),
             ],
+            z: self.z.or(b.z),
         }
     }
 
@@ -170,6 +187,7 @@ impl FlexItemInner {
             grow: self.grow,
             basis: self.basis,
             shrink: self.shrink,
+            push_end: self.push_end,
             margin: self.frame.map_or(Margin::ZERO, |f| f.total_margin().into()),
             content_id: self.content_id,
         }
@@ -212,12 +230,27 @@ impl<'a> FlexItem<'a> {
 
     /// If `align_self` is stretch, how do we align the content?
     ///
+    /// Unlike [`FlexItem::content_align`], widgets added via [`FlexInstance::add`] still fill
+    /// the whole item.
+    ///
     /// Default is `center`.
     pub fn align_self_content(mut self, align_self_content: Align2) -> Self {
         self.inner.align_content = Some(align_self_content);
         self
     }
 
+    /// Where to place the content within the item, if the item is bigger than its content
+    /// (e.g. because it grows or is stretched).
+    ///
+    /// By default, [`FlexInstance::add_ui`] content is centered and widgets added via
+    /// [`FlexInstance::add`] fill the whole item. If this is set, widgets keep their intrinsic
+    /// size and are aligned like other content.
+    pub fn content_align(mut self, content_align: Align2) -> Self {
+        self.inner.align_content = Some(content_align);
+        self.inner.intrinsic_content = true;
+        self
+    }
+
     /// Shrink this item if there isn't enough space.
     ///
     /// Note: You may only ever set this on a single item in a flex container.
@@ -226,6 +259,18 @@ impl<'a> FlexItem<'a> {
         self
     }
 
+    /// Push this item (and all items after it in the same row) to the end of the row, e.g. to
+    /// have left and right aligned items in a toolbar.
+    /// All remaining space is put before this item, so items in the row won't grow and
+    /// [`Flex::justify`] has no effect. Like justify, this only applies if the flex container has
+    /// a size in the main direction (or wraps).
+    ///
+    /// See also [`FlexInstance::spacer`].
+    pub fn push_end(mut self) -> Self {
+        self.inner.push_end = true;
+        self
+    }
+
     /// Set the frame of the item.
     pub fn frame(mut self, frame: Frame) -> Self {
         self.inner.frame = Some(frame);
@@ -282,6 +327,18 @@ impl<'a> FlexItem<'a> {
         self.inner.min_size[1] = min_height.into();
         self
     }
+
+    /// Set the paint order of the item within the flex container, like the css `z-index`.
+    /// Items with a higher z are painted after (on top of) their siblings, e.g. so a drop shadow
+    /// or focus ring isn't covered by the next item. Items with the same z are painted in the
+    /// order they were added. The default is 0.
+    ///
+    /// This only changes the paint order, which overlapping item receives the pointer input
+    /// is not affected. Content shown in other layers (e.g. popups) is not affected either.
+    pub fn z(mut self, z: i32) -> Self {
+        self.inner.z = Some(z);
+        self
+    }
 }
 
 impl Flex {
@@ -306,6 +363,22 @@ impl Flex {
         self
     }
 
+    /// Choose the direction based on the aspect ratio of the available space: horizontal if it
+    /// is wider than high, vertical otherwise. Useful for responsive layouts, e.g. a split view
+    /// or a card with an image and a caption.
+    ///
+    /// To prevent the direction from flipping back and forth while resizing, it only changes
+    /// once the width is [`Self::AUTO_DIRECTION_HYSTERESIS`] times the height (or vice versa).
+    /// This overrides [`Self::direction`].
+    pub fn auto_direction(mut self) -> Self {
+        self.auto_direction = true;
+        self
+    }
+
+    /// How much wider than high (or vice versa) the available space has to be before
+    /// [`Self::auto_direction`] changes the direction.
+    pub const AUTO_DIRECTION_HYSTERESIS: f32 = 1.2;
+
     /// Set how to justify the content (alignment in the main axis).
     /// This will only have an effect if all items have grow set to 0.
     pub fn justify(mut self, justify: FlexJustify) -> Self {
@@ -320,6 +393,7 @@ impl Flex {
     }
 
     /// If `align_items` is stretch, how do we align the item content?
+    /// See [`FlexItem::align_self_content`].
     pub fn align_items_content(mut self, align_item_content: Align2) -> Self {
         self.default_item.align_content = Some(align_item_content);
         self
@@ -426,6 +500,17 @@ impl Flex {
         self
     }
 
+    /// Set the size of the flex container in the cross direction, i.e. the height of a
+    /// horizontal and the width of a vertical flex container.
+    ///
+    /// The size in the main direction is unaffected, so this can be used to e.g. give a toolbar
+    /// a fixed height without knowing its direction at the call site.
+    /// This takes precedence over [`Self::width`] or [`Self::height`] for the cross direction.
+    pub fn cross_size(mut self, cross_size: impl Into<Size>) -> Self {
+        self.cross_size = Some(cross_size.into());
+        self
+    }
+
     /// The width of the flex container will be set to the width of the content, but not larger
     /// than the available width (unless wrap is set to false).
     ///
@@ -444,7 +529,30 @@ impl Flex {
         self
     }
 
+    /// The direction [`Self::auto_direction`] picks for `available_size`, given the direction
+    /// it picked in the last pass.
+    fn pick_direction(available_size: Vec2, previous: Option<FlexDirection>) -> FlexDirection {
+        let threshold = match previous {
+            None => 1.0,
+            Some(FlexDirection::Horizontal) => 1.0 / Self::AUTO_DIRECTION_HYSTERESIS,
+            Some(FlexDirection::Vertical) => Self::AUTO_DIRECTION_HYSTERESIS,
+        };
+        if available_size.x >= available_size.y * threshold {
+            FlexDirection::Horizontal
+        } else {
+            FlexDirection::Vertical
+        }
+    }
+
     #[track_caller]
+    /// The width and height, with [`Self::cross_size`] applied
+    fn resolved_size(&self) -> (Option<Size>, Option<Size>) {
+        match self.direction {
+            FlexDirection::Horizontal => (self.width, self.cross_size.or(self.height)),
+            FlexDirection::Vertical => (self.cross_size.or(self.width), self.height),
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
     fn show_inside<R>(
         mut self,
@@ -458,7 +566,7 @@ impl Flex {
         } else {
             ui.auto_id_with("flex")
         };
-        let previous_state: FlexState = ui
+        let mut previous_state: FlexState = ui
             .ctx()
             .memory(|mem| mem.data.get_temp(id).clone().unwrap_or_default());
 
@@ -470,14 +578,23 @@ impl Flex {
             panic!("Id clash in flex container! Id: {id:?}");
         }
 
-        let width = self.width.or_else(|| {
+        if self.auto_direction {
+            self.direction = Self::pick_direction(
+                ui.available_rect_before_wrap().size(),
+                previous_state.direction,
+            );
+        }
+
+        let (width, height) = self.resolved_size();
+
+        let width = width.or_else(|| {
             if ui.layout().horizontal_justify() {
                 Some(Size::Percent(1.0))
             } else {
                 None
             }
         });
-        let height = self.height.or_else(|| {
+        let height = height.or_else(|| {
             if ui.layout().vertical_justify() {
                 Some(Size::Percent(1.0))
             } else {
@@ -485,15 +602,34 @@ impl Flex {
             }
         });
 
-        let layout = match self.direction {
+        let item_layout = match self.direction {
             FlexDirection::Horizontal => Layout::left_to_right(Align::Min),
             FlexDirection::Vertical => Layout::top_down(Align::Min),
         };
 
+        // If the parent lays out its content right to left or bottom up, the flex container
+        // has to be placed at the end of the available space, next to the parent's cursor.
+        // The container layout is only used for this, items are always laid out with `item_layout`.
+        let parent_direction = ui.layout().main_dir();
+        let align_to_end = [
+            parent_direction == Direction::RightToLeft,
+            parent_direction == Direction::BottomUp,
+        ];
+        let layout = match (self.direction, align_to_end) {
+            (FlexDirection::Horizontal, [true, _]) => Layout::right_to_left(Align::Min),
+            (FlexDirection::Horizontal, [_, true]) => Layout::left_to_right(Align::Max),
+            (FlexDirection::Vertical, [true, _]) => Layout::top_down(Align::Max),
+            (FlexDirection::Vertical, [_, true]) => Layout::bottom_up(Align::Min),
+            _ => item_layout,
+        };
+
         let mut state_changed = false;
 
         let parent_rect = ui.max_rect();
 
+        #[cfg(feature = "introspection")]
+        let mut layout_info = None;
+
         let r = ui.scope_builder(
             UiBuilder::new()
                 .layout(layout)
@@ -521,17 +657,20 @@ impl Flex {
                 let cross_direction = 1 - direction;
 
                 // TODO: I think it should be possible to cache the layout
-                let rows = self.layout_rows(
+                let mut rows = self.layout_rows(
                     &previous_state,
                     available_size,
                     size,
                     gap,
                     direction,
-                    ui.min_rect().min,
+                    ui.max_rect().min,
                 );
+                Self::align_rows_to_end(&mut rows, align_to_end, available_size, size, direction);
 
                 let max_item_size = max_item_size.unwrap_or(available_size).round_ui();
 
+                let paint_start = next_shape_idx(ui.ctx(), ui.layer_id());
+
                 let mut instance = FlexInstance {
                     current_row: 0,
                     current_row_index: 0,
@@ -539,21 +678,36 @@ impl Flex {
                     state: FlexState {
                         items: vec![],
                         max_item_size,
+                        item_rects: vec![],
+                        direction: Some(self.direction),
                         frame_time,
                         passes,
                     },
                     direction,
-                    row_ui: FlexInstance::row_ui(ui, rows.first()),
+                    row_ui: FlexInstance::row_ui(ui, rows.first(), item_layout),
+                    item_layout,
                     ui,
                     rows,
                     max_item_size,
                     last_max_item_size: previous_state.max_item_size,
                     item_spacing: original_item_spacing,
                     size,
+                    push_next_to_end: false,
+                    #[cfg(feature = "introspection")]
+                    item_infos: vec![],
+                    paint_start,
+                    z_ranges: vec![],
+                    items_end: paint_start,
+                    placeholders: vec![],
+                    last_items: mem::take(&mut previous_state.items),
+                    last_item_rects: mem::take(&mut previous_state.item_rects),
+                    item_rects: vec![],
                 };
 
                 let r = f(&mut instance);
 
+                instance.apply_z_order();
+
                 let mut min_size =
                     instance
                         .state
@@ -580,12 +734,30 @@ impl Flex {
                 //     current
                 // });
 
-                if (&previous_state.items, &previous_state.max_item_size)
+                if (&instance.last_items, &previous_state.max_item_size)
                     != (&instance.state.items, &instance.state.max_item_size)
                 {
                     state_changed = true;
                 }
 
+                // The rects of a pass that is laid out again aren't final, so popups stay
+                // anchored to the last settled layout
+                instance.state.item_rects = if state_changed {
+                    mem::take(&mut instance.last_item_rects)
+                } else {
+                    let origin = instance.ui.max_rect().min.to_vec2();
+                    instance
+                        .item_rects
+                        .iter()
+                        .map(|rect| rect.translate(-origin))
+                        .collect()
+                };
+
+                #[cfg(feature = "introspection")]
+                {
+                    layout_info = Some(instance.layout_info());
+                }
+
                 instance.ui.ctx().memory_mut(|mem| {
                     mem.data.insert_temp(id, instance.state);
                 });
@@ -599,6 +771,12 @@ impl Flex {
             },
         );
 
+        #[cfg(feature = "introspection")]
+        if let Some(mut layout_info) = layout_info {
+            layout_info.rect = r.response.rect;
+            layout_info.store(ui.ctx(), r.response.id);
+        }
+
         // We move this down here because `#[track_caller]` doesn't work with closures
         if state_changed {
             ui.ctx()
@@ -609,9 +787,8 @@ impl Flex {
         (r.inner.0, InnerResponse::new(r.inner.1, r.response))
     }
 
-    #[allow(clippy::too_many_lines)]
     fn layout_rows(
-        &mut self,
+        &self,
         state: &FlexState,
         available_size: Vec2,
         size: [Option<f32>; 2],
@@ -619,156 +796,101 @@ impl Flex {
         direction: usize,
         min_position: Pos2,
     ) -> Vec<RowData> {
-        let cross_direction = 1 - direction;
-
-        let available_length = size[direction].unwrap_or(available_size[direction]);
-        let gap_direction = gap[direction];
-
-        let mut rows = vec![];
-        let mut current_row = RowData::default();
-
-        let mut shrink_index = None;
-
-        for (idx, item) in state.items.iter().enumerate() {
-            if item.config.shrink && !self.wrap {
-                debug_assert!(
-                    shrink_index.is_none(),
-                    "Only one item may have shrink set to true"
-                );
-                shrink_index = Some(idx);
-            }
-
-            let item_length = item
-                .config
-                .basis
-                .map_or(item.min_size_with_margin()[direction], |basis| {
-                    basis + item.config.margin.sum()[direction]
-                });
+        let items: Vec<_> = state
+            .items
+            .iter()
+            .map(|item| LayoutItem {
+                size: item.min_size_with_margin(),
+                basis: item
+                    .config
+                    .basis
+                    .map(|basis| basis + item.config.margin.sum()[direction]),
+                grow: item.config.grow.unwrap_or(0.0),
+                shrink: item.config.shrink,
+                push_end: item.config.push_end,
+                align_self: FlexAlign::default(),
+            })
+            .collect();
+
+        self.solve_rows(&items, available_size, size, gap, direction, min_position)
+            .into_iter()
+            .map(|row| {
+                let items = state.items[row.items.clone()].to_vec();
+                RowData {
+                    push_end_index: items.iter().position(|item| item.config.push_end),
+                    items,
+                    total_size: row.total_size,
+                    total_grow: row.total_grow,
+                    extra_space: row.extra_space,
+                    extra_gap: row.extra_gap,
+                    extra_start_gap: row.extra_start_gap,
+                    push_end_gap: row.push_end_gap,
+                    cross_size_with_extra_space: row.rect.size()[1 - direction],
+                    rect: Some(row.rect),
+                    final_rect: None,
+                }
+            })
+            .collect()
+    }
 
-            if item_length + gap_direction + current_row.total_size > available_length
-                && !current_row.items.is_empty()
-                && self.wrap
-            {
-                rows.push(mem::take(&mut current_row));
-            }
+    /// Moves the rows to the end of the available space on the axes where `align_to_end` is set.
+    /// Within the rows, the items keep their order and alignment.
+    fn align_rows_to_end(
+        rows: &mut [RowData],
+        align_to_end: [bool; 2],
+        available_size: Vec2,
+        size: [Option<f32>; 2],
+        direction: usize,
+    ) {
+        let cross_direction = 1 - direction;
+        let row_count = rows.len();
 
-            current_row.total_size += item_length;
-            if !current_row.items.is_empty() {
-                current_row.total_size += gap_direction;
-            }
-            current_row.total_grow += item.config.grow.unwrap_or(0.0);
-            current_row.items.push(item.clone());
-            if item.min_size_with_margin()[cross_direction] > current_row.cross_size {
-                current_row.cross_size = item.min_size_with_margin()[cross_direction];
+        if align_to_end[direction] {
+            for row in rows.iter_mut() {
+                if let Some(rect) = &mut row.rect {
+                    // If the size is content driven, the row is only as long as its items
+                    let length = if size[direction].is_none() && row_count == 1 {
+                        f32::min(row.total_size, rect.size()[direction]).round_ui()
+                    } else {
+                        rect.size()[direction]
+                    };
+                    rect.min[direction] += f32::max(available_size[direction] - length, 0.0);
+                    rect.max[direction] = rect.min[direction] + length;
+                }
             }
         }
 
-        if !current_row.items.is_empty() {
-            rows.push(current_row);
-        }
-
-        let target_cross_size = size[cross_direction];
-        let total_cross_size = rows.iter().map(|row| row.cross_size).sum::<f32>()
-            + (rows.len().max(1) - 1) as f32 * gap[cross_direction];
-        let extra_cross_space = target_cross_size.map_or(0.0, |target_cross_size| {
-            f32::max(target_cross_size - total_cross_size, 0.0)
-        });
-
-        let mut extra_cross_gap_start = 0.0;
-        let mut extra_cross_gap = 0.0;
-        let mut _extra_cross_gap_end = 0.0; // TODO: How to handle extra end space?
-        let mut extra_cross_space_per_row = 0.0;
-
-        if !self.wrap {
-            self.align_content = FlexAlignContent::Stretch;
-        }
-        match self.align_content {
-            FlexAlignContent::Start => {
-                _extra_cross_gap_end = extra_cross_space;
-            }
-            FlexAlignContent::Stretch => {
-                extra_cross_space_per_row = extra_cross_space / rows.len() as f32;
-            }
-            FlexAlignContent::End => {
-                extra_cross_gap_start = extra_cross_space;
-            }
-            FlexAlignContent::Center => {
-                extra_cross_gap_start = extra_cross_space / 2.0;
-                _extra_cross_gap_end = extra_cross_space / 2.0;
-            }
-            FlexAlignContent::SpaceBetween => {
-                extra_cross_gap = extra_cross_space / (rows.len() as f32 - 1.0);
-            }
-            FlexAlignContent::SpaceAround => {
-                extra_cross_gap = extra_cross_space / rows.len() as f32;
-                extra_cross_gap_start = extra_cross_gap / 2.0;
-                _extra_cross_gap_end = extra_cross_gap / 2.0;
-            }
-        };
-
-        let mut row_position = min_position;
-
-        row_position[cross_direction] += extra_cross_gap_start;
-
-        let row_count = rows.len();
-        for (_idx, row) in &mut rows.iter_mut().enumerate() {
-            let mut row_size = Vec2::ZERO;
-            row_size[direction] = available_length;
-            row_size[cross_direction] = row.cross_size + extra_cross_space_per_row;
-            // TODO: Should there be an option to also limit in the cross dir?
-            // if size[cross_direction].is_some() {
-            //     row_size[cross_direction] =
-            //         f32::min(row_size[cross_direction], available_size[cross_direction]);
-            // }
-
-            row.cross_size_with_extra_space = row_size[cross_direction];
-            row.rect = Some(Rect::from_min_size(row_position, row_size));
-
-            row_position[cross_direction] +=
-                row_size[cross_direction] + gap[cross_direction] + extra_cross_gap;
-
-            let diff = available_length - row.total_size;
-            // Only grow items if a explicit size is set or if we wrapped
-            // If diff is < 0.0, we also set extra_space so we can shrink
-            if size[direction].is_some() || row_count > 1 || diff < 0.0 {
-                row.extra_space = diff;
-            }
-            if row.total_grow == 0.0 && row.extra_space > 0.0
-                // If size is none, the flex container should be sized based on the content and
-                // justify doesn't apply
-                && size[direction].is_some()
-            {
-                match self.justify {
-                    FlexJustify::Start => {}
-                    FlexJustify::End => {
-                        row.extra_start_gap = row.extra_space;
-                    }
-                    FlexJustify::Center => {
-                        row.extra_start_gap = row.extra_space / 2.0;
-                    }
-                    FlexJustify::SpaceBetween => {
-                        row.extra_gap = row.extra_space / (row.items.len() as f32 - 1.0);
-                    }
-                    FlexJustify::SpaceAround => {
-                        row.extra_gap = row.extra_space / row.items.len() as f32;
-                        row.extra_start_gap = row.extra_gap / 2.0;
-                    }
-                    FlexJustify::SpaceEvenly => {
-                        row.extra_gap = row.extra_space / (row.items.len() as f32 + 1.0);
-                        row.extra_start_gap = row.extra_gap;
-                    }
+        if align_to_end[cross_direction] {
+            let cross_range = rows.iter().filter_map(|row| row.rect).fold(
+                None,
+                |range: Option<(f32, f32)>, rect| {
+                    let (min, max) =
+                        range.unwrap_or((rect.min[cross_direction], rect.max[cross_direction]));
+                    Some((
+                        f32::min(min, rect.min[cross_direction]),
+                        f32::max(max, rect.max[cross_direction]),
+                    ))
+                },
+            );
+            if let Some((min, max)) = cross_range {
+                let mut offset = Vec2::ZERO;
+                offset[cross_direction] =
+                    f32::max(available_size[cross_direction] - (max - min), 0.0);
+                for row in rows.iter_mut() {
+                    row.rect = row.rect.map(|rect| rect.translate(offset));
                 }
-                row.extra_gap = f32::max(row.extra_gap.round_ui(), 0.0);
-                row.extra_start_gap = f32::max(row.extra_start_gap.round_ui(), 0.0);
             }
         }
-        rows
     }
 
     /// Show the flex ui. If [`Self::wrap`] is `true`, it will try to stay within [`Ui::max_rect`].
     ///
     /// Note: You will likely get weird results when showing this within a `Ui::horizontal` layout,
     /// since it limits the `max_rect` to some small value. Use `Ui::horizontal_top` instead.
+    ///
+    /// In a right to left or bottom up parent layout, the flex container is placed at the end of
+    /// the available space (next to the previous widget). The items are still laid out from the
+    /// start of the container, so [`FlexJustify::Start`] is always the left / top.
     #[track_caller]
     pub fn show<R>(self, ui: &mut Ui, f: impl FnOnce(&mut FlexInstance) -> R) -> InnerResponse<R> {
         self.show_inside(ui, None, None, f).1
@@ -794,7 +916,10 @@ struct RowData {
     extra_space: f32,
     extra_gap: f32,
     extra_start_gap: f32,
-    cross_size: f32,
+    /// The index of the first item with [`FlexItem::push_end`]
+    push_end_index: Option<usize>,
+    /// The space added before the item at `push_end_index`
+    push_end_gap: f32,
     cross_size_with_extra_space: f32,
     rect: Option<Rect>,
     final_rect: Option<Rect>,
@@ -819,6 +944,12 @@ impl ItemState {
 struct FlexState {
     items: Vec<ItemState>,
     max_item_size: Vec2,
+    /// The rects of the items (relative to the container) in the last pass whose layout didn't
+    /// change, see [`FlexInstance::popup_anchor`]
+    item_rects: Vec<Rect>,
+    /// The direction of the container, so [`Flex::auto_direction`] only changes it once the
+    /// aspect ratio changed enough
+    direction: Option<FlexDirection>,
     // We use this to keep track if there is a id clash.
     frame_time: f64,
     passes: u64,
@@ -834,18 +965,123 @@ pub struct FlexInstance<'a> {
     rows: Vec<RowData>,
     direction: usize,
     row_ui: Ui,
+    item_layout: Layout,
     max_item_size: Vec2,
     last_max_item_size: Vec2,
     // Original item spacing to store when showing children
     item_spacing: Vec2,
     size: [Option<f32>; 2],
+    // Set by FlexInstance::spacer, the next item is pushed to the end
+    push_next_to_end: bool,
+    // The row index and layout of each item shown this pass
+    #[cfg(feature = "introspection")]
+    item_infos: Vec<(usize, FlexItemInfo)>,
+    // The index of the first shape painted in the container
+    paint_start: usize,
+    // The z and the range of shapes of each item with a z, see FlexItem::z
+    z_ranges: Vec<(i32, usize, usize)>,
+    // The index after the last shape of the previous item
+    items_end: usize,
+    // The Noop shapes painted between the items, these may be placeholders that are filled in
+    // after the items were added
+    placeholders: Vec<usize>,
+    // The items of the last pass, the rows were laid out for these
+    last_items: Vec<ItemState>,
+    // The item rects of the last settled layout, relative to the container
+    last_item_rects: Vec<Rect>,
+    // The rect of each item shown this pass
+    item_rects: Vec<Rect>,
 }
 
 impl FlexInstance<'_> {
-    fn row_ui(parent: &mut Ui, row: Option<&RowData>) -> Ui {
+    fn row_ui(parent: &mut Ui, row: Option<&RowData>, layout: Layout) -> Ui {
         let rect = row.map_or(parent.max_rect(), |row| row.rect.unwrap());
 
-        parent.new_child(UiBuilder::new().max_rect(rect))
+        parent.new_child(UiBuilder::new().max_rect(rect).layout(layout))
+    }
+
+    #[cfg(feature = "introspection")]
+    fn layout_info(&mut self) -> FlexLayoutInfo {
+        let mut item_infos = mem::take(&mut self.item_infos).into_iter().peekable();
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| FlexRowInfo {
+                rect: row.final_rect.or(row.rect).unwrap_or(Rect::NOTHING),
+                total_size: row.total_size,
+                total_grow: row.total_grow,
+                extra_space: row.extra_space,
+                items: std::iter::from_fn(|| item_infos.next_if(|(row, _)| *row == idx))
+                    .map(|(_, item)| item)
+                    .collect(),
+            })
+            .collect();
+
+        FlexLayoutInfo {
+            direction: self.flex.direction,
+            rect: Rect::NOTHING,
+            rows,
+        }
+    }
+
+    /// Reorders the shapes painted in the container by the z of their items, see [`FlexItem::z`].
+    /// Shapes that don't belong to an item with a z (e.g. painted via [`Self::painter`]) have a
+    /// z of 0.
+    ///
+    /// [`Shape::Noop`] shapes painted between the items may be placeholders whose [`ShapeIdx`]
+    /// is still held by someone (e.g. a [`Frame`] around some of the items that is filled in
+    /// later), so they are kept at their index and no shape is moved past them: only the shapes
+    /// between two placeholders are reordered. Noop shapes painted by an item (e.g. the
+    /// background slot of a [`egui::TextEdit`] without a frame) are moved with the item.
+    fn apply_z_order(&mut self) {
+        if self.z_ranges.is_empty() {
+            return;
+        }
+        self.record_placeholders();
+        let z_ranges = mem::take(&mut self.z_ranges);
+        let placeholders = mem::take(&mut self.placeholders);
+        let start = self.paint_start;
+        self.ui.ctx().graphics_mut(|graphics| {
+            let list = graphics.entry(self.ui.layer_id());
+            let mut shapes: Vec<_> = list
+                .all_entries()
+                .enumerate()
+                .skip(start)
+                .map(|(idx, shape)| {
+                    let z = z_ranges
+                        .iter()
+                        .find(|(_, start, end)| (*start..*end).contains(&idx))
+                        .map_or(0, |(z, _, _)| *z);
+                    (idx, z, shape.clone())
+                })
+                .collect();
+            for segment in shapes.split_mut(|(idx, _, _)| placeholders.contains(idx)) {
+                // The sort is stable, so shapes with the same z keep their order
+                segment.sort_by_key(|(_, z, _)| *z);
+            }
+            for (idx, (_, _, shape)) in shapes.into_iter().enumerate() {
+                list.mutate_shape(ShapeIdx(start + idx), |s| *s = shape);
+            }
+        });
+    }
+
+    /// Records the [`Shape::Noop`] shapes painted since the end of the previous item, see
+    /// [`Self::apply_z_order`].
+    fn record_placeholders(&mut self) {
+        let layer_id = self.row_ui.layer_id();
+        let start = self.items_end;
+        self.ui.ctx().graphics(|graphics| {
+            if let Some(list) = graphics.get(layer_id) {
+                self.placeholders.extend(
+                    list.all_entries()
+                        .enumerate()
+                        .skip(start)
+                        .filter(|(_, clipped)| matches!(clipped.shape, Shape::Noop))
+                        .map(|(idx, _)| idx),
+                );
+            }
+        });
     }
 
     /// Get the direction of the flex container.
@@ -898,6 +1134,49 @@ impl FlexInstance<'_> {
         self.row_ui.spacing()
     }
 
+    /// The rect of the item at `idx` (counting all items added to this container), to anchor a
+    /// popup, tooltip or [`egui::Area`] to it, e.g. a dropdown menu below a toolbar button.
+    ///
+    /// Unlike the rect of the item's [`Response`], this is stable while the container lays out
+    /// its items again after they changed (e.g. a label got longer): until the new layout is
+    /// final, the rect of the last settled layout is returned (moved along with the container),
+    /// so popups don't jump for a frame. Call this after the item was added, before that the
+    /// settled rect is returned. Returns `None` if the item has never been shown.
+    ///
+    /// ```
+    /// # use egui::{Area, Order};
+    /// # use egui_flex::{item, Flex};
+    /// # egui::__run_test_ui(|ui| {
+    /// Flex::horizontal().show(ui, |flex| {
+    ///     flex.add(item(), egui::Button::new("File"));
+    ///     let edit = flex.add(item(), egui::Button::new("Edit"));
+    ///     if let Some(anchor) = flex.popup_anchor(1) {
+    ///         Area::new(edit.id.with("menu"))
+    ///             .order(Order::Foreground)
+    ///             .fixed_pos(anchor.left_bottom())
+    ///             .show(flex.ui().ctx(), |ui| {
+    ///                 ui.label("Undo");
+    ///             });
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn popup_anchor(&self, idx: usize) -> Option<Rect> {
+        // The rows were laid out for the items of the last pass, so if the item and the ones
+        // before it didn't change, it is already at its final position
+        let settled = self.max_item_size == self.last_max_item_size
+            && idx < self.state.items.len()
+            && self.state.items.get(..=idx) == self.last_items.get(..=idx);
+        if settled {
+            self.item_rects.get(idx).copied()
+        } else {
+            let origin = self.ui.max_rect().min.to_vec2();
+            self.last_item_rects
+                .get(idx)
+                .map(|rect| rect.translate(origin))
+        }
+    }
+
     /// Create a child ui to e.g. show a overlay over some component
     pub fn new_child(&mut self, ui_builder: UiBuilder) -> Ui {
         self.ui.new_child(ui_builder)
@@ -905,6 +1184,8 @@ impl FlexInstance<'_> {
 
     #[allow(clippy::too_many_lines)] // TODO: Refactor this to be more readable
     fn add_container<R>(&mut self, mut item: FlexItem, content: ContentFn<R>) -> InnerResponse<R> {
+        self.record_placeholders();
+
         let row = self.rows.get_mut(self.current_row);
 
         if let Some(row) = &row {
@@ -913,13 +1194,23 @@ impl FlexInstance<'_> {
             } else {
                 self.row_ui.add_space(row.extra_gap);
             }
+            if row.push_end_index == Some(self.current_row_index) {
+                self.row_ui.add_space(row.push_end_gap);
+            }
             row.extra_start_gap
         } else {
             0.0
         };
 
+        if mem::take(&mut self.push_next_to_end) {
+            item.inner.push_end = true;
+        }
         item.inner = item.inner.or(self.flex.default_item);
 
+        let layer_id = self.row_ui.layer_id();
+        let z = item.inner.z.filter(|z| *z != 0);
+        let paint_start = next_shape_idx(self.ui.ctx(), layer_id);
+
         let res = self.row_ui.scope_builder(
             UiBuilder::new().sense(item.inner.sense.unwrap_or(Sense::hover())),
             |ui| {
@@ -936,16 +1227,11 @@ impl FlexInstance<'_> {
                     // TODO: Handle when this is not set (Why doesn't this fail?)
                     let item_state = row.items.get_mut(self.current_row_index).unwrap();
 
-                    let extra_length =
-                        if item_state.config.grow.unwrap_or(0.0) > 0.0 && row.total_grow > 0.0 {
-                            f32::max(
-                                row.extra_space * item_state.config.grow.unwrap_or(0.0)
-                                    / row.total_grow,
-                                0.0,
-                            )
-                        } else {
-                            0.0
-                        };
+                    let extra_length = layout_solver::grow_length(
+                        row.extra_space,
+                        item_state.config.grow.unwrap_or(0.0),
+                        row.total_grow,
+                    );
 
                     let do_shrink = item_state.config.shrink && row.extra_space < 0.0;
 
@@ -1021,9 +1307,9 @@ impl FlexInstance<'_> {
                     if let Some(basis) = item.basis {
                         let mut size = content_rect.size();
                         size[self.direction] = basis + extra_length;
-                        content_rect = Rect::from_center_size(
-                            content_rect.center(),
+                        content_rect = content_align.align_size_within_rect(
                             size.min(self.ui.available_size() - item_state.config.margin.sum()),
+                            frame_without_margin,
                         );
                     }
 
@@ -1083,6 +1369,31 @@ impl FlexInstance<'_> {
                         .inner;
                     let (_, _r) = ui.allocate_space(child_ui.min_rect().size());
 
+                    #[cfg(feature = "introspection")]
+                    self.item_infos.push((
+                        self.current_row,
+                        FlexItemInfo {
+                            id: ui.id(),
+                            basis: item_state.config.basis.map_or(
+                                item_state.min_size_with_margin()[self.direction],
+                                |basis| basis + item_state.config.margin.sum()[self.direction],
+                            ),
+                            min_size: item_state.min_size_with_margin(),
+                            grow: item_state.config.grow.unwrap_or(0.0),
+                            grown: extra_length,
+                            shrunk: if do_shrink {
+                                f32::min(
+                                    -row.extra_space,
+                                    item_state.min_size_with_margin()[self.direction]
+                                        + extra_length,
+                                )
+                            } else {
+                                0.0
+                            },
+                            rect: child_ui.min_rect(),
+                        },
+                    ));
+
                     let mut inner_size = res.child_rect.size();
                     if do_shrink {
                         let this_frame = res.child_rect.size()[self.direction];
@@ -1148,6 +1459,12 @@ impl FlexInstance<'_> {
         );
         let (inner, item, row_len) = res.inner;
 
+        let paint_end = next_shape_idx(self.ui.ctx(), layer_id);
+        if let Some(z) = z {
+            self.z_ranges.push((z, paint_start, paint_end));
+        }
+        self.items_end = paint_end;
+
         let is_last_item = self.current_row_index + 1 >= row_len;
         // TODO: Find a better way to do this, maybe just set the row ui rect to it's max rect?
         // if is_last_item
@@ -1172,19 +1489,21 @@ impl FlexInstance<'_> {
         }
 
         self.state.items.push(item);
+        self.item_rects.push(res.response.rect);
 
         self.current_row_index += 1;
         if is_last_item {
             self.current_row += 1;
             self.current_row_index = 0;
-            self.row_ui = FlexInstance::row_ui(self.ui, self.rows.get(self.current_row));
+            self.row_ui =
+                FlexInstance::row_ui(self.ui, self.rows.get(self.current_row), self.item_layout);
         }
 
         InnerResponse::new(inner, res.response)
     }
 
     /// Add a child ui to the flex container.
-    /// It will be positioned based on [FlexItem::align_self_content].
+    /// It will be positioned based on [FlexItem::content_align].
     pub fn add_ui<R>(
         &mut self,
         item: FlexItem,
@@ -1244,6 +1563,14 @@ impl FlexInstance<'_> {
     pub fn grow(&mut self) -> Response {
         self.add_ui(FlexItem::new().grow(1.0), |_| {}).response
     }
+
+    /// Push the items added after this to the end of the row, e.g. to have left and right
+    /// aligned items in a toolbar. Unlike [`Self::grow`], this doesn't add an item and the
+    /// remaining space isn't shared with other growing items.
+    /// This is the same as calling [`FlexItem::push_end`] on the next item.
+    pub fn spacer(&mut self) {
+        self.push_next_to_end = true;
+    }
 }
 
 type ContentFn<'a, R> = Box<dyn FnOnce(&mut Ui, FlexContainerUi) -> FlexContainerResponse<R> + 'a>;
@@ -1397,6 +1724,16 @@ impl FlexContainerUi {
         } else {
             ui.set_width(ui.available_width());
             ui.set_height(ui.available_height());
+            // Keep the widget at its intrinsic size instead of filling the item
+            if let Some(content_align) = self
+                .item
+                .align_content
+                .filter(|_| self.item.intrinsic_content)
+            {
+                let inner_rect = self.frame_rect - self.margin;
+                let size = self.target_inner_size.min(inner_rect.size());
+                builder = builder.max_rect(content_align.align_size_within_rect(size, inner_rect));
+            }
         };
         let response = ui.scope_builder(builder, |ui| widget.ui(ui)).inner;
 
@@ -1426,3 +1763,8 @@ impl FlexContainerUi {
         }
     }
 }
+
+/// The index the next shape painted in `layer_id` will have
+fn next_shape_idx(ctx: &Context, layer_id: LayerId) -> usize {
+    ctx.graphics(|graphics| graphics.get(layer_id).map_or(0, |list| list.next_idx().0))
+}
@@ -1,6 +1,7 @@
 use eframe::emath::Vec2;
 use egui::{
-    Align, Button, Checkbox, DragValue, Frame, Id, Label, Layout, ScrollArea, TextEdit, Ui,
+    Align, Align2, Button, Checkbox, DragValue, Frame, Id, Label, Layout, Rect, ScrollArea,
+    TextEdit, Ui,
 };
 use egui_flex::{item, Flex, FlexAlign, FlexAlignContent, FlexItem, FlexJustify, Size};
 use egui_kittest::wgpu::WgpuTestRenderer;
@@ -443,3 +444,27 @@ fn truncate_shrink() {
         result.unwrap();
     }
 }
+
+#[rstest]
+fn content_align(
+    #[values(Align2::LEFT_CENTER, Align2::CENTER_CENTER, Align2::RIGHT_CENTER)] align: Align2,
+) {
+    let rects = Cell::new((Rect::NOTHING, Rect::NOTHING));
+    let mut harness = Harness::builder().with_size([300.0, 100.0]).build_ui(|ui| {
+        let response = Flex::horizontal().w_full().show(ui, |flex| {
+            flex.add(item().grow(1.0).content_align(align), Button::new("Button"))
+        });
+        rects.set((response.response.rect, response.inner.rect));
+    });
+
+    harness.run();
+    should_be_stable(&mut harness);
+
+    let (container, button) = rects.get();
+    assert!(
+        button.width() < container.width() / 2.0,
+        "The button should not fill the item"
+    );
+    let expected = align.align_size_within_rect(button.size(), container);
+    assert!((button.center().x - expected.center().x).abs() < 1.0);
+}
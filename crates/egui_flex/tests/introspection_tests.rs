@@ -0,0 +1,143 @@
+#![cfg(feature = "introspection")]
+
+use egui::{Id, Rect, Ui, UiBuilder, Vec2};
+use egui_flex::{item, Flex, FlexDirection, FlexLayoutInfo};
+use egui_kittest::Harness;
+use std::cell::Cell;
+
+fn layout_info(app: impl Fn(&mut Ui) -> Id) -> FlexLayoutInfo {
+    let id = Cell::new(None);
+    let mut harness = Harness::new_ui(|ui| id.set(Some(app(ui))));
+    harness.run();
+    FlexLayoutInfo::load(&harness.ctx, id.get().unwrap()).unwrap()
+}
+
+fn spacer(ui: &mut Ui) {
+    ui.allocate_exact_size(Vec2::splat(20.0), egui::Sense::hover());
+}
+
+#[test]
+fn grow_distributes_extra_space() {
+    let info = layout_info(|ui| {
+        Flex::horizontal()
+            .width(300.0)
+            .gap(Vec2::ZERO)
+            .show(ui, |flex| {
+                flex.add_ui(item().grow(1.0), spacer);
+                flex.add_ui(item().grow(2.0), spacer);
+                flex.add_ui(item(), spacer);
+            })
+            .response
+            .id
+    });
+
+    assert_eq!(info.rows.len(), 1);
+    let row = &info.rows[0];
+    assert_eq!(row.total_size, 60.0);
+    assert_eq!(row.total_grow, 3.0);
+    assert_eq!(row.extra_space, 240.0);
+
+    let grown: Vec<_> = row.items.iter().map(|item| item.grown).collect();
+    assert_eq!(grown, [80.0, 160.0, 0.0]);
+    let widths: Vec<_> = row.items.iter().map(|item| item.size().x).collect();
+    assert_eq!(widths, [100.0, 180.0, 20.0]);
+}
+
+#[test]
+fn shrink_item_shrinks_by_overflow() {
+    let info = layout_info(|ui| {
+        Flex::horizontal()
+            .width(100.0)
+            .gap(Vec2::ZERO)
+            .show(ui, |flex| {
+                flex.add_ui(item().basis(80.0), spacer);
+                flex.add_ui(item().basis(60.0).shrink(), spacer);
+            })
+            .response
+            .id
+    });
+
+    let row = &info.rows[0];
+    assert_eq!(row.extra_space, -40.0);
+    assert_eq!(row.items[0].shrunk, 0.0);
+    assert_eq!(row.items[1].basis, 60.0);
+    assert_eq!(row.items[1].shrunk, 40.0);
+}
+
+#[test]
+fn wrap_creates_rows() {
+    let info = layout_info(|ui| {
+        Flex::horizontal()
+            .width(50.0)
+            .wrap(true)
+            .gap(Vec2::ZERO)
+            .show(ui, |flex| {
+                for _ in 0..5 {
+                    flex.add_ui(item(), spacer);
+                }
+            })
+            .response
+            .id
+    });
+
+    let items_per_row: Vec<_> = info.rows.iter().map(|row| row.items.len()).collect();
+    assert_eq!(items_per_row, [2, 2, 1]);
+}
+
+#[test]
+fn push_end_absorbs_extra_space() {
+    let info = layout_info(|ui| {
+        Flex::horizontal()
+            .width(300.0)
+            .gap(Vec2::ZERO)
+            .show(ui, |flex| {
+                flex.add_ui(item().grow(1.0), spacer);
+                flex.add_ui(item(), spacer);
+                flex.spacer();
+                flex.add_ui(item(), spacer);
+                flex.add_ui(item(), spacer);
+            })
+            .response
+            .id
+    });
+
+    let row = &info.rows[0];
+    assert_eq!(row.extra_space, 0.0);
+    let starts: Vec<_> = row
+        .items
+        .iter()
+        .map(|item| item.rect.min.x - info.rect.min.x)
+        .collect();
+    assert_eq!(starts, [0.0, 20.0, 260.0, 280.0]);
+}
+
+#[test]
+fn auto_direction_follows_aspect_ratio_with_hysteresis() {
+    let size = Cell::new(Vec2::ZERO);
+    let id = Cell::new(None);
+    let mut harness = Harness::new_ui(|ui| {
+        let rect = Rect::from_min_size(ui.cursor().min, size.get());
+        ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+            let response = Flex::new().auto_direction().show(ui, |flex| {
+                flex.add_ui(item(), spacer);
+                flex.add_ui(item(), spacer);
+            });
+            id.set(Some(response.response.id));
+        });
+    });
+
+    let mut direction = |width: f32, height: f32| {
+        size.set(Vec2::new(width, height));
+        harness.run();
+        FlexLayoutInfo::load(&harness.ctx, id.get().unwrap())
+            .unwrap()
+            .direction
+    };
+
+    assert_eq!(direction(300.0, 100.0), FlexDirection::Horizontal);
+    // Slightly higher than wide isn't enough to switch
+    assert_eq!(direction(100.0, 110.0), FlexDirection::Horizontal);
+    assert_eq!(direction(100.0, 130.0), FlexDirection::Vertical);
+    assert_eq!(direction(110.0, 100.0), FlexDirection::Vertical);
+    assert_eq!(direction(130.0, 100.0), FlexDirection::Horizontal);
+}
@@ -0,0 +1,94 @@
+use egui::Vec2;
+use egui_flex::{Flex, FlexAlign, FlexJustify, LayoutItem};
+
+const AVAILABLE: Vec2 = Vec2::new(300.0, 200.0);
+
+fn square() -> LayoutItem {
+    LayoutItem::new([20.0, 20.0])
+}
+
+#[test]
+fn justify_positions_items() {
+    let starts = |justify| {
+        Flex::horizontal()
+            .width(100.0)
+            .gap(Vec2::ZERO)
+            .justify(justify)
+            .layout(AVAILABLE, &[square(), square()])
+            .items
+            .iter()
+            .map(|item| item.rect.min.x)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(starts(FlexJustify::Start), [0.0, 20.0]);
+    assert_eq!(starts(FlexJustify::End), [60.0, 80.0]);
+    assert_eq!(starts(FlexJustify::Center), [30.0, 50.0]);
+    assert_eq!(starts(FlexJustify::SpaceBetween), [0.0, 80.0]);
+    assert_eq!(starts(FlexJustify::SpaceAround), [15.0, 65.0]);
+    assert_eq!(starts(FlexJustify::SpaceEvenly), [20.0, 60.0]);
+}
+
+#[test]
+fn wrapped_rows_are_as_tall_as_their_tallest_item() {
+    let layout = Flex::horizontal()
+        .width(50.0)
+        .wrap(true)
+        .gap(Vec2::new(0.0, 5.0))
+        .align_items(FlexAlign::Start)
+        .layout(
+            AVAILABLE,
+            &[
+                square(),
+                LayoutItem::new([20.0, 30.0]),
+                square(),
+                LayoutItem::new([20.0, 10.0]),
+                square(),
+            ],
+        );
+
+    let rows: Vec<_> = layout
+        .rows
+        .iter()
+        .map(|row| (row.items.len(), row.rect.min.y, row.rect.height()))
+        .collect();
+    assert_eq!(rows, [(2, 0.0, 30.0), (2, 35.0, 20.0), (1, 60.0, 20.0)]);
+    assert_eq!(layout.items[3].rect.min.y, 35.0);
+    assert_eq!(layout.items[4].row, 2);
+}
+
+#[test]
+fn align_self_in_cross_direction() {
+    let layout = Flex::horizontal().height(100.0).gap(Vec2::ZERO).layout(
+        AVAILABLE,
+        &[
+            square(),
+            square().align_self(FlexAlign::Start),
+            square().align_self(FlexAlign::Center),
+            square().align_self(FlexAlign::End),
+        ],
+    );
+
+    let cross: Vec<_> = layout
+        .items
+        .iter()
+        .map(|item| (item.rect.min.y, item.rect.height()))
+        .collect();
+    assert_eq!(
+        cross,
+        [(0.0, 100.0), (0.0, 20.0), (40.0, 20.0), (80.0, 20.0)]
+    );
+}
+
+#[test]
+fn vertical_layout() {
+    let layout = Flex::vertical().height(100.0).gap(Vec2::ZERO).layout(
+        AVAILABLE,
+        &[square().grow(1.0), LayoutItem::new([40.0, 20.0])],
+    );
+
+    let heights: Vec<_> = layout.items.iter().map(|item| item.rect.height()).collect();
+    assert_eq!(heights, [80.0, 20.0]);
+    assert_eq!(layout.items[1].rect.min.y, 80.0);
+    assert_eq!(layout.items[0].rect.width(), 40.0);
+}
@@ -0,0 +1,86 @@
+use egui::{Color32, Frame, Shape, TextEdit, Ui, Vec2};
+use egui_flex::{item, Flex};
+use egui_kittest::Harness;
+
+const COLORS: [Color32; 3] = [Color32::RED, Color32::GREEN, Color32::BLUE];
+
+/// Shows three items with the given z and returns the fill colors of their frames in paint order
+fn paint_order(z: [Option<i32>; 3]) -> Vec<Color32> {
+    let mut harness = Harness::new_ui(|ui: &mut Ui| {
+        Flex::horizontal().show(ui, |flex| {
+            for (color, z) in COLORS.into_iter().zip(z) {
+                let mut item = item().frame(Frame::NONE.fill(color));
+                if let Some(z) = z {
+                    item = item.z(z);
+                }
+                flex.add_ui(item, |ui| {
+                    ui.allocate_exact_size(Vec2::splat(20.0), egui::Sense::hover());
+                });
+            }
+        });
+    });
+    harness.run();
+
+    harness
+        .output()
+        .shapes
+        .iter()
+        .filter_map(|shape| match &shape.shape {
+            Shape::Rect(rect) if COLORS.contains(&rect.fill) => Some(rect.fill),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn items_are_painted_in_order_by_default() {
+    assert_eq!(paint_order([None, None, None]), COLORS);
+    assert_eq!(paint_order([Some(0), Some(0), Some(0)]), COLORS);
+}
+
+#[test]
+fn higher_z_is_painted_after_siblings() {
+    assert_eq!(
+        paint_order([Some(1), None, None]),
+        [Color32::GREEN, Color32::BLUE, Color32::RED]
+    );
+    assert_eq!(
+        paint_order([Some(2), Some(1), None]),
+        [Color32::BLUE, Color32::GREEN, Color32::RED]
+    );
+}
+
+#[test]
+fn lower_z_is_painted_before_siblings() {
+    assert_eq!(
+        paint_order([None, None, Some(-1)]),
+        [Color32::BLUE, Color32::RED, Color32::GREEN]
+    );
+}
+
+#[test]
+fn unfilled_placeholders_in_items_dont_block_reordering() {
+    let mut harness = Harness::new_ui(|ui: &mut Ui| {
+        Flex::horizontal().show(ui, |flex| {
+            // A TextEdit without a frame never fills its background placeholder
+            flex.add_ui(item().frame(Frame::NONE.fill(Color32::GREEN)), |ui| {
+                ui.add(TextEdit::singleline(&mut String::new()).frame(false));
+            });
+            flex.add_ui(item().frame(Frame::NONE.fill(Color32::RED)).z(-1), |ui| {
+                ui.allocate_exact_size(Vec2::splat(20.0), egui::Sense::hover());
+            });
+        });
+    });
+    harness.run();
+
+    let fills: Vec<_> = harness
+        .output()
+        .shapes
+        .iter()
+        .filter_map(|shape| match &shape.shape {
+            Shape::Rect(rect) if COLORS.contains(&rect.fill) => Some(rect.fill),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fills, [Color32::RED, Color32::GREEN]);
+}
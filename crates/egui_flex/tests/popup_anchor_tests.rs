@@ -0,0 +1,38 @@
+use egui::{Rect, Sense, Ui, Vec2};
+use egui_flex::{item, Flex};
+use egui_kittest::Harness;
+use std::cell::{Cell, RefCell};
+
+fn spacer(ui: &mut Ui) {
+    ui.allocate_exact_size(Vec2::splat(20.0), Sense::hover());
+}
+
+#[test]
+fn popup_anchor_keeps_the_settled_rect_while_laying_out_again() {
+    let width = Cell::new(20.0);
+    // The anchor and the response rect of the second item in each pass
+    let anchors: RefCell<Vec<(Option<Rect>, Rect)>> = RefCell::new(Vec::new());
+    let mut harness = Harness::new_ui(|ui| {
+        Flex::horizontal().gap(Vec2::ZERO).show(ui, |flex| {
+            flex.add_ui(item(), |ui| {
+                ui.allocate_exact_size(Vec2::new(width.get(), 20.0), Sense::hover());
+            });
+            let rect = flex.add_ui(item(), spacer).response.rect;
+            anchors.borrow_mut().push((flex.popup_anchor(1), rect));
+        });
+    });
+    harness.run();
+
+    let (anchor, settled) = anchors.borrow_mut().drain(..).last().unwrap();
+    assert_eq!(anchor, Some(settled));
+
+    width.set(50.0);
+    harness.run();
+
+    let anchors = anchors.borrow();
+    // The first pass still uses the old layout, so the anchor stays at the settled rect
+    assert_eq!(anchors[0].0, Some(settled));
+    let (anchor, rect) = anchors.last().unwrap();
+    assert_eq!(*anchor, Some(*rect));
+    assert_eq!(rect.min.x - settled.min.x, 30.0);
+}
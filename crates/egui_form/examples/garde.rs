@@ -96,4 +96,23 @@ mod tests {
 
         assert_eq!(report.error_count(), 4);
     }
+
+    #[cfg(feature = "test_util")]
+    #[test]
+    fn test_form() {
+        let mut test = Test {
+            user_name: "test".to_string(),
+            email: "garbage".to_string(),
+            nested: Nested { test: 5 },
+            vec: vec![Nested { test: 0 }],
+        };
+
+        let report = egui_form::test_util::validate_form(&mut test, form_ui);
+
+        assert!(report.errors("user_name").is_empty());
+        assert_eq!(report.errors("email").len(), 1);
+        assert!(report.errors("Nested Test").is_empty());
+        assert_eq!(report.errors("vec[0].test").len(), 1);
+        assert_eq!(report.invalid_fields().count(), 2);
+    }
 }
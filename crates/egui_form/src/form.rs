@@ -1,5 +1,7 @@
 use crate::EguiValidationReport;
+use egui::collapsing_header::CollapsingState;
 use egui::{Response, Ui};
+use std::ops::Range;
 
 pub(crate) struct FormFieldState {
     pub(crate) state_id: egui::Id,
@@ -8,11 +10,32 @@ pub(crate) struct FormFieldState {
     pub(crate) errors: Vec<String>,
 }
 
+pub(crate) struct FormSectionState {
+    pub(crate) id: egui::Id,
+    // The range of the section's fields in `Form::controls`
+    pub(crate) controls: Range<usize>,
+}
+
+/// How the fields of a [Form] are shown, see [`Form::mode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FormMode {
+    /// The fields can be edited and show their errors.
+    #[default]
+    Edit,
+    /// The fields look like in [`FormMode::Edit`], but they can't be changed.
+    /// Use this for detail (view) screens, so they can share the form with the edit screen.
+    ReadOnly,
+    /// The fields are grayed out and can't be changed, e.g. while the form is being submitted.
+    Disabled,
+}
+
 /// Form connects the state of the individual form fields with the validation results.
 /// It's also responsible for handling the submission and focusing the first invalid field on error.
 pub struct Form<R: EguiValidationReport> {
     pub(crate) controls: Vec<FormFieldState>,
+    pub(crate) sections: Vec<FormSectionState>,
     pub(crate) validation_results: Vec<R>,
+    pub(crate) mode: FormMode,
 }
 
 impl<R: EguiValidationReport> Default for Form<R> {
@@ -26,7 +49,9 @@ impl<R: EguiValidationReport> Form<R> {
     pub fn new() -> Self {
         Self {
             controls: Vec::new(),
+            sections: Vec::new(),
             validation_results: Vec::new(),
+            mode: FormMode::Edit,
         }
     }
 
@@ -39,6 +64,14 @@ impl<R: EguiValidationReport> Form<R> {
         self
     }
 
+    /// Set how the fields of this form are shown, see [`FormMode`].
+    /// The layout and labels are the same in every mode, but errors are only shown in
+    /// [`FormMode::Edit`].
+    pub fn mode(mut self, mode: FormMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     /// Handle the submission of the form.
     /// You usually pass this a button response.
     /// If this function returns Some(Ok(_)), the form data can be submitted.
@@ -64,18 +97,7 @@ impl<R: EguiValidationReport> Form<R> {
             .iter()
             .any(super::validation_report::EguiValidationReport::has_errors);
         if has_errors {
-            ui.memory_mut(|mem| {
-                for control in &self.controls {
-                    mem.data.insert_temp(control.state_id, true);
-                }
-                if let Some(first) = self
-                    .controls
-                    .iter()
-                    .find(|control| !control.errors.is_empty())
-                {
-                    mem.request_focus(first.widget_id);
-                }
-            });
+            self.show_errors(ui, 0..self.controls.len());
             Err(self
                 .validation_results
                 .iter()
@@ -85,4 +107,53 @@ impl<R: EguiValidationReport> Form<R> {
             Ok(())
         }
     }
+
+    /// Try to submit a single [`crate::FormSection`], only validating the fields in that section.
+    /// Pass the id from [`crate::FormSectionResponse::id`]. This has to be called after the
+    /// section was shown.
+    /// Returns Ok(()) if the section is valid, otherwise returns the number of invalid fields.
+    pub fn try_submit_section(&mut self, section_id: egui::Id, ui: &mut Ui) -> Result<(), usize> {
+        let Some(controls) = self
+            .sections
+            .iter()
+            .find(|section| section.id == section_id)
+            .map(|section| section.controls.clone())
+        else {
+            return Ok(());
+        };
+        let error_count = self.controls[controls.clone()]
+            .iter()
+            .filter(|control| !control.errors.is_empty())
+            .count();
+        if error_count > 0 {
+            self.show_errors(ui, controls);
+            Err(error_count)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Show the errors of the given controls, expand their sections and focus the first invalid one.
+    fn show_errors(&self, ui: &mut Ui, range: Range<usize>) {
+        let is_invalid = |index: &usize| !self.controls[*index].errors.is_empty();
+
+        ui.memory_mut(|mem| {
+            for control in &self.controls[range.clone()] {
+                mem.data.insert_temp(control.state_id, true);
+            }
+            if let Some(first) = range.clone().find(is_invalid) {
+                mem.request_focus(self.controls[first].widget_id);
+            }
+        });
+
+        for section in &self.sections {
+            let start = section.controls.start.max(range.start);
+            let end = section.controls.end.min(range.end);
+            if (start..end).any(|index| is_invalid(&index)) {
+                let mut state = CollapsingState::load_with_default_open(ui.ctx(), section.id, true);
+                state.set_open(true);
+                state.store(ui.ctx());
+            }
+        }
+    }
 }
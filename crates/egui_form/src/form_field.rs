@@ -1,14 +1,21 @@
-use crate::form::FormFieldState;
+use crate::form::{FormFieldState, FormMode};
 use crate::validation_report::IntoFieldPath;
 use crate::{EguiValidationReport, Form};
-use egui::{Response, RichText, TextStyle, Widget};
+use egui::{Color32, Response, RichText, Sense, TextStyle, Ui, Widget};
 use std::borrow::Cow;
+use std::fmt::Display;
 
 /// A form field that can be validated.
 /// Will color the field red (using the color from [`egui::style::Visuals::error_fg_color`]) if there is an error.
 /// Will show the error message below the field if the field is blurred and there is an error.
+///
+/// The field can't be changed if the form's [`FormMode`] is [`FormMode::ReadOnly`] or
+/// [`FormMode::Disabled`].
 pub struct FormField<'a, 'f, Errors: EguiValidationReport> {
     error: Option<Cow<'static, str>>,
+    #[cfg(feature = "test_util")]
+    name: Option<String>,
+    mode: FormMode,
     label: Option<Cow<'a, str>>,
     form: Option<&'f mut Form<Errors>>,
 }
@@ -29,7 +36,10 @@ impl<'a, 'f, Errors: EguiValidationReport> FormField<'a, 'f, Errors> {
             .find_map(|errors| errors.get_field_error(field_path.clone()));
 
         FormField {
+            #[cfg(feature = "test_util")]
+            name: Errors::field_name(&field_path),
             error,
+            mode: form.mode,
             label: None,
             form: Some(form),
         }
@@ -51,7 +61,7 @@ impl<'a, 'f, Errors: EguiValidationReport> FormField<'a, 'f, Errors> {
 
             let error_color = ui.style().visuals.error_fg_color;
 
-            let show_error = error.is_some() && blurred;
+            let show_error = error.is_some() && blurred && self.mode == FormMode::Edit;
 
             if show_error {
                 let widgets = &mut ui.style_mut().visuals.widgets;
@@ -65,23 +75,22 @@ impl<'a, 'f, Errors: EguiValidationReport> FormField<'a, 'f, Errors> {
                 widgets.open.bg_stroke.width = 1.0;
             }
 
+            #[cfg(feature = "test_util")]
+            crate::test_util::Recorder::record(ui, || crate::test_util::FieldReport {
+                name: self.name,
+                label: self.label.as_ref().map(ToString::to_string),
+                errors: error.iter().map(ToString::to_string).collect(),
+            });
+
             if let Some(label) = self.label {
-                let mut rich_text = RichText::new(label);
-                if show_error {
-                    rich_text = rich_text.color(error_color);
-                }
-                ui.label(
-                    rich_text.size(
-                        ui.style()
-                            .text_styles
-                            .get(&TextStyle::Body)
-                            .map_or(16.0, |s| s.size)
-                            * 0.9,
-                    ),
-                );
+                label_ui(ui, label, show_error.then_some(error_color));
             }
 
-            let response = content.ui(ui);
+            let response = match self.mode {
+                FormMode::Edit => content.ui(ui),
+                FormMode::ReadOnly => read_only_ui(ui, id, content),
+                FormMode::Disabled => ui.add_enabled(false, content),
+            };
 
             if response.lost_focus() {
                 ui.memory_mut(|mem| {
@@ -119,3 +128,79 @@ impl<'a, 'f, Errors: EguiValidationReport> FormField<'a, 'f, Errors> {
         .inner
     }
 }
+
+/// Shows the widget with its normal look, but blocks any interaction with it
+fn read_only_ui(ui: &mut Ui, id: egui::Id, content: impl Widget) -> Response {
+    let response = content.ui(ui);
+
+    // Covers the widget, so it doesn't get any pointer input
+    ui.interact(response.rect, id.with("read_only"), Sense::click_and_drag());
+    // The widget could still be focused via the keyboard
+    if response.has_focus() {
+        ui.memory_mut(|mem| mem.surrender_focus(response.id));
+    }
+
+    response
+}
+
+fn label_ui(ui: &mut Ui, label: Cow<'_, str>, color: Option<Color32>) {
+    let mut rich_text = RichText::new(label);
+    if let Some(color) = color {
+        rich_text = rich_text.color(color);
+    }
+    ui.label(
+        rich_text.size(
+            ui.style()
+                .text_styles
+                .get(&TextStyle::Body)
+                .map_or(16.0, |s| s.size)
+                * 0.9,
+        ),
+    );
+}
+
+/// A read-only field that shows a value derived from other fields, e.g. `total = qty * price`.
+/// It is laid out like a [`FormField`], but it's not part of the [Form], so it is excluded
+/// from validation and submission.
+/// Since it is rendered each frame, the value updates live as the other fields change.
+pub struct ComputedField<'a> {
+    label: Option<Cow<'a, str>>,
+}
+
+impl Default for ComputedField<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ComputedField<'a> {
+    /// Create a new `ComputedField`.
+    pub fn new() -> Self {
+        ComputedField { label: None }
+    }
+
+    /// Optionally set a label for the field.
+    pub fn label(mut self, label: impl Into<Cow<'a, str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Render the field with the computed value.
+    pub fn ui(self, ui: &mut Ui, value: impl Display) -> Response {
+        ui.vertical(|ui| {
+            if let Some(label) = self.label {
+                label_ui(ui, label, None);
+            }
+
+            let value = value.to_string();
+            let response =
+                ui.add(egui::TextEdit::singleline(&mut value.as_str()).interactive(false));
+
+            // Reserve the space of the error message, so the field lines up with other fields
+            ui.add_visible(false, egui::Label::new(RichText::new("").small()));
+
+            response
+        })
+        .inner
+    }
+}
@@ -0,0 +1,140 @@
+use crate::form::FormSectionState;
+use crate::{EguiValidationReport, Form};
+use egui::collapsing_header::{paint_default_icon, CollapsingState};
+use egui::{Align, Id, Layout, Response, RichText, Sense, Ui, UiBuilder, Vec2, WidgetText};
+
+/// A collapsible section of a [Form].
+/// The header shows how many of the section's fields currently show an error,
+/// e.g. "Billing (2 errors)".
+///
+/// When the form is submitted with errors via [`Form::try_submit`], sections containing invalid
+/// fields are expanded automatically. Use [`Form::try_submit_section`] to only validate the fields
+/// of a single section, e.g. for a multi-step form.
+///
+/// The fields in a collapsed section are still added to the form (but not shown),
+/// so their errors are counted and submitting the form will still validate them.
+pub struct FormSection {
+    title: WidgetText,
+    id_salt: Option<Id>,
+    default_open: bool,
+}
+
+/// Response of [`FormSection::show`].
+pub struct FormSectionResponse<T> {
+    /// The id of the section, pass this to [`Form::try_submit_section`].
+    pub id: Id,
+    /// The response of the section header.
+    pub response: Response,
+    /// The return value of the content closure.
+    pub inner: T,
+    /// The number of fields in this section that have an error (shown or not).
+    pub error_count: usize,
+}
+
+impl<T> FormSectionResponse<T> {
+    /// Returns true if none of the fields in this section have an error.
+    pub fn is_valid(&self) -> bool {
+        self.error_count == 0
+    }
+}
+
+impl FormSection {
+    /// Create a new section with the given title. By default, the section is open.
+    pub fn new(title: impl Into<WidgetText>) -> Self {
+        Self {
+            title: title.into(),
+            id_salt: None,
+            default_open: true,
+        }
+    }
+
+    /// Set the id salt of the section. By default, the title is used.
+    pub fn id_salt(mut self, id_salt: impl Into<Id>) -> Self {
+        self.id_salt = Some(id_salt.into());
+        self
+    }
+
+    /// Set whether the section should be open by default.
+    pub fn default_open(mut self, default_open: bool) -> Self {
+        self.default_open = default_open;
+        self
+    }
+
+    /// Show the section. Add the [`crate::FormField`]s to the form passed to `content`.
+    pub fn show<R: EguiValidationReport, T>(
+        self,
+        ui: &mut Ui,
+        form: &mut Form<R>,
+        content: impl FnOnce(&mut Ui, &mut Form<R>) -> T,
+    ) -> FormSectionResponse<T> {
+        let id = ui.make_persistent_id(self.id_salt.unwrap_or_else(|| Id::new(self.title.text())));
+        let mut state = CollapsingState::load_with_default_open(ui.ctx(), id, self.default_open);
+
+        // The header is shown after the content, so it can show the error count of this frame
+        let (header_rect, _) = ui.allocate_exact_size(
+            Vec2::new(ui.available_width(), ui.spacing().interact_size.y),
+            Sense::hover(),
+        );
+
+        let mut body_rect = ui.available_rect_before_wrap();
+        body_rect.min.x += ui.spacing().indent;
+        let body_builder = UiBuilder::new()
+            .id_salt(id.with("body"))
+            .max_rect(body_rect);
+
+        let first_control = form.controls.len();
+        // The content is shown in the same (invisible) ui when collapsed, so the fields keep
+        // their ids and can be focused once the section is expanded
+        let inner = if state.is_open() {
+            ui.scope_builder(body_builder, |ui| content(ui, form)).inner
+        } else {
+            content(&mut ui.new_child(body_builder.invisible()), form)
+        };
+        let controls = first_control..form.controls.len();
+
+        let fields = &form.controls[controls.clone()];
+        let error_count = fields
+            .iter()
+            .filter(|control| !control.errors.is_empty())
+            .count();
+        let shown_error_count = ui.data(|data| {
+            fields
+                .iter()
+                .filter(|control| {
+                    !control.errors.is_empty() && data.get_temp(control.state_id).unwrap_or(false)
+                })
+                .count()
+        });
+
+        form.sections.push(FormSectionState { id, controls });
+
+        let mut header_ui = ui.new_child(
+            UiBuilder::new()
+                .max_rect(header_rect)
+                .layout(Layout::left_to_right(Align::Center)),
+        );
+        header_ui.spacing_mut().item_spacing.x = 0.0;
+        state.show_toggle_button(&mut header_ui, paint_default_icon);
+        let mut response = header_ui.add(egui::Label::new(self.title).sense(Sense::click()));
+        if shown_error_count > 0 {
+            let suffix = if shown_error_count == 1 {
+                " (1 error)".to_owned()
+            } else {
+                format!(" ({shown_error_count} errors)")
+            };
+            let error_color = header_ui.visuals().error_fg_color;
+            response |= header_ui.label(RichText::new(suffix).color(error_color));
+        }
+        if response.clicked() {
+            state.toggle(ui);
+        }
+        state.store(ui.ctx());
+
+        FormSectionResponse {
+            id,
+            response,
+            inner,
+            error_count,
+        }
+    }
+}
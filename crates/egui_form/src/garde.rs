@@ -95,6 +95,10 @@ impl EguiValidationReport for GardeReport {
             None
         }
     }
+
+    fn field_name(field: &Self::FieldPath<'_>) -> Option<String> {
+        Some(field.to_string())
+    }
 }
 
 impl IntoFieldPath<Path> for Path {
@@ -66,6 +66,11 @@ pub mod garde;
 mod validation_report;
 
 mod form_field;
+mod form_section;
+mod mask;
+/// Run forms headlessly in tests to check their validation, see [`test_util::validate_form`].
+#[cfg(feature = "test_util")]
+pub mod test_util;
 /// To use [validator] with `egui_form`, you need to create a [`validator::ValidatorReport`] and pass it to the [Form] instance.
 ///
 /// Then, when you create a [`FormField`], you pass a slice of [`validator::PathItem`]s.
@@ -147,6 +152,8 @@ mod form_field;
 #[cfg(feature = "validator_validator")]
 pub mod validator;
 
-pub use form::Form;
+pub use form::{Form, FormMode};
 pub use form_field::*;
+pub use form_section::{FormSection, FormSectionResponse};
+pub use mask::{InputMask, MaskedTextEdit};
 pub use validation_report::{EguiValidationReport, IntoFieldPath};
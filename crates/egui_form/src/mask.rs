@@ -0,0 +1,332 @@
+use egui::text::{CCursor, CCursorRange};
+use egui::{Response, TextEdit, Ui, Widget, WidgetText};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaskChar {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Literal(char),
+}
+
+impl MaskChar {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            MaskChar::Digit => c.is_ascii_digit(),
+            MaskChar::Letter => c.is_alphabetic(),
+            MaskChar::Alphanumeric => c.is_alphanumeric(),
+            MaskChar::Literal(_) => false,
+        }
+    }
+}
+
+/// An input mask like `(###) ###-####`, used by [`MaskedTextEdit`] to format the input as the
+/// user types.
+///
+/// - `#` accepts a digit
+/// - `A` accepts a letter
+/// - `*` accepts a letter or digit
+/// - `\` escapes the next character, e.g. `\#` for a literal `#`
+/// - any other character is inserted as is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMask {
+    chars: Vec<MaskChar>,
+}
+
+impl InputMask {
+    /// Create a new mask from a pattern, see [`InputMask`] for the syntax.
+    pub fn new(pattern: &str) -> Self {
+        let mut chars = Vec::new();
+        let mut pattern = pattern.chars();
+        while let Some(c) = pattern.next() {
+            chars.push(match c {
+                '#' => MaskChar::Digit,
+                'A' => MaskChar::Letter,
+                '*' => MaskChar::Alphanumeric,
+                '\\' => MaskChar::Literal(pattern.next().unwrap_or('\\')),
+                c => MaskChar::Literal(c),
+            });
+        }
+        Self { chars }
+    }
+
+    /// A credit card number, `#### #### #### ####`
+    pub fn credit_card() -> Self {
+        Self::new("#### #### #### ####")
+    }
+
+    /// A date, `##/##/####`
+    pub fn date() -> Self {
+        Self::new("##/##/####")
+    }
+
+    /// The number of characters the raw value can have
+    pub fn len(&self) -> usize {
+        self.chars
+            .iter()
+            .filter(|c| !matches!(c, MaskChar::Literal(_)))
+            .count()
+    }
+
+    /// Returns true if the mask only consists of literals
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if `raw` fills every slot of the mask
+    pub fn is_complete(&self, raw: &str) -> bool {
+        self.unmask(&self.format(raw)).chars().count() == self.len()
+    }
+
+    /// Formats a raw value, e.g. `5551234` -> `(555) 123-4` for `(###) ###-####`.
+    /// Literals are only inserted up to the last character of the raw value, so deleting
+    /// characters doesn't get stuck on them. Characters that don't fit the mask are skipped.
+    pub fn format(&self, raw: &str) -> String {
+        let mut formatted = String::new();
+        let mut literals = String::new();
+        let mut raw = raw.chars().peekable();
+        for mask in &self.chars {
+            if let MaskChar::Literal(c) = mask {
+                literals.push(*c);
+                continue;
+            }
+            // Skip characters the slot doesn't accept
+            while raw.next_if(|c| !mask.accepts(*c)).is_some() {}
+            let Some(c) = raw.next() else {
+                break;
+            };
+            formatted.push_str(&literals);
+            literals.clear();
+            formatted.push(c);
+        }
+        formatted
+    }
+
+    /// Extracts the raw value from a (possibly edited) formatted value,
+    /// e.g. `(555) 123-4` -> `5551234` for `(###) ###-####`.
+    pub fn unmask(&self, formatted: &str) -> String {
+        self.unmask_with_cursor(formatted, 0).0
+    }
+
+    /// Like [`Self::unmask`], but also returns how many raw characters come before the char
+    /// index `cursor` in `formatted`.
+    fn unmask_with_cursor(&self, formatted: &str, cursor: usize) -> (String, usize) {
+        let mut raw = String::new();
+        let mut raw_before_cursor = 0;
+        let mut mask = self.chars.iter().peekable();
+        for (index, c) in formatted.chars().enumerate() {
+            if index == cursor {
+                raw_before_cursor = raw.chars().count();
+            }
+            // Literals that were typed (or are still there from formatting) are skipped,
+            // missing ones are filled in by format
+            let mut is_literal = false;
+            while let Some(MaskChar::Literal(literal)) =
+                mask.next_if(|m| matches!(m, MaskChar::Literal(_)))
+            {
+                if *literal == c {
+                    is_literal = true;
+                    break;
+                }
+            }
+            if is_literal {
+                continue;
+            }
+            match mask.peek() {
+                Some(slot) if slot.accepts(c) => {
+                    raw.push(c);
+                    mask.next();
+                }
+                _ => {}
+            }
+        }
+        if cursor >= formatted.chars().count() {
+            raw_before_cursor = raw.chars().count();
+        }
+        (raw, raw_before_cursor)
+    }
+
+    /// The char index in the formatted value after `raw_chars` raw characters
+    fn cursor_after(&self, formatted: &str, raw_chars: usize) -> usize {
+        if raw_chars == 0 {
+            return 0;
+        }
+        let mut seen = 0;
+        for (index, (c, mask)) in formatted.chars().zip(&self.chars).enumerate() {
+            if !matches!(mask, MaskChar::Literal(_)) && mask.accepts(c) {
+                seen += 1;
+                if seen == raw_chars {
+                    return index + 1;
+                }
+            }
+        }
+        formatted.chars().count()
+    }
+}
+
+/// A single line [`TextEdit`] that formats its input with an [`InputMask`] as the user types.
+///
+/// The bound string holds the raw, unmasked value (e.g. `5551234` instead of `(555) 123-4`),
+/// so that's what your model and validation see.
+/// Use it with [`crate::FormField::ui`] like any other widget.
+///
+/// # Example
+/// ```
+/// # use egui_form::{InputMask, MaskedTextEdit};
+/// # egui::__run_test_ui(|ui| {
+/// let mut phone = String::new();
+/// ui.add(MaskedTextEdit::new(&mut phone, InputMask::new("(###) ###-####")));
+/// # });
+/// ```
+pub struct MaskedTextEdit<'t> {
+    raw: &'t mut String,
+    mask: InputMask,
+    hint_text: Option<WidgetText>,
+}
+
+impl<'t> MaskedTextEdit<'t> {
+    /// Create a new masked text edit for the raw value.
+    pub fn new(raw: &'t mut String, mask: InputMask) -> Self {
+        Self {
+            raw,
+            mask,
+            hint_text: None,
+        }
+    }
+
+    /// Show a hint while the field is empty, e.g. the expected format.
+    pub fn hint_text(mut self, hint_text: impl Into<WidgetText>) -> Self {
+        self.hint_text = Some(hint_text.into());
+        self
+    }
+}
+
+impl Widget for MaskedTextEdit<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let mut text = self.mask.format(self.raw);
+
+        let mut edit = TextEdit::singleline(&mut text);
+        if let Some(hint_text) = self.hint_text {
+            edit = edit.hint_text(hint_text);
+        }
+        let mut output = edit.show(ui);
+
+        if output.response.changed() {
+            let cursor = output
+                .cursor_range
+                .map_or(text.chars().count(), |range| range.primary.ccursor.index);
+            let (raw, raw_before_cursor) = self.mask.unmask_with_cursor(&text, cursor);
+            *self.raw = raw;
+
+            // Keep the cursor behind the same raw character after reformatting
+            let formatted = self.mask.format(self.raw);
+            let cursor = self.mask.cursor_after(&formatted, raw_before_cursor);
+            output
+                .state
+                .cursor
+                .set_char_range(Some(CCursorRange::one(CCursor::new(cursor))));
+            output.state.store(ui.ctx(), output.response.id);
+        }
+
+        output.response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputMask;
+
+    fn phone() -> InputMask {
+        InputMask::new("(###) ###-####")
+    }
+
+    #[test]
+    fn format_partial_input() {
+        let mask = phone();
+        assert_eq!(mask.format(""), "");
+        assert_eq!(mask.format("5"), "(5");
+        assert_eq!(mask.format("555"), "(555");
+        assert_eq!(mask.format("5551"), "(555) 1");
+        assert_eq!(mask.format("5551234567"), "(555) 123-4567");
+        // Characters that don't fit are skipped, as are the ones beyond the mask
+        assert_eq!(mask.format("5a5-5"), "(555");
+        assert_eq!(mask.format("55512345678"), "(555) 123-4567");
+    }
+
+    #[test]
+    fn literals_at_the_ends() {
+        let mask = InputMask::new("$##.##%");
+        assert_eq!(mask.format("1"), "$1");
+        // Literals are only added in front of a character, so the trailing one never is
+        assert_eq!(mask.format("1234"), "$12.34");
+        assert_eq!(mask.unmask("$12.34%"), "1234");
+        assert!(mask.is_complete("1234"));
+        assert!(!mask.is_complete("123"));
+
+        let mask = InputMask::new(r"\#-##");
+        assert_eq!(mask.format("12"), "#-12");
+        assert_eq!(mask.unmask("#-12"), "12");
+    }
+
+    #[test]
+    fn unmask_edited_value() {
+        let mask = phone();
+        assert_eq!(mask.unmask("(555) 123-4"), "5551234");
+        // Missing literals are fine, typed ones are skipped
+        assert_eq!(mask.unmask("5551234"), "5551234");
+        assert_eq!(mask.unmask("(555) 12)3"), "555123");
+    }
+
+    #[test]
+    fn delete_across_literal() {
+        let mask = phone();
+
+        // Backspace after the literal removes the literal
+        let (raw, cursor) = mask.unmask_with_cursor("(555) ", 6);
+        assert_eq!(raw, "555");
+        assert_eq!(mask.format(&raw), "(555");
+        assert_eq!(mask.cursor_after(&mask.format(&raw), cursor), 4);
+
+        // Deleting the literal itself keeps the value, the cursor stays in front of it
+        let (raw, cursor) = mask.unmask_with_cursor("(555 1", 4);
+        assert_eq!(raw, "5551");
+        assert_eq!(cursor, 3);
+        assert_eq!(mask.cursor_after(&mask.format(&raw), cursor), 4);
+
+        // Deleting a digit in front of a literal moves the next digits over it
+        let (raw, cursor) = mask.unmask_with_cursor("(55) 123", 3);
+        assert_eq!(raw, "55123");
+        assert_eq!(mask.format(&raw), "(551) 23");
+        assert_eq!(mask.cursor_after(&mask.format(&raw), cursor), 3);
+    }
+
+    #[test]
+    fn cursor_after_raw_chars() {
+        let mask = phone();
+        let formatted = mask.format("5551234");
+        assert_eq!(mask.cursor_after(&formatted, 0), 0);
+        assert_eq!(mask.cursor_after(&formatted, 3), 4);
+        assert_eq!(mask.cursor_after(&formatted, 4), 7);
+        assert_eq!(mask.cursor_after(&formatted, 7), 11);
+        assert_eq!(mask.cursor_after(&formatted, 10), 11);
+    }
+
+    #[test]
+    fn multi_byte_chars() {
+        let mask = InputMask::new("AA-##");
+        let formatted = mask.format("äö12");
+        assert_eq!(formatted, "äö-12");
+        assert_eq!(mask.unmask(&formatted), "äö12");
+        // Cursors are char indices, not byte indices
+        assert_eq!(
+            mask.unmask_with_cursor(&formatted, 3),
+            ("äö12".to_owned(), 2)
+        );
+        assert_eq!(mask.cursor_after(&formatted, 2), 2);
+        assert_eq!(mask.cursor_after(&formatted, 3), 4);
+
+        let mask = InputMask::new("€ ###");
+        assert_eq!(mask.format("12"), "€ 12");
+        assert_eq!(mask.unmask_with_cursor("€ 12", 3), ("12".to_owned(), 1));
+    }
+}
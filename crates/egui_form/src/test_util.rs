@@ -0,0 +1,127 @@
+use egui::mutex::Mutex;
+use egui::{CentralPanel, Context, Id, RawInput, Ui};
+use std::sync::Arc;
+
+/// The validation state of the fields of a form, returned by [`validate_form`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormReport {
+    /// The fields in the order they were shown.
+    pub fields: Vec<FieldReport>,
+}
+
+/// The validation state of a single [`crate::FormField`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldReport {
+    /// The path of the field, e.g. `nested.vec[0].test`. `None` if the
+    /// [`crate::EguiValidationReport`] doesn't implement
+    /// [`crate::EguiValidationReport::field_name`].
+    pub name: Option<String>,
+    /// The label of the field, if it has one.
+    pub label: Option<String>,
+    /// The errors of the field, empty if it is valid.
+    pub errors: Vec<String>,
+}
+
+impl FormReport {
+    /// Returns true if none of the fields have an error.
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|field| field.errors.is_empty())
+    }
+
+    /// The fields that have an error.
+    pub fn invalid_fields(&self) -> impl Iterator<Item = &FieldReport> {
+        self.fields.iter().filter(|field| !field.errors.is_empty())
+    }
+
+    /// The field with the given name (see [`FieldReport::name`]) or label.
+    pub fn field(&self, field: &str) -> Option<&FieldReport> {
+        self.fields.iter().find(|report| {
+            report.name.as_deref() == Some(field) || report.label.as_deref() == Some(field)
+        })
+    }
+
+    /// The errors of the field with the given name (see [`FieldReport::name`]) or label.
+    ///
+    /// # Panics
+    /// If the form has no such field, so a typo in a test doesn't pass silently.
+    pub fn errors(&self, field: &str) -> &[String] {
+        match self.field(field) {
+            Some(report) => &report.errors,
+            None => panic!(
+                "The form has no field {field:?}, the fields are: {:?}",
+                self.fields
+                    .iter()
+                    .map(|report| report.name.as_ref().or(report.label.as_ref()))
+                    .collect::<Vec<_>>()
+            ),
+        }
+    }
+}
+
+/// Collects the fields shown while [`validate_form`] runs the form
+#[derive(Clone, Default)]
+pub(crate) struct Recorder(Arc<Mutex<Vec<FieldReport>>>);
+
+impl Recorder {
+    fn id() -> Id {
+        Id::new("egui_form_test_util_recorder")
+    }
+
+    /// Records a field, if the form is run via [`validate_form`]
+    pub(crate) fn record(ui: &Ui, field: impl FnOnce() -> FieldReport) {
+        if let Some(recorder) = ui.data(|data| data.get_temp::<Self>(Self::id())) {
+            recorder.0.lock().push(field());
+        }
+    }
+}
+
+/// Runs a form headlessly (without a window or renderer) against `model` and returns the
+/// validation state of its fields, so validation rules can be tested without showing the form.
+///
+/// `form_ui` is called like your app would call it in a frame and should show the
+/// [`crate::FormField`]s of the form.
+///
+/// ```
+/// # use egui_form::garde::GardeReport;
+/// # use egui_form::test_util::validate_form;
+/// # use egui_form::{Form, FormField};
+/// # use garde::Validate;
+/// #[derive(Validate)]
+/// struct Signup {
+///     #[garde(length(min = 3))]
+///     name: String,
+///     #[garde(range(min = 18))]
+///     age: u32,
+/// }
+///
+/// fn signup_form(ui: &mut egui::Ui, signup: &mut Signup) {
+///     let mut form = Form::new().add_report(GardeReport::new(signup.validate()));
+///     FormField::new(&mut form, "name")
+///         .label("Name")
+///         .ui(ui, egui::TextEdit::singleline(&mut signup.name));
+///     FormField::new(&mut form, "age")
+///         .label("Age")
+///         .ui(ui, egui::DragValue::new(&mut signup.age));
+/// }
+///
+/// let mut signup = Signup { name: "Al".into(), age: 30 };
+/// let report = validate_form(&mut signup, signup_form);
+///
+/// assert!(!report.is_valid());
+/// assert_eq!(report.errors("name").len(), 1);
+/// assert!(report.errors("Age").is_empty());
+/// ```
+pub fn validate_form<M>(model: &mut M, mut form_ui: impl FnMut(&mut Ui, &mut M)) -> FormReport {
+    let ctx = Context::default();
+    let recorder = Recorder::default();
+    ctx.data_mut(|data| data.insert_temp(Recorder::id(), recorder.clone()));
+
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        // Only keep the fields of the last pass, in case egui runs multiple passes
+        recorder.0.lock().clear();
+        CentralPanel::default().show(ctx, |ui| form_ui(ui, model));
+    });
+
+    let fields = std::mem::take(&mut *recorder.0.lock());
+    FormReport { fields }
+}
@@ -18,6 +18,14 @@ pub trait EguiValidationReport {
 
     /// Returns a reference to the errors.
     fn get_errors(&self) -> Option<&Self::Errors>;
+
+    /// Returns a readable name for a field, e.g. `nested.vec[0].test`.
+    /// This is used to identify fields in the reports of the `test_util` feature.
+    /// The default implementation returns `None`, so fields are only identified by their label.
+    fn field_name(field: &Self::FieldPath<'_>) -> Option<String> {
+        let _ = field;
+        None
+    }
 }
 
 /// Helper trait to allow constructing non-nested `FormFields` without using the `field_path`!() macro
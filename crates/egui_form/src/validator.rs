@@ -181,6 +181,30 @@ impl EguiValidationReport for ValidatorReport {
     fn get_errors(&self) -> Option<&Self::Errors> {
         self.errors.as_ref()
     }
+
+    fn field_name(field: &Self::FieldPath<'_>) -> Option<String> {
+        let items = match field {
+            ValidatorPathType::Single(item) => std::slice::from_ref(item),
+            ValidatorPathType::Borrowed(items) => items,
+        };
+        let mut name = String::new();
+        for item in items {
+            match item {
+                PathItem::Field(field) => {
+                    if !name.is_empty() {
+                        name.push('.');
+                    }
+                    name.push_str(field);
+                }
+                PathItem::Indexed(index) => {
+                    name.push('[');
+                    name.push_str(&index.to_string());
+                    name.push(']');
+                }
+            }
+        }
+        Some(name)
+    }
 }
 
 impl<'a> IntoFieldPath<ValidatorPathType<'a>> for ValidatorPathType<'a> {
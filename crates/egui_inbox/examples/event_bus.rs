@@ -0,0 +1,66 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, SidePanel, Ui};
+use egui_inbox::event_bus::{EventBus, Subscription};
+
+#[derive(Debug, Clone)]
+struct ItemSelected(usize);
+
+/// The list doesn't know about the details panel, it just sends events on the bus
+fn list_ui(ui: &mut Ui) {
+    for i in 0..10 {
+        if ui.button(format!("Item {i}")).clicked() {
+            EventBus::send(ui.ctx(), ItemSelected(i));
+        }
+    }
+}
+
+#[derive(Default)]
+struct DetailsPanel {
+    subscription: Option<Subscription<ItemSelected>>,
+    selected: Option<usize>,
+    show_log: bool,
+    log: Option<(Subscription<ItemSelected>, Vec<usize>)>,
+}
+
+impl DetailsPanel {
+    fn ui(&mut self, ui: &mut Ui) {
+        let subscription = self
+            .subscription
+            .get_or_insert_with(|| EventBus::subscribe(ui));
+        if let Some(ItemSelected(i)) = subscription.read(ui).last() {
+            self.selected = Some(i);
+        }
+        ui.label(format!("Selected: {:?}", self.selected));
+
+        // Every subscription has its own queue, the log also sees every event.
+        // Dropping the subscription when the log is hidden unsubscribes it.
+        ui.checkbox(&mut self.show_log, "Show log");
+        if self.show_log {
+            let (subscription, log) = self
+                .log
+                .get_or_insert_with(|| (EventBus::subscribe(ui), Vec::new()));
+            log.extend(subscription.read(ui).map(|ItemSelected(i)| i));
+            ui.label(format!("Log: {log:?}"));
+        } else {
+            self.log = None;
+        }
+
+        ui.label(format!(
+            "Subscribers: {}",
+            EventBus::subscriber_count::<ItemSelected>(ui.ctx())
+        ));
+    }
+}
+
+pub fn main() -> eframe::Result<()> {
+    let mut details = DetailsPanel::default();
+
+    eframe::run_simple_native(
+        "Event Bus Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            SidePanel::left("list").show(ctx, list_ui);
+            CentralPanel::default().show(ctx, |ui| details.ui(ui));
+        },
+    )
+}
@@ -0,0 +1,122 @@
+use std::sync::{Arc, Weak};
+
+use egui::{Context, Id, Ui};
+use parking_lot::Mutex;
+use type_map::concurrent::TypeMap;
+
+use crate::{UiInbox, UiInboxSender};
+
+/// A global event bus, stored in the [`Context`], for communication between parts of the ui that
+/// don't know about each other (e.g. two panels).
+///
+/// Call [`EventBus::subscribe`] to receive events of a type, and [`EventBus::send`] to send an
+/// event to all current subscribers of its type. Each [`Subscription`] has its own queue, so every
+/// subscriber sees every event sent while it was subscribed, and dropping the [`Subscription`]
+/// unsubscribes.
+///
+/// Unlike [`crate::type_broadcast::TypeBroadcast`], the bus doesn't need to be passed around,
+/// anything with access to the [`Context`] can send and subscribe.
+///
+/// NOTE: The queue of a subscription is only emptied when it is read, so make sure to read
+/// subscriptions that receive a lot of events even if their ui isn't shown.
+#[derive(Debug, Clone, Default)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<TypeMap>>,
+}
+
+/// The subscribers of one event type
+type Subscribers<T> = Arc<Mutex<SubscriberList<T>>>;
+
+#[derive(Debug)]
+struct SubscriberList<T> {
+    next_id: usize,
+    senders: Vec<(usize, UiInboxSender<T>)>,
+}
+
+impl<T> Default for SubscriberList<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            senders: Vec::new(),
+        }
+    }
+}
+
+impl EventBus {
+    /// The bus of this [`Context`], created on first use
+    fn global(ctx: &Context) -> Self {
+        ctx.data_mut(|data| data.get_temp_mut_or_default::<Self>(Id::NULL).clone())
+    }
+
+    fn subscribers<T: Send + 'static>(&self) -> Subscribers<T> {
+        self.subscribers
+            .lock()
+            .entry::<Subscribers<T>>()
+            .or_insert_with(Subscribers::default)
+            .clone()
+    }
+
+    /// Subscribe to events of type [T]. The returned [`Subscription`] receives all events sent
+    /// after this call, until it is dropped.
+    pub fn subscribe<T: Send + 'static>(ui: &Ui) -> Subscription<T> {
+        let subscribers = Self::global(ui.ctx()).subscribers::<T>();
+        let (tx, inbox) = UiInbox::channel_with_ctx(ui);
+        let id = {
+            let mut list = subscribers.lock();
+            let id = list.next_id;
+            list.next_id += 1;
+            list.senders.push((id, tx));
+            id
+        };
+        Subscription {
+            inbox,
+            id,
+            subscribers: Arc::downgrade(&subscribers),
+        }
+    }
+
+    /// Send an event to all subscribers of type [T], requesting a repaint.
+    /// Events sent while there are no subscribers are dropped.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn send<T: Clone + Send + 'static>(ctx: &Context, event: T) {
+        let subscribers = Self::global(ctx).subscribers::<T>();
+        let mut list = subscribers.lock();
+        list.senders
+            .retain(|(_, tx)| tx.send(event.clone()).is_ok());
+    }
+
+    /// The number of live subscriptions to events of type [T]
+    pub fn subscriber_count<T: Send + 'static>(ctx: &Context) -> usize {
+        Self::global(ctx).subscribers::<T>().lock().senders.len()
+    }
+}
+
+/// A subscription to events of type [T] on the [`EventBus`], with its own queue of events.
+/// Dropping it unsubscribes.
+#[derive(Debug)]
+pub struct Subscription<T> {
+    inbox: UiInbox<T>,
+    id: usize,
+    subscribers: Weak<Mutex<SubscriberList<T>>>,
+}
+
+impl<T> Subscription<T> {
+    /// Returns an iterator over all events received since the last read.
+    pub fn read(&self, ui: &Ui) -> impl Iterator<Item = T> {
+        self.inbox.read(ui)
+    }
+
+    /// Same as [`Subscription::read`], but without a [`Ui`], e.g. to read events in an update
+    /// function that runs even when the ui isn't shown.
+    pub fn read_without_ctx(&self) -> impl Iterator<Item = T> {
+        self.inbox.read_without_ctx()
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            subscribers.lock().senders.retain(|(id, _)| *id != self.id);
+        }
+    }
+}
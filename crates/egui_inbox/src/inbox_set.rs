@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+
+use crate::{AsRequestRepaint, RequestRepaintContext, UiInbox, UiInboxSender};
+
+trait MappedInbox<M> {
+    fn read_into(&self, ctx: Option<&RequestRepaintContext>, out: &mut Vec<M>);
+}
+
+struct Entry<T, M> {
+    inbox: UiInbox<T>,
+    map: fn(T) -> M,
+}
+
+impl<T, M> MappedInbox<M> for Entry<T, M> {
+    fn read_into(&self, ctx: Option<&RequestRepaintContext>, out: &mut Vec<M>) {
+        if let Some(ctx) = ctx {
+            out.extend(self.inbox.read(ctx).map(self.map));
+        } else {
+            out.extend(self.inbox.read_without_ctx().map(self.map));
+        }
+    }
+}
+
+/// A set of [`UiInbox`]es with different message types, that can be read all at once.
+/// Each inbox is added with a function that maps its messages to a common type `M`,
+/// usually an enum with one variant per inbox.
+///
+/// Messages are returned grouped by inbox, in the order the inboxes were added.
+///
+/// Example:
+/// ```
+/// use egui_inbox::UiInboxSet;
+///
+/// enum Message {
+///     Loaded(Vec<String>),
+///     Progress(f32),
+/// }
+///
+/// let mut set = UiInboxSet::new();
+/// let loaded = set.add(Message::Loaded);
+/// let progress = set.add(Message::Progress);
+///
+/// progress.send(0.5).ok();
+/// loaded.send(vec!["Hello".to_string()]).ok();
+///
+/// for message in set.read_without_ctx() {
+///     match message {
+///         Message::Loaded(items) => assert_eq!(items.len(), 1),
+///         Message::Progress(progress) => assert_eq!(progress, 0.5),
+///     }
+/// }
+/// ```
+pub struct UiInboxSet<M> {
+    inboxes: Vec<Box<dyn MappedInbox<M>>>,
+}
+
+impl<M> Debug for UiInboxSet<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UiInboxSet")
+            .field("inboxes", &self.inboxes.len())
+            .finish()
+    }
+}
+
+impl<M: 'static> Default for UiInboxSet<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: 'static> UiInboxSet<M> {
+    /// Create a new, empty inbox set.
+    pub fn new() -> Self {
+        Self {
+            inboxes: Vec::new(),
+        }
+    }
+
+    /// Create a new inbox in this set and return a sender for it.
+    /// Messages sent to it will be mapped with `map` when reading the set.
+    pub fn add<T: 'static>(&mut self, map: fn(T) -> M) -> UiInboxSender<T> {
+        let (sender, inbox) = UiInbox::channel();
+        self.insert(inbox, map);
+        sender
+    }
+
+    /// Add an existing inbox to this set.
+    /// Useful if you want to keep using the inbox, e.g. to spawn futures with it.
+    pub fn insert<T: 'static>(&mut self, inbox: UiInbox<T>, map: fn(T) -> M) {
+        self.inboxes.push(Box::new(Entry { inbox, map }));
+    }
+
+    /// Returns an iterator over all messages sent to any of the inboxes.
+    /// The inboxes are cleared after this call.
+    ///
+    /// The ui is only passed here so we can grab a reference to the egui context,
+    /// check [`UiInbox::read`] for more info.
+    pub fn read(&self, ui: &impl AsRequestRepaint) -> impl Iterator<Item = M> {
+        let ctx = ui.as_request_repaint();
+        let mut messages = Vec::new();
+        for inbox in &self.inboxes {
+            inbox.read_into(Some(&ctx), &mut messages);
+        }
+        messages.into_iter()
+    }
+
+    /// Same as [`UiInboxSet::read`], but you don't need to pass a reference to the ui.
+    /// Check [`UiInbox::read_without_ctx`] for more info.
+    pub fn read_without_ctx(&self) -> impl Iterator<Item = M> {
+        let mut messages = Vec::new();
+        for inbox in &self.inboxes {
+            inbox.read_into(None, &mut messages);
+        }
+        messages.into_iter()
+    }
+}
@@ -10,17 +10,30 @@ pub mod broadcast;
 #[cfg(feature = "type_broadcast")]
 pub mod type_broadcast;
 
+/// Global event bus stored in the egui [`egui::Context`], see [`event_bus::EventBus`].
+#[cfg(feature = "event_bus")]
+pub mod event_bus;
+
 /// Type-map based version of [`UiInbox`], useful for sending messages
 /// to specific components from different parts of the application.
 #[cfg(feature = "type_inbox")]
 pub mod type_inbox;
 
+/// Utilities to test widgets that use inboxes, see [`test_util::ManualExecutor`] and
+/// [`assert_received`].
+#[cfg(feature = "test_util")]
+pub mod test_util;
+
+mod inbox_set;
+
 use std::fmt::Debug;
 use std::mem;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
 
+pub use inbox_set::UiInboxSet;
+
 /// Trait to request a repaint.
 pub trait RequestRepaintTrait {
     /// Request a repaint.
@@ -39,7 +52,7 @@ where
 #[derive(Clone)]
 enum RequestRepaintInner {
     #[cfg(feature = "egui")]
-    Ctx(egui::Context),
+    Ctx(egui::Context, egui::ViewportId),
     Arc(Arc<dyn RequestRepaintTrait + Send + Sync>),
 }
 
@@ -65,9 +78,12 @@ impl RequestRepaintContext {
     }
 
     /// Create a new [`RequestRepaintContext`] from an [`egui::Context`].
+    /// The viewport that is currently being shown is remembered for
+    /// [`RequestRepaintContext::request_repaint_viewport`].
     #[cfg(feature = "egui")]
     pub fn from_egui_ctx(ctx: egui::Context) -> Self {
-        Self(RequestRepaintInner::Ctx(ctx))
+        let viewport_id = ctx.viewport_id();
+        Self(RequestRepaintInner::Ctx(ctx, viewport_id))
     }
 }
 
@@ -76,7 +92,17 @@ impl RequestRepaintContext {
     pub fn request_repaint(&self) {
         match &self.0 {
             #[cfg(feature = "egui")]
-            RequestRepaintInner::Ctx(ctx) => ctx.request_repaint(),
+            RequestRepaintInner::Ctx(ctx, _) => ctx.request_repaint(),
+            RequestRepaintInner::Arc(boxed) => boxed.request_repaint(),
+        }
+    }
+
+    /// Request a repaint of only the viewport this context was created in.
+    /// For callbacks, this is the same as [`RequestRepaintContext::request_repaint`].
+    pub fn request_repaint_viewport(&self) {
+        match &self.0 {
+            #[cfg(feature = "egui")]
+            RequestRepaintInner::Ctx(ctx, viewport_id) => ctx.request_repaint_of(*viewport_id),
             RequestRepaintInner::Arc(boxed) => boxed.request_repaint(),
         }
     }
@@ -170,6 +196,9 @@ struct State<T> {
     ctx: Option<RequestRepaintContext>,
     queue: Vec<T>,
     dropped: bool,
+    /// Notified when the inbox is dropped, see [`UiInboxSender::closed`]
+    #[cfg(feature = "async")]
+    closed_channels: Vec<futures_channel::oneshot::Sender<()>>,
 }
 
 impl<T> State<T> {
@@ -178,6 +207,8 @@ impl<T> State<T> {
             ctx,
             queue: Vec::new(),
             dropped: false,
+            #[cfg(feature = "async")]
+            closed_channels: Vec::new(),
         }
     }
 }
@@ -218,6 +249,12 @@ impl<T> Drop for UiInbox<T> {
 
         let mut state = self.state.lock();
         state.dropped = true;
+        // Nobody will read these anymore
+        state.queue.clear();
+        #[cfg(feature = "async")]
+        state.closed_channels.drain(..).for_each(|tx| {
+            tx.send(()).ok();
+        });
     }
 }
 
@@ -393,6 +430,24 @@ mod async_impl {
     }
 
     impl<T> UiInboxSender<T> {
+        /// Resolves once the inbox was dropped, so producers can stop their work.
+        /// Resolves immediately if the inbox was already dropped.
+        /// See also [`UiInboxSender::is_closed`].
+        pub async fn closed(&self) {
+            let rx = {
+                let mut state = self.state.lock();
+                if state.dropped {
+                    return;
+                }
+                let (tx, rx) = futures_channel::oneshot::channel();
+                // Clean up channels of closed() futures that were dropped before resolving
+                state.closed_channels.retain(|tx| !tx.is_canceled());
+                state.closed_channels.push(tx);
+                rx
+            };
+            rx.await.ok();
+        }
+
         /// Send each item of a stream to the inbox, as they come in.
         pub async fn send_stream(
             &mut self,
@@ -453,6 +508,28 @@ impl<T> UiInboxSender<T> {
             Ok(())
         }
     }
+
+    /// Returns true if the inbox was dropped, meaning any further sends will fail.
+    /// Use this to stop work whose result nobody will read.
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().dropped
+    }
+
+    /// Send an item to the inbox, only requesting a repaint of the viewport the inbox was read in.
+    /// In multi-viewport apps this avoids redrawing every window when a message is only relevant
+    /// to one of them. See [`UiInboxSender::send`] for details.
+    pub fn send_and_repaint_viewport(&self, item: T) -> Result<(), SendError<T>> {
+        let mut state = self.state.lock();
+        if state.dropped {
+            Err(SendError(item))
+        } else {
+            state.queue.push(item);
+            if let Some(ctx) = &state.ctx {
+                ctx.request_repaint_viewport();
+            }
+            Ok(())
+        }
+    }
 }
 
 /// Error returned when sending a message to the inbox fails.
@@ -0,0 +1,123 @@
+use std::future::Future;
+
+use futures::executor::{LocalPool, LocalSpawner};
+use futures::task::LocalSpawnExt;
+
+use crate::UiInbox;
+
+/// A single threaded executor that only makes progress when you tell it to.
+/// Spawn the futures that send to your inboxes here instead of on a runtime, and run them via
+/// [`ManualExecutor::run_until_stalled`] or [`ManualExecutor::step_until`]. This way tests are
+/// deterministic and don't need real threads or sleeps.
+///
+/// Example:
+/// ```
+/// use egui_inbox::test_util::ManualExecutor;
+/// use egui_inbox::{assert_received, UiInbox};
+///
+/// let mut executor = ManualExecutor::new();
+/// let (tx, inbox) = UiInbox::channel();
+///
+/// executor.spawn(async move {
+///     tx.send("loading").ok();
+///     // e.g. await a mocked request here
+///     tx.send("done").ok();
+/// });
+///
+/// assert_received!(inbox, []);
+/// executor.run_until_stalled();
+/// assert_received!(inbox, ["loading", "done"]);
+/// ```
+pub struct ManualExecutor {
+    pool: LocalPool,
+    spawner: LocalSpawner,
+}
+
+impl Default for ManualExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ManualExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManualExecutor").finish_non_exhaustive()
+    }
+}
+
+impl ManualExecutor {
+    /// Create a new executor without any tasks
+    pub fn new() -> Self {
+        let pool = LocalPool::new();
+        let spawner = pool.spawner();
+        Self { pool, spawner }
+    }
+
+    /// Spawn a future. It won't be polled until [`ManualExecutor::run_until_stalled`] or
+    /// [`ManualExecutor::step_until`] is called.
+    pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        self.spawner
+            .spawn_local(future)
+            .expect("The executor can't be shut down while it exists");
+    }
+
+    /// Poll the spawned futures until none of them can make progress anymore, e.g. because
+    /// they are all finished or wait for something that the test has to provide.
+    pub fn run_until_stalled(&mut self) {
+        self.pool.run_until_stalled();
+    }
+
+    /// Run the spawned futures and then call `step` until `done` returns true, at most
+    /// `max_steps` times. Returns true if `done` returned true.
+    ///
+    /// `step` should run a frame of the ui under test, so it reads its inboxes. With
+    /// `egui_kittest`, pass `|| harness.step()` (or `|| harness.run()`) and check the
+    /// harness's state in `done`.
+    pub fn step_until(
+        &mut self,
+        max_steps: usize,
+        mut step: impl FnMut(),
+        mut done: impl FnMut() -> bool,
+    ) -> bool {
+        for _ in 0..max_steps {
+            self.run_until_stalled();
+            step();
+            if done() {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Reads all messages that are currently in the inbox, without a [`egui::Context`].
+/// Used by [`crate::assert_received`].
+pub fn received<T>(inbox: &UiInbox<T>) -> Vec<T> {
+    inbox.read_without_ctx().collect()
+}
+
+/// Assert that an [`UiInbox`] received exactly the given messages, in order, since it was read
+/// last. The messages are read from the inbox.
+///
+/// ```
+/// # use egui_inbox::{assert_received, UiInbox};
+/// let (tx, inbox) = UiInbox::channel();
+/// tx.send(1).ok();
+/// tx.send(2).ok();
+/// assert_received!(inbox, [1, 2]);
+/// assert_received!(inbox, []);
+/// ```
+#[macro_export]
+macro_rules! assert_received {
+    ($inbox:expr, [] $(,)?) => {{
+        let received = $crate::test_util::received(&$inbox);
+        ::std::assert!(
+            received.is_empty(),
+            "expected no messages, but received {} message(s)",
+            received.len()
+        );
+    }};
+    ($inbox:expr, $messages:expr $(,)?) => {
+        ::std::assert_eq!($crate::test_util::received(&$inbox), $messages)
+    };
+}
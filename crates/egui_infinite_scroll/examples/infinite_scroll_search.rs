@@ -0,0 +1,74 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Color32, RichText, ScrollArea, TopBottomPanel};
+use egui_infinite_scroll::InfiniteScroll;
+
+const TOTAL_ITEMS: usize = 1000;
+
+pub fn main() -> eframe::Result<()> {
+    let mut infinite_scroll = InfiniteScroll::new().end_loader(|cursor, callback| {
+        let start = cursor.unwrap_or(0);
+        let end = (start + 50).min(TOTAL_ITEMS);
+        callback(Ok((
+            (start..end).collect(),
+            (end < TOTAL_ITEMS).then_some(end),
+        )));
+    });
+    let mut query = String::new();
+
+    eframe::run_simple_native(
+        "Infinite Scroll Search Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            TopBottomPanel::top("search").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.text_edit_singleline(&mut query).changed() {
+                        if query.is_empty() {
+                            infinite_scroll.clear_search();
+                        } else {
+                            let q = query.clone();
+                            infinite_scroll.set_search(move |item| item.to_string().contains(&q));
+                            // In a real app, the backend would report the total number of matches
+                            let q = query.clone();
+                            infinite_scroll.set_search_match_count(move || {
+                                (0..TOTAL_ITEMS)
+                                    .filter(|item| item.to_string().contains(&q))
+                                    .count()
+                            });
+                        }
+                    }
+                    if ui.button("Previous").clicked() {
+                        infinite_scroll.prev_match();
+                    }
+                    if ui.button("Next").clicked() {
+                        infinite_scroll.next_match();
+                    }
+                    if infinite_scroll.searching() {
+                        ui.spinner();
+                    }
+                    ui.label(format!(
+                        "{} loaded matches",
+                        infinite_scroll.match_indices().len()
+                    ));
+                });
+            });
+
+            CentralPanel::default().show(ctx, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+
+                    let current = infinite_scroll.current_match();
+                    let query = query.clone();
+                    infinite_scroll.ui(ui, 10, |ui, index, item| {
+                        let mut text = RichText::new(format!("Item {item}"));
+                        if current == Some(index) {
+                            text = text.strong().background_color(Color32::YELLOW);
+                        } else if !query.is_empty() && item.to_string().contains(&query) {
+                            text = text.background_color(Color32::LIGHT_YELLOW);
+                        }
+                        ui.label(text);
+                    });
+                });
+            });
+        },
+    )
+}
@@ -2,15 +2,15 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+use std::collections::{HashMap, VecDeque};
 use std::fmt::{Debug, Formatter};
-use std::mem;
 use std::ops::Range;
 
-use egui::Ui;
+use egui::{Context, Pos2, Rect, Ui};
 #[cfg(feature = "egui_extras")]
 use egui_extras::{TableBody, TableRow};
 
-use egui_inbox::UiInbox;
+use egui_inbox::{UiInbox, UiInboxSender};
 use egui_virtual_list::{VirtualList, VirtualListResponse};
 use hello_egui_utils::asyncify;
 
@@ -38,9 +38,207 @@ impl<T, C> LoadingState<T, C> {
 
 type CallbackResult<T, Cursor> = Result<(Vec<T>, Option<Cursor>), String>;
 type Callback<T, Cursor> = Box<dyn FnOnce(CallbackResult<T, Cursor>) + Send + Sync>;
-type Loader<T, Cursor> = Box<dyn FnMut(Option<Cursor>, Callback<T, Cursor>) + Send + Sync>;
+type Loader<T, Cursor> = Box<dyn FnMut(Option<Cursor>, PageSender<T, Cursor>) + Send + Sync>;
+
+/// Sent by a loader via the [`PageSender`].
+enum LoadEvent<T, Cursor> {
+    Chunk(Vec<T>),
+    Done(Option<Cursor>),
+    Error(String),
+}
+
+/// Passed to a streaming loader (see [`InfiniteScroll::end_loader_streaming`]) to deliver a page
+/// in multiple chunks. The items of each chunk are shown as soon as they are received, while the
+/// loading state stays [`LoadingState::Loading`] until [`PageSender::finish`] is called.
+///
+/// If the page fails with [`PageSender::error`] (or the sender is dropped without finishing the
+/// page), the chunks received so far are removed again, so retrying loads the whole page.
+pub struct PageSender<T, Cursor> {
+    sender: UiInboxSender<LoadEvent<T, Cursor>>,
+    done: bool,
+}
+
+impl<T, Cursor> PageSender<T, Cursor> {
+    /// Add a chunk of items to the page.
+    pub fn send_chunk(&self, items: Vec<T>) {
+        self.sender.send(LoadEvent::Chunk(items)).ok();
+    }
+
+    /// Mark the page as complete. Pass the cursor to load the next page with,
+    /// or `None` if there are no more items.
+    pub fn finish(mut self, cursor: Option<Cursor>) {
+        self.done = true;
+        self.sender.send(LoadEvent::Done(cursor)).ok();
+    }
+
+    /// Fail loading the page with an error.
+    pub fn error(mut self, error: impl Into<String>) {
+        self.done = true;
+        self.sender.send(LoadEvent::Error(error.into())).ok();
+    }
+}
+
+impl<T, Cursor> Drop for PageSender<T, Cursor> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.sender
+                .send(LoadEvent::Error(
+                    "The page sender was dropped without finishing the page".to_string(),
+                ))
+                .ok();
+        }
+    }
+}
 
 type FilterType<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+type ItemVisibleCallback<T> = Box<dyn FnMut(usize, &T, f32) + Send + Sync>;
+type MatchCount = Box<dyn Fn() -> usize + Send + Sync>;
+
+/// The state of the search, see [`InfiniteScroll::set_search`].
+struct Search<T> {
+    is_match: FilterType<T>,
+    total_matches: Option<MatchCount>,
+    /// The index of the current match, in the filtered items
+    current: Option<usize>,
+    /// Set while more pages are loaded to find the next (true) or previous (false) match
+    pending: Option<bool>,
+}
+
+/// The loader and cursor a page was loaded with, so it can be loaded again after it was evicted.
+#[derive(Debug, Clone)]
+struct PageSource<Cursor> {
+    from_start: bool,
+    cursor: Option<Cursor>,
+}
+
+/// A page of items, as returned by a loader.
+#[derive(Debug)]
+struct Page<Cursor> {
+    len: usize,
+    source: PageSource<Cursor>,
+}
+
+#[derive(Debug)]
+struct PendingLoad<Cursor> {
+    source: PageSource<Cursor>,
+    /// True if this loads a page that was evicted before.
+    reload: bool,
+    /// True if the loading state was [`LoadingState::NoMoreItems`] before the reload.
+    no_more_items: bool,
+    /// The number of items received so far, if the page is sent in chunks.
+    received: usize,
+}
+
+/// Rate limits the page loads at one end of the list, see [`InfiniteScroll::min_load_interval`]
+/// and [`InfiniteScroll::load_debounce`].
+#[derive(Debug, Default)]
+struct LoadLimiter {
+    /// The time the last page was requested
+    last_load: Option<f64>,
+    /// The time since which a load has been triggered in every frame
+    triggered_since: Option<f64>,
+}
+
+impl LoadLimiter {
+    /// Returns true if a page may be loaded now. If it's triggered but has to wait, a repaint is
+    /// requested for when it may load.
+    fn ready(&mut self, ctx: &Context, triggered: bool, min_interval: f32, debounce: f32) -> bool {
+        if !triggered {
+            self.triggered_since = None;
+            return false;
+        }
+        let now = ctx.input(|input| input.time);
+        let since = *self.triggered_since.get_or_insert(now);
+        let ready_at = (since + f64::from(debounce)).max(
+            self.last_load
+                .map_or(now, |last| last + f64::from(min_interval)),
+        );
+        if now >= ready_at {
+            true
+        } else {
+            ctx.request_repaint_after_secs((ready_at - now) as f32);
+            false
+        }
+    }
+
+    fn loaded(&mut self, ctx: &Context) {
+        self.last_load = Some(ctx.input(|input| input.time));
+        self.triggered_since = None;
+    }
+}
+
+/// Tracks how long the items have been visible, see [`InfiniteScroll::on_item_visible`].
+struct Impressions<T> {
+    callback: ItemVisibleCallback<T>,
+    /// The items that are currently visible, by their index in the filtered items
+    visible: HashMap<usize, VisibleItem>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct VisibleItem {
+    /// The time the item became visible
+    since: f64,
+    /// True once the callback was called for this visibility period
+    reported: bool,
+}
+
+impl<T> Impressions<T> {
+    /// Updates the visible items, `rows` are the item ranges shown in this frame with the
+    /// fraction of each row that is inside the scroll area.
+    fn update(
+        &mut self,
+        ctx: &Context,
+        rows: &[(Range<usize>, f32)],
+        items: &[&mut T],
+        min_visible_time: f32,
+        min_visible_fraction: f32,
+    ) {
+        let now = ctx.input(|input| input.time);
+        let mut visible = HashMap::new();
+        let mut next_report: Option<f32> = None;
+
+        let visible_rows = rows
+            .iter()
+            .filter(|(_, fraction)| *fraction > 0.0 && *fraction >= min_visible_fraction);
+        for (range, _) in visible_rows {
+            for index in range.clone() {
+                let mut item = self.visible.remove(&index).unwrap_or(VisibleItem {
+                    since: now,
+                    reported: false,
+                });
+                let visible_duration = (now - item.since) as f32;
+                if !item.reported {
+                    if visible_duration >= min_visible_time {
+                        if let Some(value) = items.get(index) {
+                            (self.callback)(index, value, visible_duration);
+                        }
+                        item.reported = true;
+                    } else {
+                        let remaining = min_visible_time - visible_duration;
+                        next_report =
+                            Some(next_report.map_or(remaining, |next| next.min(remaining)));
+                    }
+                }
+                visible.insert(index, item);
+            }
+        }
+
+        // Items that aren't visible anymore start a new visibility period when they come back
+        self.visible = visible;
+
+        if let Some(next_report) = next_report {
+            ctx.request_repaint_after_secs(next_report);
+        }
+    }
+
+    fn shift(&mut self, inserted: usize, removed: usize) {
+        self.visible = self
+            .visible
+            .drain()
+            .filter_map(|(index, item)| Some(((index + inserted).checked_sub(removed)?, item)))
+            .collect();
+    }
+}
 
 /// A infinite scroll widget.
 pub struct InfiniteScroll<T: Debug + Send + Sync, Cursor: Clone + Debug> {
@@ -56,10 +254,27 @@ pub struct InfiniteScroll<T: Debug + Send + Sync, Cursor: Clone + Debug> {
     top_loading_state: LoadingState<T, Cursor>,
     bottom_loading_state: LoadingState<T, Cursor>,
 
-    top_inbox: UiInbox<LoadingState<T, Cursor>>,
-    bottom_inbox: UiInbox<LoadingState<T, Cursor>>,
+    top_inbox: UiInbox<LoadEvent<T, Cursor>>,
+    bottom_inbox: UiInbox<LoadEvent<T, Cursor>>,
 
     filter: Option<FilterType<T>>,
+    search: Option<Search<T>>,
+
+    max_retained_items: Option<usize>,
+    pages: VecDeque<Page<Cursor>>,
+    evicted_start: Vec<PageSource<Cursor>>,
+    evicted_end: Vec<PageSource<Cursor>>,
+    top_pending: Option<PendingLoad<Cursor>>,
+    bottom_pending: Option<PendingLoad<Cursor>>,
+
+    min_load_interval: f32,
+    load_debounce: f32,
+    top_limiter: LoadLimiter,
+    bottom_limiter: LoadLimiter,
+
+    impressions: Option<Impressions<T>>,
+    min_visible_time: f32,
+    min_visible_fraction: f32,
 
     /// The `egui_virtual_list` instance. You can use this to customize settings of the virtual list.
     pub virtual_list: VirtualList,
@@ -82,6 +297,16 @@ where
             .field("top_inbox", &self.top_inbox)
             .field("bottom_inbox", &self.bottom_inbox)
             .field("filter", &self.filter.is_some())
+            .field("search", &self.search.is_some())
+            .field("max_retained_items", &self.max_retained_items)
+            .field("pages", &self.pages.len())
+            .field("evicted_start", &self.evicted_start.len())
+            .field("evicted_end", &self.evicted_end.len())
+            .field("min_load_interval", &self.min_load_interval)
+            .field("load_debounce", &self.load_debounce)
+            .field("impressions", &self.impressions.is_some())
+            .field("min_visible_time", &self.min_visible_time)
+            .field("min_visible_fraction", &self.min_visible_fraction)
             .field("virtual_list", &self.virtual_list)
             .finish()
     }
@@ -115,6 +340,20 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
             bottom_inbox,
             top_inbox,
             filter: None,
+            search: None,
+            max_retained_items: None,
+            pages: VecDeque::new(),
+            evicted_start: Vec::new(),
+            evicted_end: Vec::new(),
+            top_pending: None,
+            bottom_pending: None,
+            min_load_interval: 0.0,
+            load_debounce: 0.0,
+            top_limiter: LoadLimiter::default(),
+            bottom_limiter: LoadLimiter::default(),
+            impressions: None,
+            min_visible_time: 1.0,
+            min_visible_fraction: 0.5,
             virtual_list: VirtualList::new(),
         }
     }
@@ -130,11 +369,22 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         future: (impl FnMut(Option<Cursor>) -> F + Send + Sync + 'static),
         return_type: (Self),
         body: |(mut self,)| {
-            self.start_loader = Some(Box::new(f));
+            self.start_loader = Some(Self::callback_loader(f));
             self
         },
     }
 
+    /// Sets a loader for the start of the list that can deliver each page in multiple chunks,
+    /// via the [`PageSender`]. Useful for slow backends, so the first items of a page can be
+    /// shown right away while the rest streams in.
+    pub fn start_loader_streaming(
+        mut self,
+        f: impl FnMut(Option<Cursor>, PageSender<T, Cursor>) + Send + Sync + 'static,
+    ) -> Self {
+        self.start_loader = Some(Box::new(f));
+        self
+    }
+
     asyncify! {
         /// Sets the loader for the end of the list.
         end_loader,
@@ -146,11 +396,107 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         future: (impl FnMut(Option<Cursor>) -> F + Send + Sync + 'static),
         return_type: (Self),
         body: |(mut self,)| {
-            self.end_loader = Some(Box::new(f));
+            self.end_loader = Some(Self::callback_loader(f));
             self
         },
     }
 
+    /// Sets a loader for the end of the list that can deliver each page in multiple chunks,
+    /// via the [`PageSender`]. Useful for slow backends, so the first items of a page can be
+    /// shown right away while the rest streams in.
+    pub fn end_loader_streaming(
+        mut self,
+        f: impl FnMut(Option<Cursor>, PageSender<T, Cursor>) + Send + Sync + 'static,
+    ) -> Self {
+        self.end_loader = Some(Box::new(f));
+        self
+    }
+
+    fn callback_loader(
+        mut f: impl FnMut(Option<Cursor>, Callback<T, Cursor>) + Send + Sync + 'static,
+    ) -> Loader<T, Cursor> {
+        Box::new(move |cursor, sender: PageSender<T, Cursor>| {
+            f(
+                cursor,
+                Box::new(move |result| match result {
+                    Ok((items, cursor)) => {
+                        sender.send_chunk(items);
+                        sender.finish(cursor);
+                    }
+                    Err(err) => {
+                        sender.error(err);
+                    }
+                }),
+            );
+        })
+    }
+
+    /// Limit the number of items kept in memory.
+    /// Once there are more items, whole pages far from the visible items are evicted.
+    /// The loader and cursor each page was loaded with are kept, so evicted pages are loaded
+    /// again when they are scrolled back into view.
+    ///
+    /// Useful for endless feeds that might be left scrolling for hours.
+    /// The limit should be a few pages larger than the visible items plus the prefetch count,
+    /// otherwise pages can't be evicted without being loaded again right away.
+    ///
+    /// If you modify [`Self::items`] manually, no pages will be evicted until the next [`Self::reset`].
+    pub fn max_retained_items(mut self, max_retained_items: usize) -> Self {
+        self.max_retained_items = Some(max_retained_items);
+        self
+    }
+
+    /// Set the minimum time in seconds between the requests of two consecutive pages at the same
+    /// end of the list, so flinging through a feed doesn't hammer your backend.
+    /// Defaults to 0 (no limit).
+    pub fn min_load_interval(mut self, seconds: f32) -> Self {
+        self.min_load_interval = seconds;
+        self
+    }
+
+    /// Set how long in seconds the user has to stay close to an end of the list before a page is
+    /// loaded there. This ignores load triggers caused by scroll jitter, e.g. when quickly
+    /// scrolling past the start of the list. Loads for [`Self::next_match`] and
+    /// [`Self::prev_match`] are not debounced. Defaults to 0 (no debounce).
+    pub fn load_debounce(mut self, seconds: f32) -> Self {
+        self.load_debounce = seconds;
+        self
+    }
+
+    /// Call `f` with the index (in the filtered items), the item and the time in seconds it has
+    /// been visible, once an item has been visible for [`Self::min_visible_time`] with at least
+    /// [`Self::min_visible_fraction`] of it inside the scroll area. Useful to track impressions
+    /// of a feed for analytics.
+    ///
+    /// The callback is called once per visibility period: if the item is scrolled out of view
+    /// and back in, it is reported again. In [`Self::ui_columns`] and [`Self::ui_custom_layout`]
+    /// all items of a row share the visibility of the row. Items shown via [`Self::ui_table`]
+    /// aren't tracked.
+    pub fn on_item_visible(
+        mut self,
+        f: impl FnMut(usize, &T, f32) + Send + Sync + 'static,
+    ) -> Self {
+        self.impressions = Some(Impressions {
+            callback: Box::new(f),
+            visible: HashMap::new(),
+        });
+        self
+    }
+
+    /// Set how long in seconds an item has to be visible before [`Self::on_item_visible`] is
+    /// called for it. Defaults to 1 second.
+    pub fn min_visible_time(mut self, seconds: f32) -> Self {
+        self.min_visible_time = seconds;
+        self
+    }
+
+    /// Set the fraction (0.0 to 1.0) of an item's area that has to be inside the scroll area
+    /// for it to count as visible for [`Self::on_item_visible`]. Defaults to 0.5.
+    pub fn min_visible_fraction(mut self, fraction: f32) -> Self {
+        self.min_visible_fraction = fraction;
+        self
+    }
+
     /// Returns true if the initial loading is in progress (no items and loading state is loading)
     pub fn initial_loading(&self) -> bool {
         self.items.is_empty()
@@ -173,7 +519,8 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
     }
 
     /// Retry loading the top items
-    /// This only works if the top loading state is [`LoadingState::Error`]
+    /// This only works if the top loading state is [`LoadingState::Error`].
+    /// The page that failed is loaded again.
     pub fn retry_top(&mut self) {
         if let LoadingState::Error(_) = self.top_loading_state {
             self.top_loading_state = LoadingState::Idle;
@@ -181,7 +528,8 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
     }
 
     /// Retry loading the bottom items
-    /// This only works if the bottom loading state is [`LoadingState::Error`]
+    /// This only works if the bottom loading state is [`LoadingState::Error`].
+    /// The page that failed is loaded again.
     pub fn retry_bottom(&mut self) {
         if let LoadingState::Error(_) = self.bottom_loading_state {
             self.bottom_loading_state = LoadingState::Idle;
@@ -196,6 +544,21 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         self.bottom_loading_state = LoadingState::Idle;
         self.start_cursor = None;
         self.end_cursor = None;
+        self.pages.clear();
+        self.evicted_start.clear();
+        self.evicted_end.clear();
+        self.top_pending = None;
+        self.bottom_pending = None;
+        // The time of the last load is kept, so reloading is rate limited as well
+        self.top_limiter.triggered_since = None;
+        self.bottom_limiter.triggered_since = None;
+        if let Some(impressions) = &mut self.impressions {
+            impressions.visible.clear();
+        }
+        if let Some(search) = &mut self.search {
+            search.current = None;
+            search.pending = None;
+        }
 
         // Create new inboxes in case there is a request in progress
         self.top_inbox = UiInbox::new();
@@ -225,55 +588,290 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
     pub fn set_filter(&mut self, filter: impl Fn(&T) -> bool + Send + Sync + 'static) {
         self.filter = Some(Box::new(filter));
         self.virtual_list.reset();
+        // The indices of the matches and visible items changed
+        if let Some(search) = &mut self.search {
+            search.current = None;
+        }
+        if let Some(impressions) = &mut self.impressions {
+            impressions.visible.clear();
+        }
+    }
+
+    /// Search the items, e.g. for a search bar in a feed. Use [`Self::next_match`] and
+    /// [`Self::prev_match`] to scroll to the matching items.
+    ///
+    /// The search doesn't change how the items are shown. To highlight the matches, check
+    /// [`Self::current_match`] before showing the list and use your query in the item ui.
+    pub fn set_search(&mut self, is_match: impl Fn(&T) -> bool + Send + Sync + 'static) {
+        self.search = Some(Search {
+            is_match: Box::new(is_match),
+            total_matches: None,
+            current: None,
+            pending: None,
+        });
+    }
+
+    /// Set the total number of matches of the search, including items that aren't loaded yet,
+    /// e.g. as reported by your backend. If there are more matches than loaded ones,
+    /// [`Self::next_match`] and [`Self::prev_match`] load more pages until the next match is found,
+    /// instead of wrapping around.
+    ///
+    /// Call this after [`Self::set_search`].
+    pub fn set_search_match_count(&mut self, count: impl Fn() -> usize + Send + Sync + 'static) {
+        if let Some(search) = &mut self.search {
+            search.total_matches = Some(Box::new(count));
+        }
+    }
+
+    /// Clear the search set via [`Self::set_search`].
+    pub fn clear_search(&mut self) {
+        self.search = None;
+    }
+
+    /// Returns true if the item matches the search.
+    pub fn is_match(&self, item: &T) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|search| (search.is_match)(item))
+    }
+
+    /// The index of the current match, as passed to the item ui.
+    pub fn current_match(&self) -> Option<usize> {
+        self.search.as_ref().and_then(|search| search.current)
+    }
+
+    /// Returns true while more pages are loaded to find the next or previous match.
+    pub fn searching(&self) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|search| search.pending.is_some())
+    }
+
+    /// The indices (as passed to the item ui) of the loaded items matching the search.
+    pub fn match_indices(&self) -> Vec<usize> {
+        let Some(search) = &self.search else {
+            return Vec::new();
+        };
+        self.items
+            .iter()
+            .filter(|item| self.filter.as_ref().is_none_or(|filter| filter(item)))
+            .enumerate()
+            .filter(|(_, item)| (search.is_match)(item))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Scroll to the next match of the search. If there is none, more pages are loaded if
+    /// [`Self::set_search_match_count`] reports unloaded matches, otherwise it wraps around to
+    /// the first match.
+    pub fn next_match(&mut self) {
+        self.find_match(true);
+    }
+
+    /// Scroll to the previous match of the search. If there is none, more pages are loaded at the
+    /// start if [`Self::set_search_match_count`] reports unloaded matches, otherwise it wraps
+    /// around to the last match.
+    pub fn prev_match(&mut self) {
+        self.find_match(false);
+    }
+
+    fn find_match(&mut self, forward: bool) {
+        let matches = self.match_indices();
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        search.pending = None;
+
+        let next = if forward {
+            matches
+                .iter()
+                .find(|index| search.current.is_none_or(|current| **index > current))
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|index| search.current.is_none_or(|current| **index < current))
+        };
+        if let Some(next) = next {
+            search.current = Some(*next);
+            self.virtual_list.scroll_to_item(*next);
+            return;
+        }
+
+        let (state, loader, evicted) = if forward {
+            (
+                &self.bottom_loading_state,
+                &self.end_loader,
+                &self.evicted_end,
+            )
+        } else {
+            (
+                &self.top_loading_state,
+                &self.start_loader,
+                &self.evicted_start,
+            )
+        };
+        let can_load = loader.is_some()
+            && match state {
+                LoadingState::NoMoreItems => !evicted.is_empty(),
+                LoadingState::Error(_) => false,
+                _ => true,
+            };
+        let unloaded_matches = search
+            .total_matches
+            .as_ref()
+            .is_some_and(|total| total() > matches.len());
+
+        if can_load && unloaded_matches {
+            search.pending = Some(forward);
+        } else {
+            // Wrap around
+            search.current = if forward {
+                matches.first().copied()
+            } else {
+                matches.last().copied()
+            };
+            if let Some(current) = search.current {
+                self.virtual_list.scroll_to_item(current);
+            }
+        }
+    }
+
+    /// Updates the stored item indices after items were inserted or removed at the start.
+    /// The current match is cleared if it was removed.
+    fn shift_indices(&mut self, inserted: usize, removed: usize) {
+        if let Some(impressions) = &mut self.impressions {
+            impressions.shift(inserted, removed);
+        }
+        if let Some(search) = &mut self.search {
+            search.current = search
+                .current
+                .and_then(|current| (current + inserted).checked_sub(removed));
+        }
     }
 
     fn read_inboxes(&mut self, ui: &mut Ui) {
-        self.bottom_inbox.read(ui).for_each(|state| {
-            self.bottom_loading_state = match state {
-                LoadingState::Loaded(items, cursor) => {
-                    let has_cursor = cursor.is_some();
-                    if has_cursor {
-                        self.end_cursor = cursor;
+        self.bottom_inbox.read(ui).for_each(|event| match event {
+            LoadEvent::Chunk(items) => {
+                if let Some(pending) = &mut self.bottom_pending {
+                    pending.received += items.len();
+                }
+                self.items.extend(items);
+                ui.ctx().request_repaint();
+            }
+            LoadEvent::Done(cursor) => {
+                let pending = self.bottom_pending.take();
+                let received = pending.as_ref().map_or(0, |pending| pending.received);
+                if let Some(pending) = &pending {
+                    if received > 0 {
+                        self.pages.push_back(Page {
+                            len: received,
+                            source: pending.source.clone(),
+                        });
                     }
-                    let empty = items.is_empty();
-                    self.items.extend(items);
+                }
+                ui.ctx().request_repaint();
 
-                    ui.ctx().request_repaint();
-                    if empty || !has_cursor {
-                        LoadingState::NoMoreItems
-                    } else {
-                        LoadingState::Idle
-                    }
+                self.bottom_loading_state =
+                    Self::loaded_state(pending, received, cursor, &mut self.end_cursor);
+            }
+            LoadEvent::Error(err) => {
+                // Remove the chunks of the failed page, so retrying loads the whole page again.
+                // The app might have removed items from `items` in the meantime.
+                if let Some(pending) = &mut self.bottom_pending {
+                    self.items
+                        .truncate(self.items.len().saturating_sub(pending.received));
+                    pending.received = 0;
                 }
-                state => state,
-            };
+                self.bottom_loading_state = LoadingState::Error(err);
+            }
         });
 
-        self.top_inbox.read(ui).for_each(|state| {
-            self.top_loading_state = match state {
-                LoadingState::Loaded(items, cursor) => {
-                    self.virtual_list.items_inserted_at_start(items.len());
-                    let has_cursor = cursor.is_some();
-                    if has_cursor {
-                        self.start_cursor = cursor;
+        self.top_inbox.read(ui).for_each(|event| match event {
+            LoadEvent::Chunk(items) => {
+                // The chunks of a page are inserted in order, after the chunks received before
+                let position = self
+                    .top_pending
+                    .as_ref()
+                    .map_or(0, |pending| pending.received)
+                    .min(self.items.len());
+                if let Some(pending) = &mut self.top_pending {
+                    pending.received += items.len();
+                }
+                let inserted = Self::filtered_count(&items, self.filter.as_ref());
+                self.virtual_list.items_inserted_at_start(inserted);
+                self.shift_indices(inserted, 0);
+                self.items.splice(position..position, items);
+                ui.ctx().request_repaint();
+            }
+            LoadEvent::Done(cursor) => {
+                let pending = self.top_pending.take();
+                let received = pending.as_ref().map_or(0, |pending| pending.received);
+                if let Some(pending) = &pending {
+                    if received > 0 {
+                        self.pages.push_front(Page {
+                            len: received,
+                            source: pending.source.clone(),
+                        });
                     }
-                    let empty = items.is_empty();
-                    let mut old_items = mem::take(&mut self.items);
-                    self.items = items;
-                    self.items.append(&mut old_items);
-
-                    ui.ctx().request_repaint();
-                    if empty || !has_cursor {
-                        LoadingState::NoMoreItems
-                    } else {
-                        LoadingState::Idle
+                }
+                ui.ctx().request_repaint();
+
+                self.top_loading_state =
+                    Self::loaded_state(pending, received, cursor, &mut self.start_cursor);
+            }
+            LoadEvent::Error(err) => {
+                // Remove the chunks of the failed page, so retrying loads the whole page again
+                if let Some(pending) = &mut self.top_pending {
+                    let received = pending.received.min(self.items.len());
+                    let removed = self.items.drain(..received).collect::<Vec<_>>();
+                    pending.received = 0;
+                    let removed = Self::filtered_count(&removed, self.filter.as_ref());
+                    if removed > 0 {
+                        self.virtual_list.items_removed_at_start(removed);
+                        self.shift_indices(0, removed);
                     }
                 }
-                state => state,
-            };
+                self.top_loading_state = LoadingState::Error(err);
+            }
         });
     }
 
+    /// The loading state after a page was completely received.
+    fn loaded_state(
+        pending: Option<PendingLoad<Cursor>>,
+        received: usize,
+        cursor: Option<Cursor>,
+        current_cursor: &mut Option<Cursor>,
+    ) -> LoadingState<T, Cursor> {
+        // Reloading an evicted page restores the previous state, the cursor is already further
+        if let Some(pending) = pending.filter(|pending| pending.reload) {
+            return if pending.no_more_items {
+                LoadingState::NoMoreItems
+            } else {
+                LoadingState::Idle
+            };
+        }
+
+        let has_cursor = cursor.is_some();
+        if has_cursor {
+            *current_cursor = cursor;
+        }
+        if received == 0 || !has_cursor {
+            LoadingState::NoMoreItems
+        } else {
+            LoadingState::Idle
+        }
+    }
+
+    fn filtered_count(items: &[T], filter: Option<&FilterType<T>>) -> usize {
+        if let Some(filter) = filter {
+            items.iter().filter(|item| filter(item)).count()
+        } else {
+            items.len()
+        }
+    }
+
     fn filtered_items<'a>(items: &'a mut [T], filter: Option<&FilterType<T>>) -> Vec<&'a mut T> {
         if let Some(filter) = filter {
             items
@@ -296,60 +894,252 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         self.read_inboxes(ui);
 
         let mut items = Self::filtered_items(&mut self.items, self.filter.as_ref());
+        let track_visibility = self.impressions.is_some();
+        let mut rows = Vec::new();
 
         let response = self
             .virtual_list
             .ui_custom_layout(ui, items.len(), |ui, start_index| {
-                layout(ui, start_index, &mut items[start_index..])
+                let start = ui.next_widget_position();
+                let count = layout(ui, start_index, &mut items[start_index..]);
+                if track_visibility {
+                    rows.push((
+                        start_index..start_index + count,
+                        visible_fraction(ui, start),
+                    ));
+                }
+                count
             });
 
-        self.update_items(&response.item_range, end_prefetch);
+        if let Some(impressions) = &mut self.impressions {
+            impressions.update(
+                ui.ctx(),
+                &rows,
+                &items,
+                self.min_visible_time,
+                self.min_visible_fraction,
+            );
+        }
+
+        self.update_items(ui.ctx(), &response.item_range, end_prefetch);
 
         response
     }
 
-    fn update_items(&mut self, item_range: &Range<usize>, end_prefetch: usize) {
+    fn update_items(&mut self, ctx: &Context, item_range: &Range<usize>, end_prefetch: usize) {
+        self.evict_pages(item_range, end_prefetch);
+
+        // Check whether the pages loaded while searching contain the next match
+        let searching = self.search.as_ref().and_then(|search| search.pending);
+        if let Some(forward) = searching {
+            let loading = if forward {
+                self.bottom_loading_state.loading()
+            } else {
+                self.top_loading_state.loading()
+            };
+            if !loading {
+                self.find_match(forward);
+            }
+        }
+        let search_end = searching == Some(true);
+        let search_start = searching == Some(false);
+
         let items = Self::filtered_items(&mut self.items, self.filter.as_ref());
 
-        if item_range.end + end_prefetch >= items.len()
-            && matches!(self.bottom_loading_state, LoadingState::Idle { .. })
-        {
-            if let Some(end_loader) = &mut self.end_loader {
+        // Evicted pages are loaded again, even if there are no more new items
+        let can_load_bottom = match self.bottom_loading_state {
+            LoadingState::Idle => true,
+            LoadingState::NoMoreItems => !self.evicted_end.is_empty(),
+            _ => false,
+        };
+        let load_bottom = self.bottom_limiter.ready(
+            ctx,
+            (item_range.end + end_prefetch >= items.len() || search_end) && can_load_bottom,
+            self.min_load_interval,
+            if search_end { 0.0 } else { self.load_debounce },
+        );
+        if load_bottom {
+            // A page that failed to load is loaded again first, it's next to the last page
+            let pending = self.bottom_pending.take().unwrap_or_else(|| {
+                let reload = self.evicted_end.pop();
+                PendingLoad {
+                    reload: reload.is_some(),
+                    no_more_items: matches!(self.bottom_loading_state, LoadingState::NoMoreItems),
+                    received: 0,
+                    source: reload.unwrap_or_else(|| PageSource {
+                        from_start: false,
+                        cursor: self.end_cursor.clone(),
+                    }),
+                }
+            });
+            if Self::load(
+                &mut self.start_loader,
+                &mut self.end_loader,
+                &pending.source,
+                &self.bottom_inbox,
+            ) {
                 self.bottom_loading_state = LoadingState::Loading;
-                let sender = self.bottom_inbox.sender();
-                end_loader(
-                    self.end_cursor.clone(),
-                    Box::new(move |result| match result {
-                        Ok((items, cursor)) => {
-                            sender.send(LoadingState::Loaded(items, cursor)).ok();
-                        }
-                        Err(err) => {
-                            sender.send(LoadingState::Error(err.to_string())).ok();
-                        }
+                self.bottom_pending = Some(pending);
+                self.bottom_limiter.loaded(ctx);
+            } else if pending.reload {
+                self.evicted_end.push(pending.source);
+            }
+        }
+
+        let can_load_top = match self.top_loading_state {
+            LoadingState::Idle => true,
+            LoadingState::NoMoreItems => !self.evicted_start.is_empty(),
+            _ => false,
+        };
+        let load_top = self.top_limiter.ready(
+            ctx,
+            (item_range.start < end_prefetch || search_start) && can_load_top,
+            self.min_load_interval,
+            if search_start {
+                0.0
+            } else {
+                self.load_debounce
+            },
+        );
+        if load_top {
+            let pending = self.top_pending.take().unwrap_or_else(|| {
+                let reload = self.evicted_start.pop();
+                PendingLoad {
+                    reload: reload.is_some(),
+                    no_more_items: matches!(self.top_loading_state, LoadingState::NoMoreItems),
+                    received: 0,
+                    source: reload.unwrap_or_else(|| PageSource {
+                        from_start: true,
+                        cursor: self.start_cursor.clone(),
                     }),
-                );
+                }
+            });
+            if Self::load(
+                &mut self.start_loader,
+                &mut self.end_loader,
+                &pending.source,
+                &self.top_inbox,
+            ) {
+                self.top_loading_state = LoadingState::Loading;
+                self.top_pending = Some(pending);
+                self.top_limiter.loaded(ctx);
+            } else if pending.reload {
+                self.evicted_start.push(pending.source);
             }
         }
+    }
+
+    /// Calls the loader the page source refers to. Returns false if there is no such loader.
+    fn load(
+        start_loader: &mut Option<Loader<T, Cursor>>,
+        end_loader: &mut Option<Loader<T, Cursor>>,
+        source: &PageSource<Cursor>,
+        inbox: &UiInbox<LoadEvent<T, Cursor>>,
+    ) -> bool {
+        let loader = if source.from_start {
+            start_loader
+        } else {
+            end_loader
+        };
+        let Some(loader) = loader else {
+            return false;
+        };
+
+        loader(
+            source.cursor.clone(),
+            PageSender {
+                sender: inbox.sender(),
+                done: false,
+            },
+        );
+        true
+    }
 
-        if item_range.start < end_prefetch
-            && matches!(self.top_loading_state, LoadingState::Idle { .. })
+    /// Evicts pages far from the visible items, until there are at most
+    /// [`Self::max_retained_items`] items.
+    fn evict_pages(&mut self, item_range: &Range<usize>, end_prefetch: usize) {
+        let Some(max_retained_items) = self.max_retained_items else {
+            return;
+        };
+        // If the items were modified manually, the pages no longer match the items
+        // Pages that are still being received in chunks aren't in `pages` yet
+        let receiving = [&self.top_pending, &self.bottom_pending]
+            .into_iter()
+            .flatten()
+            .map(|pending| pending.received)
+            .sum::<usize>();
+        if self.items.len() <= max_retained_items
+            || self.pages.iter().map(|page| page.len).sum::<usize>() + receiving != self.items.len()
         {
-            if let Some(start_loader) = &mut self.start_loader {
-                self.top_loading_state = LoadingState::Loading;
-                let sender = self.top_inbox.sender();
-                start_loader(
-                    self.start_cursor.clone(),
-                    Box::new(move |result| match result {
-                        Ok((items, cursor)) => {
-                            sender.send(LoadingState::Loaded(items, cursor)).ok();
-                        }
-                        Err(err) => {
-                            sender.send(LoadingState::Error(err.to_string())).ok();
-                        }
-                    }),
-                );
+            return;
+        }
+
+        // The item range refers to the filtered items, so we need to map it to the actual items
+        let mut visible = if let Some(filter) = &self.filter {
+            let filtered_indices = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| filter(item))
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            let start = filtered_indices
+                .get(item_range.start)
+                .copied()
+                .unwrap_or(self.items.len());
+            let end = item_range
+                .end
+                .checked_sub(1)
+                .and_then(|last| filtered_indices.get(last))
+                .map_or(self.items.len(), |index| index + 1);
+            start..end.max(start)
+        } else {
+            item_range.clone()
+        };
+
+        let mut removed_at_start = 0;
+        while self.items.len() > max_retained_items && self.pages.len() > 1 {
+            let items_above = visible.start;
+            let items_below = self.items.len().saturating_sub(visible.end);
+            let first_len = self.pages.front().map_or(0, |page| page.len);
+            let last_len = self.pages.back().map_or(0, |page| page.len);
+
+            // Keep enough items around the visible ones, so the evicted page isn't
+            // immediately loaded again. Also don't evict on a side that is currently loading
+            // or has a page to retry, since that page has to be placed next to the page it was
+            // loaded after.
+            let can_evict_start =
+                items_above >= first_len + end_prefetch && self.top_pending.is_none();
+            let can_evict_end =
+                items_below > last_len + end_prefetch && self.bottom_pending.is_none();
+
+            if can_evict_start && (items_above >= items_below || !can_evict_end) {
+                let Some(page) = self.pages.pop_front() else {
+                    break;
+                };
+                let removed = self.items.drain(..page.len);
+                removed_at_start += if let Some(filter) = &self.filter {
+                    removed.filter(|item| filter(item)).count()
+                } else {
+                    removed.len()
+                };
+                visible = visible.start - page.len..visible.end - page.len;
+                self.evicted_start.push(page.source);
+            } else if can_evict_end {
+                let Some(page) = self.pages.pop_back() else {
+                    break;
+                };
+                self.items.truncate(self.items.len() - page.len);
+                self.evicted_end.push(page.source);
+            } else {
+                break;
             }
         }
+
+        if removed_at_start > 0 {
+            self.virtual_list.items_removed_at_start(removed_at_start);
+            self.shift_indices(0, removed_at_start);
+        }
     }
 
     /// A simple layout with multiple columns.
@@ -403,6 +1193,56 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         });
     }
 
+    /// A single column layout that shows a separator whenever the group key changes between
+    /// two consecutive items, e.g. a date header between the messages of a chat.
+    /// The first item always starts a new group.
+    ///
+    /// The keys are only computed for the visible items, so the separators stay up to date
+    /// as new pages are loaded, without having to insert separator items into [`Self::items`].
+    pub fn ui_grouped<K: PartialEq>(
+        &mut self,
+        ui: &mut Ui,
+        prefetch_count: usize,
+        group_key: impl Fn(&T) -> K,
+        mut separator_ui: impl FnMut(&mut Ui, &K),
+        mut item_ui: impl FnMut(&mut Ui, usize, &mut T),
+    ) {
+        self.read_inboxes(ui);
+
+        let mut items = Self::filtered_items(&mut self.items, self.filter.as_ref());
+        let track_visibility = self.impressions.is_some();
+        let mut rows = Vec::new();
+
+        let response = self
+            .virtual_list
+            .ui_custom_layout(ui, items.len(), |ui, index| {
+                let key = group_key(items[index]);
+                let starts_group = index == 0 || group_key(items[index - 1]) != key;
+                if starts_group {
+                    separator_ui(ui, &key);
+                }
+                // The separator doesn't count towards the visibility of the item
+                let start = ui.next_widget_position();
+                item_ui(ui, index, items[index]);
+                if track_visibility {
+                    rows.push((index..index + 1, visible_fraction(ui, start)));
+                }
+                1
+            });
+
+        if let Some(impressions) = &mut self.impressions {
+            impressions.update(
+                ui.ctx(),
+                &rows,
+                &items,
+                self.min_visible_time,
+                self.min_visible_fraction,
+            );
+        }
+
+        self.update_items(ui.ctx(), &response.item_range, prefetch_count);
+    }
+
     /// Layout for to use with [`egui_extras::TableBody`].
     #[cfg(feature = "egui_extras")]
     pub fn ui_table(
@@ -413,6 +1253,7 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         mut row_ui: impl FnMut(TableRow, &mut T),
     ) {
         self.read_inboxes(table.ui_mut());
+        let ctx = table.ui_mut().ctx().clone();
 
         let mut min_item = 0;
         let mut max_item = 0;
@@ -426,6 +1267,199 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         });
 
         let item_range = min_item..max_item;
-        self.update_items(&item_range, prefetch_count);
+        self.update_items(&ctx, &item_range, prefetch_count);
+    }
+}
+
+/// The fraction of the row laid out since `start` that is inside the clip rect of the `ui`.
+fn visible_fraction(ui: &Ui, start: Pos2) -> f32 {
+    let rect = Rect::from_x_y_ranges(
+        ui.max_rect().x_range(),
+        start.y..=ui.next_widget_position().y,
+    );
+    let visible = rect.intersect(ui.clip_rect());
+    if rect.area() > 0.0 && visible.is_positive() {
+        visible.area() / rect.area()
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use egui::{CentralPanel, Context, Pos2, RawInput, Rect, ScrollArea, Sense, Vec2};
+
+    use super::{InfiniteScroll, LoadingState, PageSender};
+
+    const PAGE_SIZE: usize = 10;
+
+    /// Loads the pages `0..pages`, failing while `fail` is set
+    fn scroll(pages: usize, fail: &Arc<AtomicBool>) -> InfiniteScroll<usize, usize> {
+        let fail = fail.clone();
+        InfiniteScroll::new()
+            .end_loader(move |cursor, callback| {
+                if fail.load(Ordering::Relaxed) {
+                    callback(Err("Failed".to_owned()));
+                    return;
+                }
+                let page = cursor.unwrap_or(0);
+                let items = (page * PAGE_SIZE..(page + 1) * PAGE_SIZE).collect();
+                callback(Ok((items, Some(page + 1).filter(|next| *next < pages))));
+            })
+            .max_retained_items(2 * PAGE_SIZE)
+    }
+
+    /// Runs a frame with the first (`at_end` = false) or last item visible
+    fn frame(scroll: &mut InfiniteScroll<usize, usize>, at_end: bool) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                scroll.read_inboxes(ui);
+                let range = if at_end {
+                    scroll.items.len().saturating_sub(1)..scroll.items.len()
+                } else {
+                    0..1
+                };
+                scroll.update_items(ctx, &range, 1);
+            });
+        });
+    }
+
+    fn assert_contiguous(items: &[usize]) {
+        assert!(
+            items.windows(2).all(|pair| pair[1] == pair[0] + 1),
+            "items out of order: {items:?}"
+        );
+    }
+
+    #[test]
+    fn retry_reloads_failed_evicted_page() {
+        let fail = Arc::new(AtomicBool::new(false));
+        let mut scroll = scroll(4, &fail);
+
+        for _ in 0..20 {
+            frame(&mut scroll, true);
+        }
+        assert_eq!(scroll.items, (20..40).collect::<Vec<_>>());
+        assert!(matches!(
+            scroll.bottom_loading_state,
+            LoadingState::NoMoreItems
+        ));
+
+        // Reloading the evicted pages at the start fails
+        fail.store(true, Ordering::Relaxed);
+        for _ in 0..5 {
+            frame(&mut scroll, false);
+        }
+        assert!(matches!(scroll.top_loading_state, LoadingState::Error(_)));
+        assert_eq!(scroll.items, (20..40).collect::<Vec<_>>());
+
+        fail.store(false, Ordering::Relaxed);
+        scroll.retry_top();
+        for _ in 0..20 {
+            frame(&mut scroll, false);
+        }
+        assert_eq!(scroll.items, (0..20).collect::<Vec<_>>());
+
+        // The pages evicted at the end while scrolling up fail the same way
+        fail.store(true, Ordering::Relaxed);
+        for _ in 0..5 {
+            frame(&mut scroll, true);
+        }
+        assert!(matches!(
+            scroll.bottom_loading_state,
+            LoadingState::Error(_)
+        ));
+        assert_contiguous(&scroll.items);
+
+        fail.store(false, Ordering::Relaxed);
+        scroll.retry_bottom();
+        for _ in 0..20 {
+            frame(&mut scroll, true);
+            assert_contiguous(&scroll.items);
+        }
+        assert_eq!(scroll.items, (20..40).collect::<Vec<_>>());
+        // The end of the list was reached before the pages were evicted
+        assert!(matches!(
+            scroll.bottom_loading_state,
+            LoadingState::NoMoreItems
+        ));
+    }
+
+    #[test]
+    fn evicted_current_match_is_cleared() {
+        let fail = Arc::new(AtomicBool::new(false));
+        let mut scroll = scroll(4, &fail);
+        scroll.set_search(|item| item % 20 == 5);
+
+        for _ in 0..2 {
+            frame(&mut scroll, false);
+        }
+        scroll.next_match();
+        assert_eq!(scroll.current_match(), Some(5));
+
+        for _ in 0..20 {
+            frame(&mut scroll, true);
+        }
+        assert_eq!(scroll.items, (20..40).collect::<Vec<_>>());
+        assert_eq!(scroll.current_match(), None);
+
+        scroll.next_match();
+        assert_eq!(scroll.current_match(), Some(5));
+        assert_eq!(scroll.items[5], 25);
+    }
+
+    #[test]
+    fn chunks_inserted_at_start_in_one_frame() {
+        let sender = Arc::new(Mutex::new(None::<PageSender<i32, i32>>));
+        let start_sender = sender.clone();
+        let mut scroll = InfiniteScroll::new()
+            .start_loader_streaming(move |_cursor, sender| {
+                *start_sender.lock().unwrap() = Some(sender);
+            })
+            .end_loader(|_cursor, callback| callback(Ok(((0..100).collect(), None))));
+
+        let ctx = Context::default();
+        // Returns the first item that is visible in the scroll area
+        let mut frame = || {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::splat(400.0))),
+                ..RawInput::default()
+            };
+            let mut first_visible = None;
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ScrollArea::vertical().animated(false).show(ui, |ui| {
+                        scroll.ui(ui, 5, |ui, _index, item| {
+                            let (rect, _) =
+                                ui.allocate_exact_size(Vec2::new(100.0, 20.0), Sense::hover());
+                            if first_visible.is_none() && rect.bottom() > ui.clip_rect().top() {
+                                first_visible = Some(*item);
+                            }
+                        });
+                    });
+                });
+            });
+            first_visible
+        };
+
+        for _ in 0..3 {
+            frame();
+        }
+        assert_eq!(frame(), Some(0));
+
+        let sender = sender.lock().unwrap().take().unwrap();
+        sender.send_chunk((-10..-5).collect());
+        sender.send_chunk((-5..0).collect());
+        sender.finish(None);
+
+        // The items above keep their scroll position, so the same item stays at the top
+        for _ in 0..3 {
+            frame();
+        }
+        assert_eq!(frame(), Some(0));
     }
 }
@@ -1,22 +1,14 @@
 #![allow(clippy::needless_pass_by_value)] // It's ok here as it is an example
 use eframe::NativeOptions;
 use egui::{CentralPanel, Color32, Frame, ScrollArea, Ui};
-use egui_inbox::UiInbox;
-use egui_router::{EguiRouter, Request, Route};
+use egui_router::{router_link, EguiRouter, Request, Route};
 
-type AppState = UiInbox<RouterMessage>;
-
-enum RouterMessage {
-    Navigate(String),
-    Back,
-}
+type AppState = ();
 
 #[tokio::main]
 async fn main() -> eframe::Result<()> {
     let mut router: Option<EguiRouter<AppState>> = None;
 
-    let mut inbox = UiInbox::new();
-
     eframe::run_simple_native(
         "Router Example",
         NativeOptions::default(),
@@ -25,52 +17,32 @@ async fn main() -> eframe::Result<()> {
                 EguiRouter::builder()
                     .route("/", home)
                     .route("/post/{id}", post)
+                    .fallback(not_found)
                     .default_path("/")
-                    .build(&mut inbox)
-            });
-
-            inbox.read(ctx).for_each(|msg| match msg {
-                RouterMessage::Navigate(route) => {
-                    router.navigate(&mut inbox, route).ok();
-                }
-                RouterMessage::Back => {
-                    router.back().ok();
-                }
+                    .build(&mut ())
             });
 
             CentralPanel::default().show(ctx, |ui| {
-                router.ui(ui, &mut inbox);
+                router.ui(ui, &mut ());
             });
         },
     )
 }
 
-fn home(_request: Request<AppState>) -> impl Route<AppState> {
-    |ui: &mut Ui, state: &mut AppState| {
+fn home(request: Request<AppState>) -> impl Route<AppState> {
+    // The handle navigates without mutable access to the router
+    let router = request.router();
+    move |ui: &mut Ui, _state: &mut AppState| {
         background(ui, ui.style().visuals.faint_bg_color, |ui| {
             ui.heading("Home!");
 
             ui.label("Navigate to post:");
 
-            if ui.link("Post 1").clicked() {
-                state
-                    .sender()
-                    .send(RouterMessage::Navigate("/post/1".to_string()))
-                    .ok();
-            }
-
-            if ui.link("Post 2").clicked() {
-                state
-                    .sender()
-                    .send(RouterMessage::Navigate("/post/2".to_string()))
-                    .ok();
-            }
+            router_link(ui, "Post 1", "/post/1");
+            router_link(ui, "Post 2", "/post/2");
 
             if ui.link("Invalid Post").clicked() {
-                state
-                    .sender()
-                    .send(RouterMessage::Navigate("/post/".to_string()))
-                    .ok();
+                router.navigate("/post/");
             }
         });
     }
@@ -78,22 +50,23 @@ fn home(_request: Request<AppState>) -> impl Route<AppState> {
 
 fn post(request: Request<AppState>) -> impl Route<AppState> {
     let id = request.params.get("id").map(ToOwned::to_owned);
+    let router = request.router();
 
-    move |ui: &mut Ui, inbox: &mut AppState| {
+    move |ui: &mut Ui, _state: &mut AppState| {
         background(ui, ui.style().visuals.extreme_bg_color, |ui| {
             ScrollArea::vertical().show(ui, |ui| {
                 if let Some(id) = &id {
                     ui.label(format!("Post: {id}"));
 
                     if ui.button("back").clicked() {
-                        inbox.sender().send(RouterMessage::Back).ok();
+                        router.back();
                     }
 
                     ui.label(include_str!("../../../README.md"));
                 } else {
                     ui.label("Post not found");
                     if ui.button("back").clicked() {
-                        inbox.sender().send(RouterMessage::Back).ok();
+                        router.back();
                     }
                 }
             });
@@ -101,6 +74,18 @@ fn post(request: Request<AppState>) -> impl Route<AppState> {
     }
 }
 
+fn not_found(request: Request<AppState>) -> impl Route<AppState> {
+    let router = request.router();
+    move |ui: &mut Ui, _state: &mut AppState| {
+        background(ui, ui.style().visuals.extreme_bg_color, |ui| {
+            ui.heading("Page not found");
+            if ui.button("back").clicked() {
+                router.back();
+            }
+        });
+    }
+}
+
 fn background(ui: &mut Ui, color: Color32, content: impl FnOnce(&mut Ui)) {
     Frame::NONE.fill(color).inner_margin(16.0).show(ui, |ui| {
         ui.set_width(ui.available_width());
@@ -0,0 +1,70 @@
+#[cfg(feature = "accesskit")]
+use egui::accesskit;
+use egui::Ui;
+
+/// Announces route changes to screen readers via an `AccessKit` live region, so they know the
+/// page changed even though the focus stays where it was
+#[cfg(feature = "accesskit")]
+#[derive(Debug, Default)]
+pub(crate) struct RouteAnnouncer {
+    /// The active route when the announcement was updated last
+    route: Option<usize>,
+    /// The text of the live region, changed when the active route changes
+    announcement: String,
+}
+
+#[cfg(feature = "accesskit")]
+impl RouteAnnouncer {
+    /// Updates the live region. `announcement` is called when the active route changed (but not
+    /// for the initial route), screen readers read the new text.
+    pub(crate) fn ui(
+        &mut self,
+        ui: &Ui,
+        route: Option<usize>,
+        announcement: impl FnOnce() -> String,
+    ) {
+        if self.route != route {
+            if self.route.is_some() {
+                self.announcement = announcement();
+            }
+            self.route = route;
+        }
+
+        ui.ctx()
+            .accesskit_node_builder(ui.id().with("egui_router_announcement"), |node| {
+                node.set_role(accesskit::Role::Status);
+                node.set_live(accesskit::Live::Polite);
+                node.set_label(self.announcement.as_str());
+            });
+    }
+}
+
+/// Shows a route as a region, labeled with its title, so screen reader users can jump to the
+/// content of the active route. Without the `accesskit` feature, the content is shown as is.
+pub(crate) fn route_region(ui: &mut Ui, title: Option<&str>, content: impl FnOnce(&mut Ui)) {
+    #[cfg(feature = "accesskit")]
+    {
+        let id = ui.id().with("egui_router_route");
+        let rect = ui.max_rect();
+        ui.ctx().accesskit_node_builder(id, |node| {
+            node.set_role(accesskit::Role::Region);
+            if let Some(title) = title {
+                node.set_label(title);
+            }
+            node.set_bounds(accesskit::Rect {
+                x0: rect.min.x.into(),
+                y0: rect.min.y.into(),
+                x1: rect.max.x.into(),
+                y1: rect.max.y.into(),
+            });
+        });
+        ui.ctx()
+            .clone()
+            .with_accessibility_parent(id, || content(ui));
+    }
+    #[cfg(not(feature = "accesskit"))]
+    {
+        let _ = title;
+        content(ui);
+    }
+}
@@ -1,16 +1,28 @@
 use crate::handler::HandlerError;
-use crate::Route;
+use crate::{Route, RouteOrRedirect, RouterHandle};
 use egui::Ui;
 use egui_suspense::EguiSuspense;
 
 pub(crate) struct AsyncRoute<State> {
-    pub suspense: EguiSuspense<Box<dyn Route<State> + Send + Sync>, HandlerError>,
+    pub suspense: EguiSuspense<RouteOrRedirect<Box<dyn Route<State> + Send + Sync>>, HandlerError>,
+    /// Used to follow a redirect of the handler
+    pub handle: RouterHandle,
+    pub redirected: bool,
 }
 
 impl<State: 'static> AsyncRoute<State> {
     pub fn ui(&mut self, ui: &mut egui::Ui, state: &mut State) {
-        self.suspense.ui(ui, |ui, data, _state| {
-            data.ui(ui, state);
+        let handle = &self.handle;
+        let redirected = &mut self.redirected;
+        self.suspense.ui(ui, |ui, data, _state| match data {
+            RouteOrRedirect::Route(route) => route.ui(ui, state),
+            RouteOrRedirect::Redirect(path) => {
+                // The handler is done, so the route replaces itself with the redirect
+                if !*redirected {
+                    *redirected = true;
+                    handle.replace(path.clone());
+                }
+            }
         });
     }
 }
@@ -19,4 +31,12 @@ impl<State: 'static> Route<State> for AsyncRoute<State> {
     fn ui(&mut self, ui: &mut Ui, state: &mut State) {
         self.ui(ui, state);
     }
+
+    fn loading(&self) -> bool {
+        self.suspense.loading()
+            || self.suspense.data().is_some_and(|data| match data {
+                RouteOrRedirect::Route(route) => route.loading(),
+                RouteOrRedirect::Redirect(_) => true,
+            })
+    }
 }
@@ -0,0 +1,131 @@
+/// Turns a deep link into a url of the router, removing a leading scheme like `myapp://`,
+/// see [`crate::EguiRouter::navigate_initial`]
+pub(crate) fn deep_link_url(path: &str) -> String {
+    let path = path.trim();
+    let path = match path.split_once("://") {
+        Some((scheme, rest)) if is_scheme(scheme) => rest,
+        _ => path,
+    };
+    if path.starts_with('/') {
+        path.to_owned()
+    } else {
+        format!("/{path}")
+    }
+}
+
+/// Whether `scheme` is a valid url scheme: a letter followed by letters, digits, `+`, `-` or `.`
+fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Finds the deep link in command line arguments, see
+/// [`crate::EguiRouter::navigate_from_args`]
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn deep_link_from_args(
+    args: impl IntoIterator<Item = String>,
+    scheme: Option<&str>,
+) -> Option<String> {
+    let scheme_prefix = scheme.map(|scheme| format!("{scheme}://"));
+    let mut args = args.into_iter();
+    let mut scheme_link = None;
+    while let Some(arg) = args.next() {
+        if arg == "--route" {
+            return args.next();
+        } else if let Some(route) = arg.strip_prefix("--route=") {
+            return Some(route.to_owned());
+        } else if scheme_link.is_none()
+            && scheme_prefix.as_ref().is_some_and(|prefix| {
+                arg.get(..prefix.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            })
+        {
+            scheme_link = Some(arg);
+        }
+    }
+    scheme_link
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_leading_scheme() {
+        assert_eq!(
+            deep_link_url("myapp://settings/account"),
+            "/settings/account"
+        );
+        assert_eq!(
+            deep_link_url("my-app+v2://settings?tab=2"),
+            "/settings?tab=2"
+        );
+        assert_eq!(deep_link_url(" myapp:///settings "), "/settings");
+        assert_eq!(deep_link_url("myapp://"), "/");
+    }
+
+    #[test]
+    fn keeps_scheme_like_parts_of_the_path() {
+        assert_eq!(deep_link_url("/search?u=http://x"), "/search?u=http://x");
+        assert_eq!(deep_link_url("search?u=http://x"), "/search?u=http://x");
+        assert_eq!(deep_link_url("/files/a://b"), "/files/a://b");
+        assert_eq!(deep_link_url("/post#http://x"), "/post#http://x");
+        assert_eq!(deep_link_url("1app://settings"), "/1app://settings");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn route_argument() {
+        let from_args = |a: &[&str]| deep_link_from_args(args(a), Some("myapp"));
+        assert_eq!(
+            from_args(&["--route", "/settings"]).as_deref(),
+            Some("/settings")
+        );
+        assert_eq!(
+            from_args(&["--route=/settings"]).as_deref(),
+            Some("/settings")
+        );
+        assert_eq!(from_args(&["--route"]), None);
+        // `--route` wins over an url
+        assert_eq!(
+            from_args(&["myapp://about", "--route", "/settings"]).as_deref(),
+            Some("/settings")
+        );
+        assert_eq!(from_args(&[]), None);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn scheme_argument() {
+        let from_args = |a: &[&str]| deep_link_from_args(args(a), Some("myapp"));
+        assert_eq!(
+            from_args(&["--verbose", "myapp://settings"]).as_deref(),
+            Some("myapp://settings")
+        );
+        assert_eq!(
+            from_args(&["MyApp://settings"]).as_deref(),
+            Some("MyApp://settings")
+        );
+        assert_eq!(
+            from_args(&["myapp://a", "myapp://b"]).as_deref(),
+            Some("myapp://a")
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn ignores_other_urls() {
+        let from_args = |a: &[&str]| deep_link_from_args(args(a), Some("myapp"));
+        assert_eq!(from_args(&["--log=http://localhost:4317"]), None);
+        assert_eq!(from_args(&["file:///home/me/notes.txt"]), None);
+        assert_eq!(from_args(&["otherapp://settings"]), None);
+        assert_eq!(from_args(&["myapp:/settings"]), None);
+        assert_eq!(deep_link_from_args(args(&["myapp://settings"]), None), None);
+    }
+}
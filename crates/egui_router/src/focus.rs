@@ -0,0 +1,123 @@
+use crate::shared_element::SharedElementContext;
+use egui::{Context, Id, Rect, Response, Sense, Ui, Vec2, WidgetInfo, WidgetType};
+use std::collections::HashMap;
+
+/// Moves the keyboard focus when the active route changes, see
+/// [`crate::RouterBuilder::focus_on_navigate`]
+#[derive(Debug, Default)]
+pub(crate) struct RouteFocus {
+    /// The active route when the focus was updated last
+    route: Option<usize>,
+    /// The focused widgets of the routes we navigated away from, focused again when going back
+    saved: HashMap<usize, Id>,
+    /// The new route, focused once it was shown
+    pending: Option<PendingFocus>,
+}
+
+#[derive(Debug)]
+struct PendingFocus {
+    route: usize,
+    /// Whether the route was shown in this pass, so its widgets exist
+    shown: bool,
+    /// The widget passed to [`route_autofocus`], if any
+    target: Option<Id>,
+}
+
+impl RouteFocus {
+    fn anchor_id(route: usize) -> Id {
+        Id::new("egui_router_focus_anchor").with(route)
+    }
+
+    /// Called after the routes were shown, `routes` are the ids of the routes in the history.
+    /// Saves the focus of the route we left and moves it to the new route.
+    pub(crate) fn update(&mut self, ctx: &Context, routes: &[usize]) {
+        let active = routes.last().copied();
+        if self.route != active {
+            if let Some(previous) = self.route {
+                if let Some(focused) = ctx.memory(egui::Memory::focused) {
+                    self.saved.insert(previous, focused);
+                }
+            }
+            self.saved.retain(|route, _| routes.contains(route));
+
+            self.pending = None;
+            // The initial route doesn't take the focus
+            if self.route.is_some() {
+                if let Some(route) = active {
+                    if let Some(id) = self.saved.remove(&route) {
+                        ctx.memory_mut(|memory| memory.request_focus(id));
+                    } else {
+                        self.pending = Some(PendingFocus {
+                            route,
+                            shown: false,
+                            target: None,
+                        });
+                    }
+                }
+            }
+            self.route = active;
+        }
+
+        if let Some(pending) = self.pending.take_if(|pending| pending.shown) {
+            let id = pending
+                .target
+                .unwrap_or_else(|| Self::anchor_id(pending.route));
+            ctx.memory_mut(|memory| memory.request_focus(id));
+        }
+    }
+}
+
+/// Shows an invisible focusable widget at the start of the route while it is about to get (or
+/// has) the focus, so the focus can be moved into routes without a [`route_autofocus`] widget.
+/// Pressing tab then focuses the first focusable widget of the route. Screen readers read the
+/// `title` of the route.
+pub(crate) fn focus_anchor(ui: &Ui, title: Option<&str>) {
+    let Some(context) = SharedElementContext::get(ui) else {
+        return;
+    };
+    let id = RouteFocus::anchor_id(context.route);
+
+    let pending = match context.focus.lock().pending.as_mut() {
+        Some(pending) if pending.route == context.route => {
+            pending.shown = true;
+            true
+        }
+        _ => false,
+    };
+    if pending || ui.memory(|memory| memory.has_focus(id)) {
+        let rect = Rect::from_min_size(ui.max_rect().min, Vec2::ZERO);
+        let response = ui.interact(rect, id, Sense::focusable_noninteractive());
+        response.widget_info(|| {
+            WidgetInfo::labeled(WidgetType::Other, true, title.unwrap_or_default())
+        });
+    }
+}
+
+/// Focus this widget when its route is opened, instead of the start of the route, e.g. the
+/// search field of a search page. Only has an effect if
+/// [`crate::RouterBuilder::focus_on_navigate`] is enabled. Going back to the route focuses the
+/// widget that was focused when the route was left instead.
+///
+/// Call it every frame the widget is shown. Outside of routes rendered by a
+/// [`crate::EguiRouter`], this does nothing.
+///
+/// ```
+/// # use egui_router::route_autofocus;
+/// fn search(ui: &mut egui::Ui, query: &mut String) {
+///     let response = ui.text_edit_singleline(query);
+///     route_autofocus(ui, &response);
+/// }
+/// ```
+pub fn route_autofocus(ui: &Ui, response: &Response) {
+    if let Some(context) = SharedElementContext::get(ui) {
+        if let Some(pending) = context
+            .focus
+            .lock()
+            .pending
+            .as_mut()
+            .filter(|pending| pending.route == context.route)
+        {
+            pending.target = Some(response.id);
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::Request;
+
+/// Returned by a route guard to decide what happens with a navigation,
+/// see [`crate::RouterBuilder::guard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardAction {
+    /// Continue with the navigation (and run the next guard)
+    Allow,
+    /// Cancel the navigation, the current route stays active.
+    /// The navigation returns [`crate::RouterError::Cancelled`].
+    Cancel,
+    /// Navigate to another path instead
+    Redirect(String),
+}
+
+pub(crate) type Guard<State> = Box<dyn FnMut(&str, &mut Request<State>) -> GuardAction>;
@@ -0,0 +1,70 @@
+use egui_inbox::UiInboxSender;
+
+/// A navigation request sent via a [`RouterHandle`]
+#[derive(Debug)]
+pub(crate) enum RouterCommand {
+    Navigate(String),
+    Replace(String),
+    Preload(String),
+    Back,
+}
+
+/// A handle to navigate a [`crate::EguiRouter`] without mutable access to it, e.g. from a
+/// button callback of a route or a background task. It is cheap to clone and `Send`.
+///
+/// Get it via [`crate::Request::router`] in a route handler or via
+/// [`crate::EguiRouter::handle`]. The navigation happens in the router's next call to
+/// [`crate::EguiRouter::ui`], using the router's default transitions. Errors (e.g. if the route
+/// can't be found) are ignored, like for a [`crate::RouterLink`], and nothing happens once the
+/// router was dropped.
+///
+/// ```
+/// # use egui_router::{EguiRouter, Request, Route};
+/// fn post(request: Request) -> impl Route {
+///     let router = request.router();
+///     move |ui: &mut egui::Ui, _state: &mut ()| {
+///         if ui.button("Back").clicked() {
+///             router.back();
+///         }
+///     }
+/// }
+///
+/// let router: EguiRouter<()> = EguiRouter::builder()
+///     .route("/", || |ui: &mut egui::Ui, _: &mut ()| {})
+///     .route("/post/{id}", post)
+///     .build(&mut ());
+///
+/// let handle = router.handle();
+/// std::thread::spawn(move || handle.navigate("/post/1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RouterHandle {
+    pub(crate) sender: UiInboxSender<RouterCommand>,
+}
+
+impl RouterHandle {
+    /// Navigate to `path`, see [`crate::EguiRouter::navigate`]
+    pub fn navigate(&self, path: impl Into<String>) {
+        self.sender.send(RouterCommand::Navigate(path.into())).ok();
+    }
+
+    /// Replace the active route with `path`, see [`crate::EguiRouter::replace`]
+    pub fn replace(&self, path: impl Into<String>) {
+        self.sender.send(RouterCommand::Replace(path.into())).ok();
+    }
+
+    /// Preload the route at `path`, see [`crate::EguiRouter::preload`]
+    pub fn preload(&self, path: impl Into<String>) {
+        self.sender.send(RouterCommand::Preload(path.into())).ok();
+    }
+
+    /// Go back to the previous route, see [`crate::EguiRouter::back`]
+    pub fn back(&self) {
+        self.sender.send(RouterCommand::Back).ok();
+    }
+
+    /// Returns true if the router was dropped, so the handle doesn't do anything anymore
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
@@ -17,23 +17,86 @@ pub enum HandlerError {
 /// Handler Result type
 pub type HandlerResult<T = ()> = Result<T, HandlerError>;
 
+/// Returned by a handler that either shows a route or redirects to another path, e.g. for
+/// conditional entry points.
+///
+/// ```
+/// # use egui_router::{EguiRouter, Request, Route, RouteOrRedirect};
+/// fn page(_req: Request<bool>) -> impl Route<bool> {
+///     |ui: &mut egui::Ui, _: &mut bool| {}
+/// }
+///
+/// fn start(req: Request<bool>) -> RouteOrRedirect<impl Route<bool>> {
+///     if *req.state {
+///         RouteOrRedirect::Route(|ui: &mut egui::Ui, _: &mut bool| {
+///             ui.label("Welcome back!");
+///         })
+///     } else {
+///         RouteOrRedirect::Redirect("/onboarding".to_owned())
+///     }
+/// }
+///
+/// let mut onboarded = false;
+/// let router: EguiRouter<bool> = EguiRouter::builder()
+///     .route("/", start)
+///     .route("/onboarding", page)
+///     .default_path("/")
+///     .build(&mut onboarded);
+///
+/// assert_eq!(router.active_route(), Some("/onboarding"));
+/// ```
+pub enum RouteOrRedirect<R> {
+    /// Show the route
+    Route(R),
+    /// Redirect to another path instead of showing a route.
+    /// The router opens the path in place of the requested one, without adding a history entry.
+    /// Useful to e.g. normalize legacy paths or for conditional entry points.
+    ///
+    /// Async routes (see [`crate::RouterBuilder::async_route`]) show nothing once their handler
+    /// redirects, and replace themselves with the path in the next frame.
+    Redirect(String),
+}
+
+impl<R> RouteOrRedirect<R> {
+    /// Maps the route, keeping a redirect
+    pub fn map<T>(self, f: impl FnOnce(R) -> T) -> RouteOrRedirect<T> {
+        match self {
+            Self::Route(route) => RouteOrRedirect::Route(f(route)),
+            Self::Redirect(path) => RouteOrRedirect::Redirect(path),
+        }
+    }
+}
+
+/// Splits the result of a handler into the route (or error) to show, or the path it redirects to
+pub(crate) fn split_redirect<R>(
+    result: HandlerResult<RouteOrRedirect<R>>,
+) -> Result<HandlerResult<R>, String> {
+    match result {
+        Ok(RouteOrRedirect::Route(route)) => Ok(Ok(route)),
+        Ok(RouteOrRedirect::Redirect(path)) => Err(path),
+        Err(err) => Ok(Err(err)),
+    }
+}
+
+/// The result of a handler, see [`MakeHandler`]
+pub type HandlerOutput<State> = HandlerResult<RouteOrRedirect<Box<dyn Route<State>>>>;
+
 /// Trait for a route handler.
 // The args argument is just so we can implement multiple specializations, like explained here:
 // https://geo-ant.github.io/blog/2021/rust-traits-and-variadic-functions/
 pub trait MakeHandler<State, Args> {
-    fn handle(&mut self, state: Request<State>) -> HandlerResult<Box<dyn Route<State>>>;
+    fn handle(&mut self, state: Request<State>) -> HandlerOutput<State>;
 }
 
-pub(crate) type Handler<State> =
-    Box<dyn FnMut(Request<State>) -> HandlerResult<Box<dyn Route<State>>>>;
+pub(crate) type Handler<State> = Box<dyn FnMut(Request<State>) -> HandlerOutput<State>>;
 
 impl<F, State, R> MakeHandler<State, (Request<'static, State>, ())> for F
 where
     F: Fn(Request<State>) -> R,
     R: Route<State> + 'static,
 {
-    fn handle(&mut self, request: Request<State>) -> HandlerResult<Box<dyn Route<State>>> {
-        Ok(Box::new(self(request)))
+    fn handle(&mut self, request: Request<State>) -> HandlerOutput<State> {
+        Ok(RouteOrRedirect::Route(Box::new(self(request))))
     }
 }
 
@@ -42,8 +105,8 @@ where
     F: Fn() -> R,
     R: Route<State> + 'static,
 {
-    fn handle(&mut self, _request: Request<State>) -> HandlerResult<Box<dyn Route<State>>> {
-        Ok(Box::new(self()))
+    fn handle(&mut self, _request: Request<State>) -> HandlerOutput<State> {
+        Ok(RouteOrRedirect::Route(Box::new(self())))
     }
 }
 
@@ -52,8 +115,8 @@ where
     F: Fn(Request<State>) -> HandlerResult<R>,
     R: Route<State> + 'static,
 {
-    fn handle(&mut self, request: Request<State>) -> HandlerResult<Box<dyn Route<State>>> {
-        Ok(Box::new(self(request)?))
+    fn handle(&mut self, request: Request<State>) -> HandlerOutput<State> {
+        Ok(RouteOrRedirect::Route(Box::new(self(request)?)))
     }
 }
 
@@ -62,22 +125,47 @@ where
     F: Fn() -> HandlerResult<R>,
     R: Route<State> + 'static,
 {
-    fn handle(&mut self, _request: Request<State>) -> HandlerResult<Box<dyn Route<State>>> {
-        Ok(Box::new(self()?))
+    fn handle(&mut self, _request: Request<State>) -> HandlerOutput<State> {
+        Ok(RouteOrRedirect::Route(Box::new(self()?)))
+    }
+}
+
+impl<F, State, R> MakeHandler<State, (Request<'static, State>, RouteOrRedirect<()>)> for F
+where
+    F: Fn(Request<State>) -> RouteOrRedirect<R>,
+    R: Route<State> + 'static,
+{
+    fn handle(&mut self, request: Request<State>) -> HandlerOutput<State> {
+        Ok(self(request).map(|route| Box::new(route) as Box<dyn Route<State>>))
+    }
+}
+
+impl<F, State, R> MakeHandler<State, (Request<'static, State>, HandlerResult<RouteOrRedirect<()>>)>
+    for F
+where
+    F: Fn(Request<State>) -> HandlerResult<RouteOrRedirect<R>>,
+    R: Route<State> + 'static,
+{
+    fn handle(&mut self, request: Request<State>) -> HandlerOutput<State> {
+        Ok(self(request)?.map(|route| Box::new(route) as Box<dyn Route<State>>))
     }
 }
 
 #[cfg(feature = "async")]
 mod async_impl {
-    use crate::handler::HandlerResult;
+    use crate::handler::{HandlerResult, RouteOrRedirect};
     use crate::{OwnedRequest, Request, Route};
     use std::future::Future;
 
+    /// The result of an async handler, see [`AsyncMakeHandler`]
+    pub type AsyncHandlerOutput<State> =
+        HandlerResult<RouteOrRedirect<Box<dyn Route<State> + Send + Sync>>>;
+
     pub trait AsyncMakeHandler<State, Args> {
         fn handle(
             &self,
             state: OwnedRequest<State>,
-        ) -> impl Future<Output = HandlerResult<Box<dyn Route<State> + Send + Sync>>> + Send + Sync;
+        ) -> impl Future<Output = AsyncHandlerOutput<State>> + Send + Sync;
     }
 
     impl<F, Fut, State, R> AsyncMakeHandler<State, (Request<'static, State>, ())> for F
@@ -87,11 +175,8 @@ mod async_impl {
         R: Route<State> + 'static + Send + Sync,
         State: Send + Sync,
     {
-        async fn handle(
-            &self,
-            request: OwnedRequest<State>,
-        ) -> HandlerResult<Box<dyn Route<State> + Send + Sync>> {
-            Ok(Box::new(self(request).await))
+        async fn handle(&self, request: OwnedRequest<State>) -> AsyncHandlerOutput<State> {
+            Ok(RouteOrRedirect::Route(Box::new(self(request).await)))
         }
     }
 
@@ -102,11 +187,8 @@ mod async_impl {
         R: Route<State> + 'static + Send + Sync,
         State: Send + Sync,
     {
-        async fn handle(
-            &self,
-            _request: OwnedRequest<State>,
-        ) -> HandlerResult<Box<dyn Route<State> + Send + Sync>> {
-            Ok(Box::new(self().await))
+        async fn handle(&self, _request: OwnedRequest<State>) -> AsyncHandlerOutput<State> {
+            Ok(RouteOrRedirect::Route(Box::new(self().await)))
         }
     }
 
@@ -117,11 +199,8 @@ mod async_impl {
         R: Route<State> + 'static + Send + Sync,
         State: Send + Sync,
     {
-        async fn handle(
-            &self,
-            request: OwnedRequest<State>,
-        ) -> HandlerResult<Box<dyn Route<State> + Send + Sync>> {
-            Ok(Box::new(self(request).await?))
+        async fn handle(&self, request: OwnedRequest<State>) -> AsyncHandlerOutput<State> {
+            Ok(RouteOrRedirect::Route(Box::new(self(request).await?)))
         }
     }
 
@@ -132,11 +211,23 @@ mod async_impl {
         R: Route<State> + 'static + Send + Sync,
         State: Send + Sync,
     {
-        async fn handle(
-            &self,
-            _request: OwnedRequest<State>,
-        ) -> HandlerResult<Box<dyn Route<State> + Send + Sync>> {
-            Ok(Box::new(self().await?))
+        async fn handle(&self, _request: OwnedRequest<State>) -> AsyncHandlerOutput<State> {
+            Ok(RouteOrRedirect::Route(Box::new(self().await?)))
+        }
+    }
+
+    impl<F, Fut, State, R>
+        AsyncMakeHandler<State, (Request<'static, State>, HandlerResult<RouteOrRedirect<()>>)> for F
+    where
+        F: Fn(OwnedRequest<State>) -> Fut + Send + Sync,
+        Fut: Future<Output = HandlerResult<RouteOrRedirect<R>>> + Send + Sync,
+        R: Route<State> + 'static + Send + Sync,
+        State: Send + Sync,
+    {
+        async fn handle(&self, request: OwnedRequest<State>) -> AsyncHandlerOutput<State> {
+            Ok(self(request)
+                .await?
+                .map(|route| Box::new(route) as Box<dyn Route<State> + Send + Sync>))
         }
     }
 }
@@ -129,6 +129,12 @@ impl History for BrowserHistory {
         Ok(())
     }
 
+    fn go_back(&mut self, count: usize) -> HistoryResult {
+        self.history
+            .go_with_delta(-i32::try_from(count).unwrap_or(i32::MAX))?;
+        Ok(())
+    }
+
     fn forward(&mut self) -> HistoryResult {
         self.history.forward()?;
         Ok(())
@@ -19,6 +19,13 @@ pub trait History {
     fn replace(&mut self, url: &str, state: u32) -> HistoryResult;
     /// Go back in the history
     fn back(&mut self) -> HistoryResult;
+    /// Go back multiple entries in the history at once
+    fn go_back(&mut self, count: usize) -> HistoryResult {
+        for _ in 0..count {
+            self.back()?;
+        }
+        Ok(())
+    }
     /// Go forward in the history
     fn forward(&mut self) -> HistoryResult;
 }
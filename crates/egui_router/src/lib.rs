@@ -2,33 +2,96 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod accessibility;
 #[cfg(feature = "async")]
 mod async_route;
+mod deep_link;
+mod focus;
+mod guard;
+mod handle;
 mod handler;
 /// History types
 pub mod history;
+mod link;
+#[cfg(feature = "async")]
+mod loader_route;
+mod metadata;
+mod mount;
+mod navigation_event;
+mod navigation_input;
+mod navigation_progress;
+#[cfg(feature = "serde")]
+mod params;
 mod route_kind;
+mod route_table;
 mod router;
 mod router_builder;
+mod routes_macro;
+mod scroll_restore;
+mod shared_element;
+#[cfg(feature = "suspense")]
+mod suspense_route;
+mod tab_router;
+mod task_scope;
+/// Helpers to test router apps with `egui_kittest`, see [`test_util::RouterHarness`]
+#[cfg(feature = "test_util")]
+pub mod test_util;
+mod title;
 /// Transition types
 pub mod transition;
+mod viewport;
 
 use crate::history::HistoryError;
-use crate::transition::{ActiveTransition, SlideFadeTransition, SlideTransition, Transition};
+use crate::metadata::RouteMetadata;
+use crate::mount::Mount;
+use crate::task_scope::TaskScope;
+use crate::transition::{
+    ActiveTransition, LayerTiming, ScaleFadeTransition, ScaleTransition, SlideFadeTransition,
+    SlideTransition, Transition,
+};
 use egui::emath::ease_in_ease_out;
 use egui::{Ui, Vec2};
+use std::any::Any;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::rc::Rc;
+use std::str::FromStr;
 use std::sync::atomic::AtomicUsize;
 
-pub use handler::{HandlerError, HandlerResult};
+pub use focus::route_autofocus;
+pub use guard::GuardAction;
+pub use handle::RouterHandle;
+pub use handler::{HandlerError, HandlerResult, RouteOrRedirect};
+pub use link::{router_link, RouterLink};
+pub use navigation_event::{NavigationEvent, NavigationKind};
+pub use navigation_input::NavigationInput;
+pub use navigation_progress::NavigationProgress;
+#[cfg(feature = "serde")]
+pub use params::ParamError;
+pub use route_kind::RoutePolicy;
 pub use router::EguiRouter;
 pub use router_builder::RouterBuilder;
+#[doc(hidden)]
+pub use routes_macro::__private;
+pub use scroll_restore::route_scroll_area;
+pub use shared_element::shared_element;
+#[cfg(feature = "suspense")]
+pub use suspense_route::SuspenseRoute;
+pub use tab_router::TabRouter;
+pub use title::{Breadcrumb, RouteTitle};
 
 /// A route instance created by a [`handler::Handler`]
 pub trait Route<State = ()> {
     /// Render the route ui
     fn ui(&mut self, ui: &mut egui::Ui, state: &mut State);
+
+    /// True while the route is loading, e.g. waiting for data from a background task. The
+    /// navigation to the route counts as ongoing until it's done, see
+    /// [`EguiRouter::navigation_progress`]. The default is false.
+    fn loading(&self) -> bool {
+        false
+    }
 }
 
 impl<F: FnMut(&mut Ui, &mut State), State> Route<State> for F {
@@ -39,11 +102,65 @@ impl<F: FnMut(&mut Ui, &mut State), State> Route<State> for F {
 
 static ID: AtomicUsize = AtomicUsize::new(0);
 
+/// An entry in the router's history, see [`EguiRouter::history`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryEntry {
+    /// The path of the route, without the query
+    pub path: String,
+    /// The path params the route was matched with
+    pub params: BTreeMap<String, String>,
+    /// The query params of the route
+    pub query: BTreeMap<String, String>,
+}
+
+impl HistoryEntry {
+    /// The path including the query, e.g. `/search?q=hello`
+    pub fn url(&self) -> String {
+        if self.query.is_empty() {
+            self.path.clone()
+        } else {
+            let query = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.query)
+                .finish();
+            format!("{}?{query}", self.path)
+        }
+    }
+}
+
+/// The navigation stack of a router, see [`EguiRouter::save_state`] and [`EguiRouter::restore`].
+///
+/// With the `serde` feature, this can be serialized, e.g. via `eframe::set_value` to reopen the
+/// app on the screen the user left.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouterState {
+    /// The history entries, starting with the first route
+    pub history: Vec<HistoryEntry>,
+    /// The urls of the forward history (see [`EguiRouter::forward`]), the route
+    /// [`EguiRouter::forward`] goes to last
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub forward: Vec<String>,
+}
+
 struct RouteState<State> {
-    path: String,
+    entry: HistoryEntry,
     route: HandlerResult<Box<dyn Route<State>>>,
     id: usize,
     state: u32,
+    /// Set if this route shows a mounted child router
+    mount: Option<Rc<Mount<State>>>,
+    /// The transition set via [`RouterBuilder::route_with_transition`], if any
+    transition: Option<TransitionConfig>,
+    /// Set for routes added via [`RouterBuilder::overlay_route`]
+    overlay: bool,
+    /// Set for routes opened via [`EguiRouter::navigate_for_result`]
+    on_result: Option<ResultCallback<State>>,
+    policy: RoutePolicy,
+    /// Set if the route has to be rebuilt before it's shown again, see [`RoutePolicy::Rebuild`]
+    stale: bool,
+    /// Cancels the route's tasks when the route is dropped
+    _tasks: TaskScope,
 }
 
 /// Router Result type
@@ -58,6 +175,30 @@ pub enum RouterError {
     /// Not found error
     #[error("Route not found")]
     NotFound,
+    /// There is no previous route to go back to
+    #[error("No previous route to go back to")]
+    NoPreviousRoute,
+    /// There is no route to go forward to
+    #[error("No route to go forward to")]
+    NoForwardRoute,
+    /// The navigation was cancelled by a guard
+    #[error("Navigation cancelled by a guard")]
+    Cancelled,
+    /// The navigation was redirected too often, e.g. because two routes redirect to each other
+    #[error("Too many redirects")]
+    TooManyRedirects,
+    /// The route passed to [`EguiRouter::add_route`] is invalid or conflicts with another route
+    #[error("Invalid route: {0}")]
+    InvalidRoute(matchit::InsertError),
+    /// The deep link passed to [`EguiRouter::navigate_initial`] couldn't be opened, so the
+    /// router opened `/` instead
+    #[error("Can't open the deep link {path:?}: {reason}")]
+    InvalidDeepLink {
+        /// The deep link
+        path: String,
+        /// Why it couldn't be opened
+        reason: Box<RouterError>,
+    },
 }
 
 impl From<HistoryError> for RouterError {
@@ -73,6 +214,10 @@ pub struct TransitionConfig {
     easing: fn(f32) -> f32,
     in_: Transition,
     out: Transition,
+    /// The timing of the in transition, if it differs from `duration` and `easing`
+    in_timing: LayerTiming,
+    /// The timing of the out transition, if it differs from `duration` and `easing`
+    out_timing: LayerTiming,
 }
 
 impl Default for TransitionConfig {
@@ -82,12 +227,14 @@ impl Default for TransitionConfig {
             easing: ease_in_ease_out,
             in_: transition::SlideTransition::new(Vec2::X).into(),
             out: transition::SlideTransition::new(Vec2::X * -0.1).into(),
+            in_timing: LayerTiming::default(),
+            out_timing: LayerTiming::default(),
         }
     }
 }
 
 impl TransitionConfig {
-    /// Create a new transition
+    /// Create a new transition. Use [`transition::CustomTransition`] for your own transitions.
     pub fn new(in_: impl Into<Transition>, out: impl Into<Transition>) -> Self {
         Self {
             in_: in_.into(),
@@ -112,6 +259,40 @@ impl TransitionConfig {
         )
     }
 
+    /// A zoom transition: the new page zooms in while the old page zooms out towards the
+    /// viewer, both fading
+    pub fn zoom() -> Self {
+        Self::new(
+            ScaleFadeTransition(ScaleTransition::new(0.85), transition::FadeTransition),
+            ScaleFadeTransition(ScaleTransition::new(1.15), transition::FadeTransition),
+        )
+    }
+
+    /// A subtle transition where the new page grows to its full size while fading in, shown on
+    /// top of the old page. Works well for dialogs and overlay routes
+    pub fn scale_fade() -> Self {
+        Self::new(
+            ScaleFadeTransition(ScaleTransition::new(0.9), transition::FadeTransition),
+            transition::NoTransition,
+        )
+    }
+
+    /// The material design "shared axis" transition (on the x axis): the pages slide a bit
+    /// in the navigation direction while crossfading, for routes with a spatial relationship,
+    /// e.g. the steps of a wizard
+    pub fn shared_axis() -> Self {
+        Self::new(
+            SlideFadeTransition(
+                SlideTransition::new(Vec2::X * 0.1),
+                transition::FadeTransition,
+            ),
+            SlideFadeTransition(
+                SlideTransition::new(Vec2::X * -0.1),
+                transition::FadeTransition,
+            ),
+        )
+    }
+
     /// A basic fade transition
     pub fn fade() -> Self {
         Self::new(transition::FadeTransition, transition::FadeTransition)
@@ -133,6 +314,63 @@ impl TransitionConfig {
         self.duration = Some(duration);
         self
     }
+
+    /// Customise the duration of the in transition (applied to the page that is being navigated
+    /// to), e.g. to let it settle slower than the old page leaves. Defaults to the duration of
+    /// the transition.
+    ///
+    /// The transition lasts until both the in and out transition are done. Backward transitions
+    /// play the whole transition in reverse, so there, the in transition of the leaving page
+    /// starts first.
+    ///
+    /// ```
+    /// # use egui_router::TransitionConfig;
+    /// // The old page fades out quickly, the new page fades in slowly once it's gone
+    /// let config = TransitionConfig::fade()
+    ///     .with_out_duration(0.1)
+    ///     .with_in_delay(0.1)
+    ///     .with_in_duration(0.4);
+    /// ```
+    pub fn with_in_duration(mut self, duration: f32) -> Self {
+        self.in_timing.duration = Some(duration);
+        self
+    }
+
+    /// Customise the duration of the out transition (applied to the page that is being
+    /// navigated from). Defaults to the duration of the transition.
+    /// See [`Self::with_in_duration`].
+    pub fn with_out_duration(mut self, duration: f32) -> Self {
+        self.out_timing.duration = Some(duration);
+        self
+    }
+
+    /// Customise the easing function of the in transition. Defaults to the easing of the
+    /// transition. See [`Self::with_in_duration`].
+    pub fn with_in_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.in_timing.easing = Some(easing);
+        self
+    }
+
+    /// Customise the easing function of the out transition. Defaults to the easing of the
+    /// transition. See [`Self::with_in_duration`].
+    pub fn with_out_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.out_timing.easing = Some(easing);
+        self
+    }
+
+    /// Delay the start of the in transition by `delay` seconds, e.g. to stagger it after the
+    /// out transition. Default is 0. See [`Self::with_in_duration`].
+    pub fn with_in_delay(mut self, delay: f32) -> Self {
+        self.in_timing.delay = delay.max(0.0);
+        self
+    }
+
+    /// Delay the start of the out transition by `delay` seconds. Default is 0.
+    /// See [`Self::with_in_duration`].
+    pub fn with_out_delay(mut self, delay: f32) -> Self {
+        self.out_timing.delay = delay.max(0.0);
+        self
+    }
 }
 
 struct CurrentTransition<State> {
@@ -140,14 +378,123 @@ struct CurrentTransition<State> {
     leaving_route: Option<RouteState<State>>,
 }
 
+/// A route created via [`EguiRouter::preload`], used by the next navigation to its url
+struct PreloadedRoute<State> {
+    url: String,
+    route: HandlerResult<Box<dyn Route<State>>>,
+    tasks: TaskScope,
+}
+
+/// A type erased payload that can be passed to a route via [`EguiRouter::navigate_with`]
+pub type Payload = Box<dyn Any + Send + Sync>;
+
+/// Receives the result passed to [`EguiRouter::back_with_result`]
+type ResultCallback<State> = Box<dyn FnOnce(&mut State, Payload)>;
+
+fn take_payload<T: Any>(payload: &mut Option<Payload>) -> Option<T> {
+    match payload.take()?.downcast::<T>() {
+        Ok(value) => Some(*value),
+        Err(other) => {
+            *payload = Some(other);
+            None
+        }
+    }
+}
+
 /// Request passed to a [`handler::MakeHandler`]
 pub struct Request<'a, State = ()> {
     /// The parsed path params
     pub params: matchit::Params<'a, 'a>,
-    /// The parsed query params
+    /// The parsed query params, e.g. navigating to `/search?q=hello&page=2` will result in
+    /// `{"page": "2", "q": "hello"}`
     pub query: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
     /// The custom state
     pub state: &'a mut State,
+    pub(crate) payload: Option<Payload>,
+    pub(crate) metadata: RouteMetadata,
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) tasks: &'a mut TaskScope,
+    pub(crate) handle: RouterHandle,
+}
+
+impl<State> Request<'_, State> {
+    /// Spawns a task that is cancelled when the route is removed from the history
+    /// (once its leaving transition is done), e.g. to poll for updates while the route is open.
+    /// Make sure your future is safe to cancel (it may stop at any await point).
+    ///
+    /// In guards and for handlers that redirect, the tasks are cancelled right away.
+    #[cfg(feature = "async")]
+    pub fn spawn_scoped(
+        &mut self,
+        future: impl std::future::Future<Output = ()> + hello_egui_utils::MaybeSend + 'static,
+    ) {
+        self.tasks.spawn(future);
+    }
+
+    /// Returns the payload passed via [`EguiRouter::navigate_with`], if it is of type `T`.
+    ///
+    /// The payload is not stored in the history, so it will be `None` when the route is
+    /// opened via a link or the browser history.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        self.payload.as_ref()?.downcast_ref()
+    }
+
+    /// Takes the payload out of the request, if it is of type `T`.
+    pub fn take_payload<T: Any>(&mut self) -> Option<T> {
+        take_payload(&mut self.payload)
+    }
+
+    /// Returns the metadata of type `T` of the route, see [`RouterBuilder::metadata`].
+    pub fn metadata<T: Any>(&self) -> Option<&T> {
+        self.metadata.get()
+    }
+
+    /// A [`RouterHandle`] of the router that opens the route, to navigate from the route's ui
+    /// or from background tasks.
+    pub fn router(&self) -> RouterHandle {
+        self.handle.clone()
+    }
+
+    /// Parse the path param `name`, e.g. `request.param::<u64>("id")` for a route `/post/{id}`.
+    /// Returns [`HandlerError::NotFound`] if the route has no such param and
+    /// [`HandlerError::Message`] if it can't be parsed, so it can be used with `?` in handlers.
+    pub fn param<T: FromStr>(&self, name: &str) -> HandlerResult<T>
+    where
+        T::Err: Display,
+    {
+        let value = self.params.get(name).ok_or(HandlerError::NotFound)?;
+        value
+            .parse()
+            .map_err(|err| HandlerError::Message(format!("Invalid param `{name}`: {err}")))
+    }
+
+    /// Deserialize the path params into `T`, e.g. for a route `/post/{id}`:
+    /// ```
+    /// # use egui_router::{ParamError, Request};
+    /// #[derive(serde::Deserialize)]
+    /// struct PostParams {
+    ///     id: u64,
+    /// }
+    ///
+    /// fn post_id(request: &Request) -> Result<u64, ParamError> {
+    ///     Ok(request.params_as::<PostParams>()?.id)
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn params_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, ParamError> {
+        params::deserialize_params(self.params.iter())
+    }
+
+    /// Deserialize the query params into `T`.
+    /// Use `Option` or `#[serde(default)]` for params that may be missing.
+    #[cfg(feature = "serde")]
+    pub fn query_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, ParamError> {
+        params::deserialize_params(
+            self.query
+                .iter()
+                .map(|(key, value)| (key.as_ref(), value.as_ref())),
+        )
+    }
 }
 
 #[cfg(feature = "async")]
@@ -159,4 +506,53 @@ pub struct OwnedRequest<State = ()> {
     pub query: BTreeMap<String, String>,
     /// The custom state
     pub state: State,
+    pub(crate) payload: Option<Payload>,
+    pub(crate) metadata: RouteMetadata,
+    pub(crate) handle: RouterHandle,
+}
+
+#[cfg(feature = "async")]
+impl<State> OwnedRequest<State> {
+    /// Returns the payload passed via [`EguiRouter::navigate_with`], if it is of type `T`.
+    ///
+    /// Check [`Request::payload`] for more info.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        self.payload.as_ref()?.downcast_ref()
+    }
+
+    /// Takes the payload out of the request, if it is of type `T`.
+    pub fn take_payload<T: Any>(&mut self) -> Option<T> {
+        take_payload(&mut self.payload)
+    }
+
+    /// Returns the metadata of type `T` of the route, see [`RouterBuilder::metadata`].
+    pub fn metadata<T: Any>(&self) -> Option<&T> {
+        self.metadata.get()
+    }
+
+    /// A [`RouterHandle`] of the router that opens the route. Check [`Request::router`] for
+    /// more info.
+    pub fn router(&self) -> RouterHandle {
+        self.handle.clone()
+    }
+
+    /// Deserialize the path params into `T`. Check [`Request::params_as`] for more info.
+    #[cfg(feature = "serde")]
+    pub fn params_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, ParamError> {
+        params::deserialize_params(
+            self.params
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        )
+    }
+
+    /// Deserialize the query params into `T`. Check [`Request::query_as`] for more info.
+    #[cfg(feature = "serde")]
+    pub fn query_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, ParamError> {
+        params::deserialize_params(
+            self.query
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        )
+    }
 }
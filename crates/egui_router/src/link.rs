@@ -0,0 +1,86 @@
+use crate::RouterHandle;
+use egui::{Id, Response, Ui, Widget, WidgetText};
+
+/// Set by the [`crate::EguiRouter`] while it renders its routes, so [`RouterLink`]s know
+/// the active route and where to send navigation requests.
+#[derive(Clone)]
+pub(crate) struct RouterLinkContext {
+    pub active_route: Option<String>,
+    pub handle: RouterHandle,
+}
+
+impl RouterLinkContext {
+    pub(crate) fn id() -> Id {
+        Id::new("egui_router_link_context")
+    }
+
+    pub(crate) fn get(ui: &Ui) -> Option<Self> {
+        ui.data(|data| data.get_temp(Self::id()))
+    }
+}
+
+/// A link that navigates to `path` when clicked.
+/// It is highlighted when its path matches the active route.
+///
+/// This only works inside a route rendered by a [`crate::EguiRouter`], outside of a router it
+/// will do nothing. The navigation happens in the router's next call to [`crate::EguiRouter::ui`],
+/// using the router's default forward transition.
+pub struct RouterLink {
+    text: WidgetText,
+    path: String,
+    preload: bool,
+}
+
+impl RouterLink {
+    /// Create a new link.
+    pub fn new(text: impl Into<WidgetText>, path: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            path: path.into(),
+            preload: false,
+        }
+    }
+
+    /// Preload the route while the link is hovered, so it opens instantly when clicked.
+    /// See [`crate::EguiRouter::preload`]. The default is false.
+    pub fn preload(mut self, preload: bool) -> Self {
+        self.preload = preload;
+        self
+    }
+}
+
+impl Widget for RouterLink {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let context = RouterLinkContext::get(ui);
+
+        let path_without_query = self.path.split('?').next().unwrap_or_default();
+        let active = context
+            .as_ref()
+            .and_then(|context| context.active_route.as_deref())
+            == Some(path_without_query);
+
+        let response = ui.selectable_label(active, self.text);
+
+        if let Some(context) = context {
+            if response.clicked() {
+                context.handle.navigate(self.path);
+            } else if self.preload && !active {
+                // Only sent when the hover starts, since sending requests a repaint
+                let hovered = response.hovered();
+                let was_hovered = ui.data_mut(|data| {
+                    std::mem::replace(data.get_temp_mut_or_default(response.id), hovered)
+                });
+                if hovered && !was_hovered {
+                    context.handle.preload(self.path);
+                }
+            }
+        }
+
+        response
+    }
+}
+
+/// Show a [`RouterLink`] that navigates to `path` when clicked.
+pub fn router_link(ui: &mut Ui, text: impl Into<WidgetText>, path: impl Into<String>) -> Response {
+    ui.add(RouterLink::new(text, path))
+}
@@ -0,0 +1,37 @@
+use crate::handler::HandlerError;
+use crate::router_builder::{ErrorUi, LoadingUi};
+use crate::Route;
+use egui::Ui;
+use egui_suspense::EguiSuspense;
+use hello_egui_utils::{MaybeSend, MaybeSync};
+use std::rc::Rc;
+
+pub(crate) type ViewFn<State, T> = Rc<dyn Fn(&mut Ui, &mut T, &mut State)>;
+
+/// The route of [`crate::RouterBuilder::loader_route`]. The suspense only loads the data, the
+/// loading and error ui of the router are shown by the route, so they can access the state.
+pub(crate) struct LoaderRoute<State, T> {
+    pub suspense: EguiSuspense<T, HandlerError>,
+    pub view: ViewFn<State, T>,
+    pub loading_ui: LoadingUi<State>,
+    pub error_ui: ErrorUi<State>,
+}
+
+impl<State: 'static, T: MaybeSend + MaybeSync + 'static> Route<State> for LoaderRoute<State, T> {
+    fn ui(&mut self, ui: &mut Ui, state: &mut State) {
+        let view = &self.view;
+        self.suspense.ui(ui, |ui, data, _suspense_state| {
+            view(ui, data, state);
+        });
+
+        if self.suspense.loading() {
+            (self.loading_ui)(ui, state);
+        } else if let Some(err) = self.suspense.error() {
+            (self.error_ui)(ui, state, err);
+        }
+    }
+
+    fn loading(&self) -> bool {
+        self.suspense.loading()
+    }
+}
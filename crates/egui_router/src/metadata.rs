@@ -0,0 +1,59 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::route_table::RouteTable;
+
+/// The typed metadata of a route, see [`crate::RouterBuilder::metadata`].
+/// Cloning is cheap, so every [`crate::Request`] gets a copy.
+#[derive(Clone, Default)]
+pub(crate) struct RouteMetadata(Arc<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>);
+
+impl RouteMetadata {
+    /// Sets the value of type `T`, replacing the previous one
+    pub(crate) fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+        Arc::make_mut(&mut self.0).insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    pub(crate) fn get<T: Any>(&self) -> Option<&T> {
+        self.0.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+}
+
+/// The metadata of all routes, in the order they were first added
+pub(crate) struct MetadataRegistry {
+    routes: RouteTable<RouteMetadata>,
+}
+
+impl MetadataRegistry {
+    pub(crate) fn new(routes: Vec<(String, RouteMetadata)>) -> Self {
+        let mut table = RouteTable::new();
+        for (route, metadata) in routes {
+            table.insert(&route, metadata).unwrap();
+        }
+        Self { routes: table }
+    }
+
+    /// The metadata of the route matching `path` (without a query)
+    pub(crate) fn at(&self, path: &str) -> RouteMetadata {
+        self.routes
+            .at(path)
+            .map(|match_| match_.value.clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get<T: Any>(&self, path: &str) -> Option<&T> {
+        self.routes.at(path).ok()?.value.get()
+    }
+
+    pub(crate) fn iter<T: Any>(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.routes
+            .iter()
+            .filter_map(|(route, metadata)| Some((route, metadata.get()?)))
+    }
+
+    /// Removes the metadata of `route` (as passed to [`crate::RouterBuilder::metadata`])
+    pub(crate) fn remove(&mut self, route: &str) {
+        self.routes.remove(route);
+    }
+}
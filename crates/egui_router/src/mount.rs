@@ -0,0 +1,57 @@
+use crate::history::MemoryHistory;
+use crate::{EguiRouter, HistoryEntry, Route};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// A child router mounted under a path prefix, see [`crate::RouterBuilder::mount`]
+pub(crate) struct Mount<State> {
+    pub(crate) prefix: String,
+    pub(crate) router: RefCell<EguiRouter<State, MemoryHistory>>,
+}
+
+impl<State: 'static> Mount<State> {
+    /// The path in the child router, e.g. `/settings/general?tab=1` -> `/general?tab=1`
+    pub(crate) fn child_path(&self, full_path: &str) -> String {
+        let rest = full_path.get(self.prefix.len()..).unwrap_or_default();
+        if rest.starts_with('/') {
+            rest.to_owned()
+        } else {
+            format!("/{rest}")
+        }
+    }
+
+    /// The history entry of the child router's active route, as seen by the parent router
+    pub(crate) fn entry(&self) -> HistoryEntry {
+        let router = self.router.borrow();
+        match router.current() {
+            Some(entry) => self.parent_entry(entry),
+            None => HistoryEntry {
+                path: self.prefix.clone(),
+                params: BTreeMap::new(),
+                query: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// A history entry of the child router, as seen by the parent router
+    pub(crate) fn parent_entry(&self, entry: &HistoryEntry) -> HistoryEntry {
+        HistoryEntry {
+            path: if entry.path == "/" {
+                self.prefix.clone()
+            } else {
+                format!("{}{}", self.prefix.trim_end_matches('/'), entry.path)
+            },
+            params: entry.params.clone(),
+            query: entry.query.clone(),
+        }
+    }
+
+    /// The route rendering the child router in the parent router
+    pub(crate) fn route(self: &Rc<Self>) -> impl Route<State> {
+        let mount = self.clone();
+        move |ui: &mut egui::Ui, state: &mut State| {
+            mount.router.borrow_mut().ui(ui, state);
+        }
+    }
+}
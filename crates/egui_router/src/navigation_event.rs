@@ -0,0 +1,64 @@
+use crate::HistoryEntry;
+
+pub(crate) type NavigateCallback = Box<dyn FnMut(&NavigationEvent)>;
+
+/// How the navigation stack changed, see [`NavigationEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationKind {
+    /// A route was pushed onto the stack, e.g. via [`crate::EguiRouter::navigate`] or
+    /// [`crate::EguiRouter::forward`]
+    Push,
+    /// The active route was replaced, e.g. via [`crate::EguiRouter::replace`]
+    Replace,
+    /// One or more routes were removed from the stack, e.g. via [`crate::EguiRouter::back`]
+    Pop,
+}
+
+/// Passed to the callback set via [`crate::RouterBuilder::on_navigate`] when the active route
+/// changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationEvent {
+    /// How the stack changed
+    pub kind: NavigationKind,
+    /// The route that was active before, if there was one
+    pub from: Option<HistoryEntry>,
+    /// The route that is active now
+    pub to: HistoryEntry,
+}
+
+/// The routes of a router (including the routes of the active mounted router) at some point,
+/// used to find out how the stack changed
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NavigationSnapshot {
+    pub(crate) ids: Vec<usize>,
+    pub(crate) entry: Option<HistoryEntry>,
+}
+
+impl NavigationSnapshot {
+    /// The event that describes how the stack changed from `self` to `current`, if the active
+    /// route changed
+    pub(crate) fn event(&self, current: &Self) -> Option<NavigationEvent> {
+        let to = current.entry.clone()?;
+        let active = *current.ids.last()?;
+        let previous = self.ids.last().copied();
+        if previous == Some(active) {
+            return None;
+        }
+
+        let kind = if previous.is_some_and(|previous| current.ids.contains(&previous)) {
+            NavigationKind::Push
+        } else if self.ids.contains(&active) {
+            NavigationKind::Pop
+        } else if previous.is_some() {
+            NavigationKind::Replace
+        } else {
+            NavigationKind::Push
+        };
+
+        Some(NavigationEvent {
+            kind,
+            from: self.entry.clone(),
+            to,
+        })
+    }
+}
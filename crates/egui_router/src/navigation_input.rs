@@ -0,0 +1,81 @@
+use egui::{Key, KeyboardShortcut, Modifiers, PointerButton, Ui};
+
+/// Keyboard shortcuts and mouse buttons that go back or forward, see
+/// [`crate::RouterBuilder::navigation_input`].
+///
+/// The shortcuts are ignored while a widget (e.g. a text edit) has keyboard focus or a popup is
+/// open, so Escape closes those first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationInput {
+    /// Shortcuts that call [`crate::EguiRouter::back`]
+    pub back_shortcuts: Vec<KeyboardShortcut>,
+    /// Shortcuts that call [`crate::EguiRouter::forward`]
+    pub forward_shortcuts: Vec<KeyboardShortcut>,
+    /// Go back and forward with the extra mouse buttons ([`PointerButton::Extra1`] and
+    /// [`PointerButton::Extra2`])
+    pub mouse_buttons: bool,
+}
+
+impl Default for NavigationInput {
+    /// Escape and Alt+Left go back, Alt+Right goes forward, and so do the extra mouse buttons.
+    ///
+    /// On the web, the browser already handles Alt+Left / Alt+Right and the mouse buttons (with
+    /// [`crate::history::BrowserHistory`]), so only Escape is enabled there.
+    fn default() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self {
+                back_shortcuts: vec![KeyboardShortcut::new(Modifiers::NONE, Key::Escape)],
+                forward_shortcuts: Vec::new(),
+                mouse_buttons: false,
+            }
+        } else {
+            Self {
+                back_shortcuts: vec![
+                    KeyboardShortcut::new(Modifiers::NONE, Key::Escape),
+                    KeyboardShortcut::new(Modifiers::ALT, Key::ArrowLeft),
+                ],
+                forward_shortcuts: vec![KeyboardShortcut::new(Modifiers::ALT, Key::ArrowRight)],
+                mouse_buttons: true,
+            }
+        }
+    }
+}
+
+impl NavigationInput {
+    /// No shortcuts and no mouse buttons, to add your own via the fields
+    pub fn none() -> Self {
+        Self {
+            back_shortcuts: Vec::new(),
+            forward_shortcuts: Vec::new(),
+            mouse_buttons: false,
+        }
+    }
+
+    /// Consumes the input of this frame, returning `Some(true)` to go back and `Some(false)` to
+    /// go forward
+    pub(crate) fn read(&self, ui: &Ui) -> Option<bool> {
+        let focused = ui.memory(|memory| memory.focused().is_some() || memory.any_popup_open());
+        ui.input_mut(|input| {
+            let back = !focused
+                && self
+                    .back_shortcuts
+                    .iter()
+                    .any(|shortcut| input.consume_shortcut(shortcut));
+            let forward = !focused
+                && self
+                    .forward_shortcuts
+                    .iter()
+                    .any(|shortcut| input.consume_shortcut(shortcut));
+            let pointer = &input.pointer;
+            if back || (self.mouse_buttons && pointer.button_pressed(PointerButton::Extra1)) {
+                Some(true)
+            } else if forward
+                || (self.mouse_buttons && pointer.button_pressed(PointerButton::Extra2))
+            {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+}
@@ -0,0 +1,52 @@
+/// The state of an ongoing navigation, see [`crate::EguiRouter::navigation_progress`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavigationProgress {
+    /// Seconds since the navigation started
+    pub elapsed: f32,
+    /// The progress of the transition from 0.0 to 1.0, `None` once it's done (or if there is
+    /// none)
+    pub transition: Option<f32>,
+    /// True while the new route is loading, e.g. the handler of an async route or the data of a
+    /// loader route, see [`crate::Route::loading`]
+    pub loading: bool,
+}
+
+impl NavigationProgress {
+    /// An estimate of the overall progress from 0.0 to 1.0, e.g. for the width of a progress
+    /// bar: follows the transition and, while the route is loading, creeps towards 1.0 without
+    /// reaching it, like the progress bar of a browser.
+    pub fn fraction(&self) -> f32 {
+        let transition = self.transition.unwrap_or(1.0);
+        if self.loading {
+            let loading = 0.9 * (1.0 - (-self.elapsed).exp());
+            transition.min(loading)
+        } else {
+            transition
+        }
+    }
+}
+
+/// Keeps track of when the current navigation started
+#[derive(Debug, Default)]
+pub(crate) struct ProgressTracker {
+    /// The time (in egui's input time) the navigation started
+    started: Option<f64>,
+    pub(crate) progress: Option<NavigationProgress>,
+}
+
+impl ProgressTracker {
+    /// Updates the progress at `time`, clearing it once the navigation is done
+    pub(crate) fn update(&mut self, time: f64, transition: Option<f32>, loading: bool) {
+        if transition.is_none() && !loading {
+            self.started = None;
+            self.progress = None;
+            return;
+        }
+        let started = *self.started.get_or_insert(time);
+        self.progress = Some(NavigationProgress {
+            elapsed: (time - started) as f32,
+            transition,
+            loading,
+        });
+    }
+}
@@ -0,0 +1,17 @@
+use serde::de::DeserializeOwned;
+
+/// Error returned by [`crate::Request::params_as`] and [`crate::Request::query_as`]
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid params: {0}")]
+pub struct ParamError(#[from] serde_urlencoded::de::Error);
+
+/// Deserialize key value pairs into `T`. Values are parsed from strings,
+/// so e.g. numbers, bools, `Option`s and unit enums are supported.
+pub(crate) fn deserialize_params<'a, T: DeserializeOwned>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<T, ParamError> {
+    let encoded = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    Ok(serde_urlencoded::from_str(&encoded)?)
+}
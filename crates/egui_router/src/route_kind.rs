@@ -1,6 +1,33 @@
 use crate::handler::Handler;
+use crate::mount::Mount;
+use crate::TransitionConfig;
+use std::rc::Rc;
+
+/// Per route settings, see [`crate::RouterBuilder::route_with_transition`] and
+/// [`crate::RouterBuilder::overlay_route`]
+#[derive(Clone, Default)]
+pub(crate) struct RouteOptions {
+    /// Overrides the caller's transition
+    pub(crate) transition: Option<TransitionConfig>,
+    /// Render on top of the previous route instead of replacing it
+    pub(crate) overlay: bool,
+}
+
+/// What happens to a route when another route is opened on top of it and the user later goes
+/// back to it, see [`crate::RouterBuilder::route_policy`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoutePolicy {
+    /// The route is kept alive while it's in the history, so going back shows the same instance
+    /// with its widget state (e.g. text inputs and scroll positions)
+    #[default]
+    KeepAlive,
+    /// The handler is run again when going back to the route, e.g. to reload data that may have
+    /// changed. Routes shown underneath an overlay route are kept alive
+    Rebuild,
+}
 
 pub(crate) enum RouteKind<State> {
-    Route(Handler<State>),
+    Route(Handler<State>, RouteOptions),
     Redirect(String),
+    Mount(Rc<Mount<State>>),
 }
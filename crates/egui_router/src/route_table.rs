@@ -0,0 +1,92 @@
+use matchit::{InsertError, Match, MatchError};
+
+/// A [`matchit::Router`] that routes can be removed from again, see [`crate::EguiRouter::unroute`].
+///
+/// The values are kept in insertion order and the matcher only maps a path to their index, so
+/// after removing a route the matcher can be rebuilt from the remaining routes.
+pub(crate) struct RouteTable<T> {
+    routes: Vec<(String, T)>,
+    /// Matches a path to its index in `routes`
+    matcher: matchit::Router<usize>,
+}
+
+impl<T> Default for RouteTable<T> {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            matcher: matchit::Router::new(),
+        }
+    }
+}
+
+impl<T> RouteTable<T> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a route, failing if it conflicts with an existing one (see [`matchit::Router::insert`])
+    pub(crate) fn insert(&mut self, route: &str, value: T) -> Result<(), InsertError> {
+        self.matcher.insert(route, self.routes.len())?;
+        self.routes.push((route.to_owned(), value));
+        Ok(())
+    }
+
+    /// Checks if `route` could be added once the routes in `replaced` are removed, without
+    /// changing the table
+    pub(crate) fn check_insert(&self, route: &str, replaced: &[String]) -> Result<(), InsertError> {
+        let mut matcher = matchit::Router::new();
+        for (existing, _) in self.routes.iter().filter(|(r, _)| !replaced.contains(r)) {
+            matcher
+                .insert(existing, ())
+                .expect("routes that were valid before stay valid");
+        }
+        matcher.insert(route, ())
+    }
+
+    /// Removes the route that was added as `route` (with the same param names) and returns its
+    /// value
+    pub(crate) fn remove(&mut self, route: &str) -> Option<T> {
+        let index = self.routes.iter().position(|(r, _)| r == route)?;
+        let (_, value) = self.routes.remove(index);
+        self.rebuild();
+        Some(value)
+    }
+
+    /// Rebuilds the matcher, since the indices of the routes after a removed one changed
+    fn rebuild(&mut self) {
+        self.matcher = matchit::Router::new();
+        for (index, (route, _)) in self.routes.iter().enumerate() {
+            self.matcher
+                .insert(route, index)
+                .expect("routes that were valid before stay valid");
+        }
+    }
+
+    /// The value of the route matching `path`, with the params of `path`
+    pub(crate) fn at<'path>(&self, path: &'path str) -> Result<Match<'_, 'path, &T>, MatchError> {
+        let Match { value, params } = self.matcher.at(path)?;
+        Ok(Match {
+            value: &self.routes[*value].1,
+            params,
+        })
+    }
+
+    /// Same as [`Self::at`], with a mutable reference to the value
+    pub(crate) fn at_mut<'path>(
+        &mut self,
+        path: &'path str,
+    ) -> Result<Match<'_, 'path, &mut T>, MatchError> {
+        let Match { value, params } = self.matcher.at(path)?;
+        Ok(Match {
+            value: &mut self.routes[*value].1,
+            params,
+        })
+    }
+
+    /// All routes with their values, in the order they were added
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.routes
+            .iter()
+            .map(|(route, value)| (route.as_str(), value))
+    }
+}
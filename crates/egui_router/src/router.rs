@@ -1,20 +1,58 @@
-use crate::history::{DefaultHistory, History};
-use crate::route_kind::RouteKind;
+use crate::accessibility;
+use crate::deep_link;
+use crate::focus::{self, RouteFocus};
+use crate::guard::Guard;
+use crate::handle::RouterCommand;
+use crate::handler::{split_redirect, Handler, MakeHandler};
+use crate::history::{DefaultHistory, History, MemoryHistory};
+use crate::link::RouterLinkContext;
+use crate::metadata::MetadataRegistry;
+use crate::mount::Mount;
+use crate::navigation_event::{NavigateCallback, NavigationSnapshot};
+use crate::navigation_progress::ProgressTracker;
+use crate::route_kind::{RouteKind, RouteOptions, RoutePolicy};
+use crate::route_table::RouteTable;
 use crate::router_builder::{ErrorUi, RouterBuilder};
+use crate::scroll_restore::ScrollPositions;
+use crate::shared_element::{SharedElementContext, SharedElements, SharedTransition};
+use crate::task_scope::TaskScope;
+use crate::title::TitleCallback;
 use crate::transition::{ActiveTransition, ActiveTransitionResult};
+use crate::viewport::ViewportRouter;
 use crate::{
-    CurrentTransition, Request, RouteState, RouterError, RouterResult, TransitionConfig, ID,
+    Breadcrumb, CurrentTransition, GuardAction, HistoryEntry, NavigationInput, NavigationProgress,
+    Payload, PreloadedRoute, Request, ResultCallback, RouteState, RouteTitle, RouterError,
+    RouterHandle, RouterResult, RouterState, TransitionConfig, ID,
 };
-use egui::Ui;
+use egui::mutex::Mutex;
+use egui::{Color32, Context, Id, Rect, Response, Sense, Ui, Vec2, ViewportBuilder, ViewportId};
+use egui_inbox::UiInbox;
 use matchit::MatchError;
+use std::any::Any;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// The max number of redirects (from guards, redirect routes or handlers) for a single navigation
+const MAX_REDIRECTS: usize = 16;
+
+/// The color laid over the route underneath an overlay route
+const OVERLAY_DIM_COLOR: Color32 = Color32::from_black_alpha(100);
+
+/// The width of the area at the left edge where a swipe back gesture can start
+const SWIPE_BACK_EDGE_WIDTH: f32 = 20.0;
+/// The horizontal pointer velocity (in points per second) that completes a swipe back gesture
+/// regardless of how far the route was dragged
+const SWIPE_BACK_VELOCITY: f32 = 400.0;
 
 /// A router instance
 pub struct EguiRouter<State, History = DefaultHistory> {
-    router: matchit::Router<RouteKind<State>>,
+    router: RouteTable<RouteKind<State>>,
     history: Vec<RouteState<State>>,
+    /// The urls of the routes we went back from, the most recent one last
+    forward_history: Vec<String>,
 
     history_kind: History,
 
@@ -24,8 +62,46 @@ pub struct EguiRouter<State, History = DefaultHistory> {
 
     current_transition: Option<CurrentTransition<State>>,
     default_duration: Option<f32>,
+    duration_scale: f32,
+
+    swipe_back: bool,
+    /// The progress of the ongoing swipe back gesture, if any
+    swipe_back_progress: Option<f32>,
+    navigation_input: Option<NavigationInput>,
+    max_history: Option<usize>,
+    dedup_navigation: bool,
 
     error_ui: ErrorUi<State>,
+    fallback: Option<Handler<State>>,
+    guards: Vec<Guard<State>>,
+
+    titles: RouteTable<RouteTitle>,
+    on_title_change: Option<TitleCallback>,
+    /// The title passed to `on_title_change` last
+    last_title: Option<String>,
+    on_navigate: Option<NavigateCallback>,
+    /// The routes when `on_navigate` was checked last
+    last_navigation: NavigationSnapshot,
+    progress: ProgressTracker,
+    metadata: MetadataRegistry,
+    default_route_policy: RoutePolicy,
+    route_policies: RouteTable<RoutePolicy>,
+    patterns: RouteTable<String>,
+    /// The rects of the shared elements of our routes
+    shared_elements: Arc<Mutex<SharedElements>>,
+    scroll_positions: Arc<Mutex<ScrollPositions>>,
+    focus_on_navigate: bool,
+    focus: Arc<Mutex<RouteFocus>>,
+    #[cfg(feature = "accesskit")]
+    announcer: accessibility::RouteAnnouncer,
+
+    /// The route created by the last call to [`Self::preload`], dropped on the next navigation
+    preloaded: Option<PreloadedRoute<State>>,
+
+    /// Receives the navigations of [`RouterHandle`]s and [`crate::RouterLink`]s
+    inbox: UiInbox<RouterCommand>,
+    /// The routers opened via [`Self::open_in_viewport`]
+    viewports: Vec<ViewportRouter<State>>,
 }
 
 impl<State: 'static, H: History + Default> EguiRouter<State, H> {
@@ -35,266 +111,1830 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
     }
 
     pub(crate) fn from_builder(builder: RouterBuilder<State, H>, state: &mut State) -> Self {
-        let mut router = Self {
+        let (mut router, default_route) = Self::without_route(builder);
+        router.navigate_start(state, default_route);
+        router.notify_navigation();
+        router
+    }
+
+    /// Build the router from `builder`, reopening the routes of a [`RouterState`] saved via
+    /// [`EguiRouter::save_state`]. The handlers are run again to rebuild the routes, without
+    /// transitions.
+    ///
+    /// Entries that can't be restored (e.g. because the route was removed or a guard cancels it)
+    /// are skipped, as are forward entries whose route was removed. Payloads are not restored and
+    /// mounted routers only restore their active route. If no entry could be restored, the router
+    /// starts at the initial route, like [`RouterBuilder::build`].
+    pub fn restore(
+        saved: &RouterState,
+        builder: RouterBuilder<State, H>,
+        state: &mut State,
+    ) -> Self {
+        let (mut router, default_route) = Self::without_route(builder);
+
+        for entry in &saved.history {
+            let url = entry.url();
+            if router.history.is_empty() {
+                if router.history_kind.replace(&url, 0).is_ok() {
+                    router
+                        .navigate_history_impl(
+                            state,
+                            &url,
+                            TransitionConfig::none(),
+                            0,
+                            None,
+                            None,
+                            0,
+                        )
+                        .ok();
+                }
+            } else {
+                router
+                    .navigate_transition_impl(state, &url, TransitionConfig::none(), None, None)
+                    .ok();
+            }
+        }
+        router.current_transition = None;
+
+        if router.history.is_empty() {
+            router.navigate_start(state, default_route);
+        } else {
+            router.forward_history = saved
+                .forward
+                .iter()
+                .filter(|url| router.matched_route(url).is_some() || router.fallback.is_some())
+                .cloned()
+                .collect();
+            router.trim_history();
+        }
+        router.notify_navigation();
+
+        router
+    }
+
+    /// Returns the router without any route and the default route of the builder
+    fn without_route(builder: RouterBuilder<State, H>) -> (Self, Option<String>) {
+        let router = Self {
             router: builder.router,
             history: Vec::new(),
+            forward_history: Vec::new(),
             history_kind: builder.history_kind.unwrap_or_default(),
             current_transition: None,
             forward_transition: builder.forward_transition,
             backward_transition: builder.backward_transition,
             replace_transition: builder.replace_transition,
             default_duration: builder.default_duration,
+            duration_scale: builder.duration_scale,
+            swipe_back: builder.swipe_back,
+            swipe_back_progress: None,
+            navigation_input: builder.navigation_input,
+            max_history: builder.max_history,
+            dedup_navigation: builder.dedup_navigation,
             error_ui: builder.error_ui,
+            fallback: builder.fallback,
+            guards: builder.guards,
+            titles: builder.titles,
+            on_title_change: builder.on_title_change,
+            last_title: None,
+            on_navigate: builder.on_navigate,
+            last_navigation: NavigationSnapshot::default(),
+            progress: ProgressTracker::default(),
+            metadata: MetadataRegistry::new(builder.metadata),
+            default_route_policy: builder.default_route_policy,
+            route_policies: builder.route_policies,
+            patterns: builder.patterns,
+            shared_elements: Arc::default(),
+            scroll_positions: Arc::default(),
+            focus_on_navigate: builder.focus_on_navigate,
+            focus: Arc::default(),
+            #[cfg(feature = "accesskit")]
+            announcer: accessibility::RouteAnnouncer::default(),
+            preloaded: None,
+            inbox: UiInbox::new(),
+            viewports: Vec::new(),
         };
+        (router, builder.default_route)
+    }
 
-        if let Some((r, state_index)) = router
+    /// Navigate to the route of the [`History`] or the default route, when the router is created
+    fn navigate_start(&mut self, state: &mut State, default_route: Option<String>) {
+        if let Some((r, state_index)) = self
             .history_kind
             .active_route()
-            .or(builder.default_route.map(|d| (d, None)))
+            .or(default_route.map(|d| (d, None)))
         {
-            router
-                .navigate_impl(
-                    state,
-                    &r,
-                    TransitionConfig::none(),
-                    state_index.unwrap_or(0),
+            match self.navigate_history_impl(
+                state,
+                &r,
+                TransitionConfig::none(),
+                state_index.unwrap_or(0),
+                None,
+                None,
+                0,
+            ) {
+                // If a guard cancels the initial route, the router starts without an active route
+                Ok(()) | Err(RouterError::Cancelled) => {}
+                Err(err) => panic!("Failed to navigate to the initial route: {err}"),
+            }
+        }
+    }
+
+    /// Open a deep link, e.g. passed as a command line argument or via a custom url scheme.
+    /// `path` may contain params and a query, a scheme prefix like `myapp://` is removed (so
+    /// `myapp://settings/account` opens `/settings/account`).
+    ///
+    /// The history is replaced by the parent routes of `path` that exist, followed by `path`
+    /// itself, so going back from `/settings/account` opens `/settings` and then `/`. Guards
+    /// run for every route.
+    ///
+    /// If `path` doesn't match a route (the fallback doesn't count) or can't be opened, e.g.
+    /// because a guard cancels it, the router opens `/` instead and returns
+    /// [`RouterError::InvalidDeepLink`] with the reason.
+    ///
+    /// ```
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut egui::Ui, _: &mut ()| {}
+    /// }
+    ///
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/settings", page)
+    ///     .route("/settings/account", page)
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// router.navigate_initial(&mut (), "myapp://settings/account?tab=2").unwrap();
+    /// let history: Vec<_> = router.history().map(|entry| entry.url()).collect();
+    /// assert_eq!(history, ["/", "/settings", "/settings/account?tab=2"]);
+    ///
+    /// assert!(router.navigate_initial(&mut (), "/unknown").is_err());
+    /// assert_eq!(router.active_route(), Some("/"));
+    /// ```
+    pub fn navigate_initial(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
+        let path = path.into();
+        let url = deep_link::deep_link_url(&path);
+        let result = self.open_deep_link(state, &url).map_err(|reason| {
+            self.reset_history();
+            if self.history_kind.replace("/", 0).is_ok() {
+                self.navigate_history_impl(state, "/", TransitionConfig::none(), 0, None, None, 0)
+                    .ok();
+            }
+            self.current_transition = None;
+            RouterError::InvalidDeepLink {
+                path,
+                reason: Box::new(reason),
+            }
+        });
+        self.notify_navigation();
+        result
+    }
+
+    /// Open the deep link the app was launched with, e.g. from a desktop shortcut or an url
+    /// scheme handler, via [`Self::navigate_initial`]. Call it once after building the router.
+    ///
+    /// The deep link is read from the command line arguments, either passed as
+    /// `--route /settings/account` (or `--route=/settings/account`) or, if your app registered
+    /// the url `scheme` (e.g. `myapp`), as an argument like `myapp://settings/account`. Other
+    /// arguments, e.g. `--log=https://example.com`, are ignored. If there is no deep link, the
+    /// environment variable `env_var` is read, if set.
+    ///
+    /// Returns `None` if no deep link was passed, so the router stays at its initial route.
+    /// If you parse the arguments yourself (e.g. with clap), pass the route to
+    /// [`Self::navigate_initial`] instead.
+    ///
+    /// ```no_run
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// # fn page(_req: Request) -> impl Route {
+    /// #     |ui: &mut egui::Ui, _: &mut ()| {}
+    /// # }
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/settings", page)
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// // E.g. `myapp --route /settings`, `myapp myapp://settings` or `MYAPP_ROUTE=/settings myapp`
+    /// let result = router.navigate_from_args(&mut (), Some("myapp"), Some("MYAPP_ROUTE"));
+    /// if let Some(Err(err)) = result {
+    ///     eprintln!("Invalid route: {err}");
+    /// }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn navigate_from_args(
+        &mut self,
+        state: &mut State,
+        scheme: Option<&str>,
+        env_var: Option<&str>,
+    ) -> Option<RouterResult> {
+        let path = deep_link::deep_link_from_args(std::env::args().skip(1), scheme)
+            .or_else(|| env_var.and_then(|var| std::env::var(var).ok()))
+            .filter(|path| !path.trim().is_empty())?;
+        Some(self.navigate_initial(state, path))
+    }
+
+    /// Replaces the history with the existing parent routes of `url`, followed by `url`
+    fn open_deep_link(&mut self, state: &mut State, url: &str) -> RouterResult {
+        let (path, _) = Self::parse_path(url);
+        if self.router.at(path).is_err() {
+            return Err(RouterError::NotFound);
+        }
+
+        let parents = if path == "/" {
+            Vec::new()
+        } else {
+            std::iter::once("/")
+                .chain(
+                    path.match_indices('/')
+                        .skip(1)
+                        .map(|(index, _)| &path[..index]),
                 )
-                .unwrap();
+                .filter(|parent| {
+                    self.router
+                        .at(parent)
+                        .is_ok_and(|match_| matches!(match_.value, RouteKind::Route(..)))
+                })
+                .map(ToOwned::to_owned)
+                .collect()
+        };
+
+        self.reset_history();
+        for (index, url) in parents.iter().map(String::as_str).chain([url]).enumerate() {
+            let result = if self.history.is_empty() {
+                self.history_kind
+                    .replace(url, 0)
+                    .map_err(RouterError::from)
+                    .and_then(|()| {
+                        self.navigate_history_impl(
+                            state,
+                            url,
+                            TransitionConfig::none(),
+                            0,
+                            None,
+                            None,
+                            0,
+                        )
+                    })
+            } else {
+                self.navigate_transition_impl(state, url, TransitionConfig::none(), None, None)
+            };
+            // Parents that can't be opened are skipped
+            if index == parents.len() {
+                result?;
+            }
         }
+        self.current_transition = None;
 
-        router
+        Ok(())
+    }
+
+    /// Removes all routes, without animating them
+    fn reset_history(&mut self) {
+        self.history.clear();
+        self.forward_history.clear();
+        self.preloaded = None;
+        self.current_transition = None;
+    }
+
+    /// A handle to navigate this router without mutable access to it, e.g. from another thread
+    pub fn handle(&self) -> RouterHandle {
+        RouterHandle {
+            sender: self.inbox.sender(),
+        }
     }
 
     /// Get the active route
     pub fn active_route(&self) -> Option<&str> {
-        self.history.last().map(|r| r.path.as_str())
+        self.history.last().map(|r| r.entry.path.as_str())
     }
 
-    fn parse_path(path: &str) -> (&str, BTreeMap<Cow<str>, Cow<str>>) {
-        path.split_once('?')
-            .map(|(path, q)| (path, form_urlencoded::parse(q.as_bytes()).collect()))
-            .unwrap_or((path, BTreeMap::new()))
+    /// Get the history entry of the active route
+    pub fn current(&self) -> Option<&HistoryEntry> {
+        self.history.last().map(|r| &r.entry)
     }
 
-    fn navigate_impl(
+    /// Iterate over the history, starting with the first route. The last entry is the active route.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> + ExactSizeIterator {
+        self.history.iter().map(|r| &r.entry)
+    }
+
+    /// The title of the active route, see [`RouterBuilder::title`]
+    pub fn title(&self) -> Option<String> {
+        self.history
+            .last()
+            .and_then(|route| self.route_title(route))
+    }
+
+    /// The routes in the history that have a title, starting with the first route.
+    /// Use this to show a breadcrumb navigation, going back via [`EguiRouter::back_to`].
+    pub fn breadcrumbs(&self) -> Vec<Breadcrumb> {
+        self.history
+            .iter()
+            .filter_map(|route| {
+                Some(Breadcrumb {
+                    title: self.route_title(route)?,
+                    entry: route.entry.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn route_title(&self, route: &RouteState<State>) -> Option<String> {
+        match self.titles.at(&route.entry.path) {
+            Ok(match_) => Some(match_.value.get(&route.entry)),
+            Err(_) => route
+                .mount
+                .as_ref()
+                .and_then(|mount| mount.router.borrow().title()),
+        }
+    }
+
+    /// The route (as passed to e.g. [`RouterBuilder::route`]) that handles `path`, following the
+    /// precedence rules explained in [`RouterBuilder::route`]. A query is ignored. Returns `None`
+    /// if no route matches (so the fallback would be shown). For paths of a mounted router, the
+    /// prefix of the mount is returned.
+    ///
+    /// ```
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut Ui, _: &mut ()| {}
+    /// }
+    ///
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/files/{*path}", page)
+    ///     .route("/files/readme", page)
+    ///     .route("/post/{id}", page)
+    ///     .route("/post/new", page)
+    ///     .build(&mut ());
+    ///
+    /// assert_eq!(router.matched_route("/files/readme"), Some("/files/readme"));
+    /// assert_eq!(router.matched_route("/files/docs/intro.md"), Some("/files/{*path}"));
+    /// assert_eq!(router.matched_route("/post/new?draft=1"), Some("/post/new"));
+    /// assert_eq!(router.matched_route("/post/1"), Some("/post/{id}"));
+    /// assert_eq!(router.matched_route("/files/"), None);
+    /// ```
+    pub fn matched_route(&self, path: &str) -> Option<&str> {
+        let (path, _) = Self::parse_path(path);
+        self.patterns
+            .at(path)
+            .ok()
+            .map(|match_| match_.value.as_str())
+    }
+
+    /// The metadata of type `T` of the route matching `path`, see [`RouterBuilder::metadata`].
+    /// Mounted routers have their own metadata.
+    pub fn metadata_for<T: Any>(&self, path: &str) -> Option<&T> {
+        self.metadata
+            .get(path.split('?').next().unwrap_or_default())
+    }
+
+    /// All routes that have metadata of type `T` (in the order their metadata was added),
+    /// e.g. to generate a sidebar from the routes
+    pub fn routes_with_metadata<T: Any>(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.metadata.iter()
+    }
+
+    /// Save the navigation stack and the forward history, so they can be reopened later via
+    /// [`EguiRouter::restore`].
+    pub fn save_state(&self) -> RouterState {
+        RouterState {
+            history: self.history().cloned().collect(),
+            forward: self.forward_history.clone(),
+        }
+    }
+
+    /// Get the number of routes in the history
+    pub fn depth(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Returns true if there is a previous route to go back to
+    /// (in this router or in the active mounted router).
+    pub fn can_go_back(&self) -> bool {
+        self.history.len() > 1
+            || self
+                .active_mount()
+                .is_some_and(|mount| mount.router.borrow().can_go_back())
+    }
+
+    /// Returns true if there is a route to go forward to, see [`Self::forward`]
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward_history.is_empty()
+    }
+
+    /// Remove all routes from the history except the active one, so [`Self::back`] has nothing
+    /// to go back to. The forward history is cleared as well.
+    pub fn clear_history(&mut self) {
+        let len = self.history.len();
+        self.history.drain(..len.saturating_sub(1));
+        self.forward_history.clear();
+        self.notify_navigation();
+    }
+
+    /// Replace the current route with `path` (see [`Self::replace`]) and clear the history, so
+    /// `path` is the only route left, e.g. to return to the start screen of a kiosk app.
+    pub fn reset_to(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
+        self.replace(state, path)?;
+        self.clear_history();
+        Ok(())
+    }
+
+    /// Add a route while the router is running, e.g. when a plugin is loaded. If `route` is
+    /// already registered (with the same param names), its handler is replaced, keeping its
+    /// title, metadata and [`RoutePolicy`]. See [`RouterBuilder::route`] for the route syntax.
+    /// Returns [`RouterError::InvalidRoute`] if the route is invalid or conflicts with a
+    /// different existing route, the router is left unchanged then.
+    ///
+    /// Routes that are already open keep showing their current instance, the new handler is
+    /// used the next time the route is opened or rebuilt.
+    ///
+    /// ```
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut Ui, _: &mut ()| {}
+    /// }
+    ///
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// router.add_route("/plugin", page).unwrap();
+    /// router.navigate(&mut (), "/plugin").unwrap();
+    ///
+    /// router.back().unwrap();
+    /// assert!(router.unroute("/plugin"));
+    /// assert_eq!(router.matched_route("/plugin"), None);
+    /// ```
+    pub fn add_route<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
         &mut self,
-        state: &mut State,
-        path: &str,
-        transition_config: TransitionConfig,
-        new_state: u32,
+        route: &str,
+        mut handler: Han,
     ) -> RouterResult {
-        let (path, query) = Self::parse_path(path);
+        let replaced = self.route_entries(route);
+        self.router
+            .check_insert(route, &replaced)
+            .map_err(RouterError::InvalidRoute)?;
+        self.remove_route_entries(route);
+        let kind = RouteKind::Route(
+            Box::new(move |req| handler.handle(req)),
+            RouteOptions::default(),
+        );
+        self.router
+            .insert(route, kind)
+            .map_err(RouterError::InvalidRoute)?;
+        self.patterns
+            .insert(route, route.to_owned())
+            .map_err(RouterError::InvalidRoute)
+    }
 
-        let mut redirect = None;
-        let result = self.router.at_mut(path);
+    /// Remove a route (as passed to e.g. [`RouterBuilder::route`] or [`Self::add_route`], or the
+    /// prefix of a [`RouterBuilder::mount`]) while the router is running, together with its
+    /// title, metadata and [`RoutePolicy`]. Returns false if there was no such route.
+    ///
+    /// Paths of the removed route are handled by the next matching route or the fallback from
+    /// now on. Routes that are already open keep showing their current instance until they are
+    /// closed, but can't be opened again.
+    pub fn unroute(&mut self, route: &str) -> bool {
+        let removed = self.remove_route_entries(route);
+        self.titles.remove(route);
+        self.route_policies.remove(route);
+        self.metadata.remove(route);
+        removed
+    }
 
-        let result = match result {
-            Ok(match_) => {
-                match match_.value {
-                    RouteKind::Route(handler) => {
-                        let route = handler(Request {
-                            state,
-                            params: match_.params,
-                            query,
-                        });
-                        self.history.push(RouteState {
-                            path: path.to_string(),
-                            route,
-                            id: ID.fetch_add(1, Ordering::SeqCst),
-                            state: new_state,
-                        });
-
-                        self.current_transition = Some(CurrentTransition {
-                            active_transition: ActiveTransition::forward(transition_config.clone())
-                                .with_default_duration(self.default_duration),
-                            leaving_route: None,
-                        });
-                    }
-                    RouteKind::Redirect(r) => {
-                        redirect = Some(r.clone());
-                    }
-                }
-                Ok(())
+    /// The entries of the route table that belong to `route` (a mount has two)
+    fn route_entries(&self, route: &str) -> Vec<String> {
+        self.patterns
+            .iter()
+            .filter(|(_, pattern)| *pattern == route)
+            .map(|(entry, _)| entry.to_owned())
+            .collect()
+    }
+
+    /// Removes the entries of the route table that belong to `route` (a mount has two) and the
+    /// preloaded routes, which might have been created by the removed handler
+    fn remove_route_entries(&mut self, route: &str) -> bool {
+        let entries = self.route_entries(route);
+        for entry in &entries {
+            self.router.remove(entry);
+            self.patterns.remove(entry);
+        }
+        self.preloaded = None;
+        !entries.is_empty()
+    }
+
+    /// Drops the oldest routes beyond [`RouterBuilder::max_history`]
+    fn trim_history(&mut self) {
+        if let Some(max) = self.max_history {
+            let len = self.history.len();
+            self.history.drain(..len.saturating_sub(max));
+            let len = self.forward_history.len();
+            self.forward_history.drain(..len.saturating_sub(max));
+        }
+    }
+
+    /// The [`RoutePolicy`] of the route matching `path` (without a query)
+    fn route_policy(&self, path: &str) -> RoutePolicy {
+        self.route_policies
+            .at(path)
+            .map_or(self.default_route_policy, |match_| *match_.value)
+    }
+
+    /// Called before a route is pushed on top of the active route. Marks the active route to be
+    /// rebuilt when it's shown again, if its policy is [`RoutePolicy::Rebuild`].
+    /// Routes underneath an overlay route stay visible, so they are kept alive.
+    fn cover_active_route(&mut self, overlay: bool) {
+        if let Some(last) = self.history.last_mut() {
+            if !overlay && last.policy == RoutePolicy::Rebuild {
+                last.stale = true;
             }
-            Err(e) => match e {
-                MatchError::NotFound => Err(RouterError::NotFound),
+        }
+    }
+
+    /// Runs the handler of the active route again, if it has been marked as stale by
+    /// [`Self::cover_active_route`]. Payloads are not passed again.
+    // Replacing `_tasks` cancels the tasks of the old instance
+    #[allow(clippy::used_underscore_binding)]
+    fn rebuild_active_route(&mut self, state: &mut State) {
+        let Some(last) = self.history.last_mut().filter(|last| last.stale) else {
+            return;
+        };
+        last.stale = false;
+
+        let url = last.entry.url();
+        let (path, query) = Self::parse_path(&url);
+        let metadata = self.metadata.at(path);
+        let handle = RouterHandle {
+            sender: self.inbox.sender(),
+        };
+        let (handler, params) = match self.router.at_mut(path) {
+            Ok(match_) => match match_.value {
+                RouteKind::Route(handler, _) => (handler, match_.params),
+                RouteKind::Redirect(_) | RouteKind::Mount(_) => return,
+            },
+            Err(MatchError::NotFound) => match &mut self.fallback {
+                Some(fallback) => (fallback, matchit::Params::new()),
+                None => return,
             },
         };
 
-        if let Some(redirect) = redirect {
-            self.history_kind.replace(&redirect, new_state)?;
-            self.navigate_impl(state, &redirect, transition_config, new_state)?;
+        let mut tasks = TaskScope::default();
+        let route = split_redirect(handler(Request {
+            params,
+            query,
+            state,
+            payload: None,
+            metadata,
+            tasks: &mut tasks,
+            handle,
+        }));
+        // The route was already shown, so we keep the old instance instead of redirecting
+        if let Ok(route) = route {
+            last.route = route;
+            last._tasks = tasks;
+            self.scroll_positions.lock().restore_route(last.id);
         }
+    }
 
-        result
+    /// The ids of the routes, followed by the ids of the routes of the active mounted router
+    fn route_ids(&self) -> Vec<usize> {
+        let mut ids: Vec<_> = self.history.iter().map(|route| route.id).collect();
+        if let Some(mount) = self.active_mount() {
+            ids.extend(mount.router.borrow().route_ids());
+        }
+        ids
     }
 
-    /// Navigate with a custom transition
-    pub fn navigate_transition(
-        &mut self,
-        state: &mut State,
-        path: impl Into<String>,
-        transition_config: TransitionConfig,
-    ) -> RouterResult {
-        let path = path.into();
-        let current_state = self.history.last().map_or(0, |r| r.state);
-        let new_state = current_state + 1;
-        self.history_kind.push(&path, new_state)?;
-        self.navigate_impl(state, &path, transition_config, new_state)?;
-        Ok(())
+    /// Calls the [`RouterBuilder::on_navigate`] callback if the active route changed since the
+    /// last call
+    fn notify_navigation(&mut self) {
+        if self.on_navigate.is_none() {
+            return;
+        }
+        let current = NavigationSnapshot {
+            ids: self.route_ids(),
+            entry: self.current().cloned(),
+        };
+        let event = self.last_navigation.event(&current);
+        self.last_navigation = current;
+        if let (Some(on_navigate), Some(event)) = (&mut self.on_navigate, event) {
+            on_navigate(&event);
+        }
     }
 
-    /// Navigate with the default transition
-    pub fn navigate(&mut self, state: &mut State, route: impl Into<String>) -> RouterResult {
-        self.navigate_transition(state, route, self.forward_transition.clone())
+    fn active_mount(&self) -> Option<Rc<Mount<State>>> {
+        self.history.last().and_then(|r| r.mount.clone())
+    }
+
+    /// Whether the swipe back gesture should go back in this router.
+    /// If a mounted router can go back, the gesture is left to it.
+    fn can_swipe_back(&self) -> bool {
+        self.swipe_back
+            && self.history.len() > 1
+            && self
+                .active_mount()
+                .is_none_or(|mount| !mount.router.borrow().can_go_back())
     }
 
-    fn back_impl(&mut self, transition_config: TransitionConfig) {
-        if self.history.len() > 1 {
+    /// The transition used when swiping back from the current route
+    fn swipe_back_transition(&self) -> TransitionConfig {
+        self.history
+            .last()
+            .and_then(|r| r.transition.clone())
+            .unwrap_or_else(|| self.backward_transition.clone())
+    }
+
+    /// Tracks the swipe back gesture and completes or cancels it on release
+    fn swipe_back_ui(&mut self, ui: &Ui) {
+        if self.current_transition.is_some() || !self.can_swipe_back() {
+            self.swipe_back_progress = None;
+            return;
+        }
+
+        let rect = ui.max_rect();
+        let edge = Rect::from_min_size(rect.min, Vec2::new(SWIPE_BACK_EDGE_WIDTH, rect.height()));
+        let response = ui.interact(edge, ui.id().with("egui_router_swipe_back"), Sense::drag());
+
+        if response.dragged() {
+            let (origin, pos) = ui.input(|i| (i.pointer.press_origin(), i.pointer.latest_pos()));
+            if let Some((origin, pos)) = origin.zip(pos) {
+                self.swipe_back_progress =
+                    Some(((pos.x - origin.x) / rect.width().max(1.0)).clamp(0.0, 1.0));
+            }
+        } else if response.drag_stopped() {
+            if let Some(progress) = self.swipe_back_progress.take() {
+                let velocity = ui.input(|i| i.pointer.velocity().x);
+                self.finish_swipe_back(progress, progress > 0.5 || velocity > SWIPE_BACK_VELOCITY);
+            }
+        } else {
+            self.swipe_back_progress = None;
+        }
+    }
+
+    /// Animates the rest of the swipe back gesture, going back if `commit` is true and returning
+    /// to the current route otherwise
+    fn finish_swipe_back(&mut self, progress: f32, commit: bool) {
+        let config = self.swipe_back_transition();
+        // The gesture moves the routes linearly, so we continue without easing to avoid a jump
+        let linear: fn(f32) -> f32 = |t| t;
+
+        if commit {
+            if self.history_kind.back().is_err() {
+                return;
+            }
             let leaving_route = self.history.pop();
+            if let Some(route) = &leaving_route {
+                self.forward_history.push(route.entry.url());
+            }
             self.current_transition = Some(CurrentTransition {
-                active_transition: ActiveTransition::backward(transition_config)
-                    .with_default_duration(self.default_duration),
+                active_transition: ActiveTransition::backward(config)
+                    .with_default_duration(self.default_duration)
+                    .with_duration_scale(self.duration_scale)
+                    .with_progress(progress)
+                    .with_easing(linear),
                 leaving_route,
             });
+        } else {
+            // A forward transition at 1 - progress looks exactly like the backward one
+            self.current_transition = Some(CurrentTransition {
+                active_transition: ActiveTransition::forward(config)
+                    .with_default_duration(self.default_duration)
+                    .with_duration_scale(self.duration_scale)
+                    .with_progress(1.0 - progress)
+                    .with_easing(linear),
+                leaving_route: None,
+            });
         }
     }
 
-    /// Go back with a custom transition
-    pub fn back_transition(&mut self, transition_config: TransitionConfig) -> RouterResult {
-        self.history_kind.back()?;
-        self.back_impl(transition_config);
-        Ok(())
+    fn history_entry(
+        path: &str,
+        params: &matchit::Params,
+        query: &BTreeMap<Cow<str>, Cow<str>>,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            path: path.to_string(),
+            params: params
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            query: query
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
     }
 
-    /// Go back with the default transition
-    pub fn back(&mut self) -> RouterResult {
-        self.back_transition(self.backward_transition.clone())
+    /// Whether `url` (path and query) is the url of the active route
+    fn is_active_url(&self, url: &str) -> bool {
+        let (path, query) = Self::parse_path(url);
+        self.current().is_some_and(|entry| {
+            entry.path == path
+                && entry.query.len() == query.len()
+                && query
+                    .iter()
+                    .all(|(key, value)| entry.query.get(key.as_ref()) == Some(&value.to_string()))
+        })
     }
 
-    /// Replace the current route with a custom transition
-    pub fn replace_transition(
+    fn parse_path(path: &str) -> (&str, BTreeMap<Cow<str>, Cow<str>>) {
+        path.split_once('?')
+            .map(|(path, q)| (path, form_urlencoded::parse(q.as_bytes()).collect()))
+            .unwrap_or((path, BTreeMap::new()))
+    }
+
+    /// Runs the guards for `path`. Returns the path to redirect to, if a guard redirects.
+    fn run_guards(&mut self, state: &mut State, path: &str) -> RouterResult<Option<String>> {
+        if self.guards.is_empty() {
+            return Ok(None);
+        }
+
+        let (path, query) = Self::parse_path(path);
+        let params = self
+            .router
+            .at(path)
+            .map_or_else(|_| matchit::Params::new(), |match_| match_.params);
+        // Tasks spawned by guards are cancelled once the guards ran
+        let mut tasks = TaskScope::default();
+        let mut request = Request {
+            params,
+            query,
+            state,
+            payload: None,
+            metadata: self.metadata.at(path),
+            tasks: &mut tasks,
+            handle: self.handle(),
+        };
+
+        for guard in &mut self.guards {
+            match guard(path, &mut request) {
+                GuardAction::Allow => {}
+                GuardAction::Cancel => return Err(RouterError::Cancelled),
+                GuardAction::Redirect(redirect) => return Ok(Some(redirect)),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Runs the guards for `path`, following their redirects.
+    /// Returns the final path, if a guard redirected.
+    fn resolve_guards(&mut self, state: &mut State, path: &str) -> RouterResult<Option<String>> {
+        let mut redirect = None;
+        for _ in 0..=MAX_REDIRECTS {
+            match self.run_guards(state, redirect.as_deref().unwrap_or(path))? {
+                Some(next) => redirect = Some(next),
+                None => return Ok(redirect),
+            }
+        }
+        Err(RouterError::TooManyRedirects)
+    }
+
+    /// Navigate to a path that is already in the [`History`] (the initial route, a navigation
+    /// via the browser history or a redirect), running the guards first.
+    #[allow(clippy::too_many_arguments)]
+    fn navigate_history_impl(
         &mut self,
         state: &mut State,
-        path: impl Into<String>,
+        path: &str,
         transition_config: TransitionConfig,
+        new_state: u32,
+        payload: Option<Payload>,
+        on_result: Option<ResultCallback<State>>,
+        redirects: usize,
     ) -> RouterResult {
-        let mut redirect = None;
-
-        let path = path.into();
-        let result = self.router.at_mut(&path);
-
-        let current_state = self.history.last().map_or(0, |r| r.state);
-        let new_state = current_state;
+        if redirects > MAX_REDIRECTS {
+            return Err(RouterError::TooManyRedirects);
+        }
+        if let Some(redirect) = self.resolve_guards(state, path)? {
+            self.history_kind.replace(&redirect, new_state)?;
+            return self.navigate_impl(
+                state,
+                &redirect,
+                transition_config,
+                new_state,
+                payload,
+                on_result,
+                redirects,
+            );
+        }
+        self.navigate_impl(
+            state,
+            path,
+            transition_config,
+            new_state,
+            payload,
+            on_result,
+            redirects,
+        )
+    }
 
-        let (path, query) = Self::parse_path(&path);
+    /// Navigate to `path`. `on_result` is set on the route that is pushed (see
+    /// [`Self::navigate_for_result`]).
+    #[allow(clippy::too_many_arguments)]
+    fn navigate_impl(
+        &mut self,
+        state: &mut State,
+        path: &str,
+        transition_config: TransitionConfig,
+        new_state: u32,
+        payload: Option<Payload>,
+        on_result: Option<ResultCallback<State>>,
+        redirects: usize,
+    ) -> RouterResult {
+        let full_path = path;
+        let (path, query) = Self::parse_path(path);
 
-        let result = match result {
+        let preloaded = self.take_preloaded(full_path, payload.is_some());
+        let metadata = self.metadata.at(path);
+        let handle = self.handle();
+        let (handler, params, options) = match self.router.at_mut(path) {
             Ok(match_) => match match_.value {
-                RouteKind::Route(handler) => {
-                    self.history_kind.replace(path, new_state)?;
-                    let leaving_route = self.history.pop();
-                    let route = handler(Request {
+                RouteKind::Route(handler, options) => (handler, match_.params, options.clone()),
+                RouteKind::Redirect(redirect) => {
+                    let redirect = redirect.clone();
+                    self.history_kind.replace(&redirect, new_state)?;
+                    return self.navigate_history_impl(
                         state,
-                        params: match_.params,
-                        query,
-                    });
-                    self.history.push(RouteState {
-                        path: path.to_string(),
-                        route,
-                        id: ID.fetch_add(1, Ordering::SeqCst),
-                        state: new_state,
-                    });
-
-                    self.current_transition = Some(CurrentTransition {
-                        active_transition: ActiveTransition::forward(transition_config.clone())
-                            .with_default_duration(self.default_duration),
-                        leaving_route,
-                    });
-
-                    Ok(())
+                        &redirect,
+                        transition_config,
+                        new_state,
+                        payload,
+                        on_result,
+                        redirects + 1,
+                    );
                 }
-                RouteKind::Redirect(r) => {
-                    redirect = Some(r.clone());
-                    Ok(())
+                RouteKind::Mount(mount) => {
+                    let mount = mount.clone();
+                    return self.navigate_mount_impl(
+                        state,
+                        &mount,
+                        full_path,
+                        transition_config,
+                        new_state,
+                        payload,
+                        on_result,
+                        false,
+                    );
                 }
             },
-            Err(MatchError::NotFound) => Err(RouterError::NotFound),
+            Err(MatchError::NotFound) => match &mut self.fallback {
+                Some(fallback) => (fallback, matchit::Params::new(), RouteOptions::default()),
+                None => return Err(RouterError::NotFound),
+            },
         };
 
-        if let Some(redirect) = redirect {
-            self.history_kind.replace(&redirect, new_state)?;
-            self.replace_transition(state, redirect, transition_config)?;
-        }
+        let entry = Self::history_entry(path, &params, &query);
+        let mut tasks = TaskScope::default();
+        let route = if let Some(preloaded) = preloaded {
+            tasks = preloaded.tasks;
+            Ok(preloaded.route)
+        } else {
+            split_redirect(handler(Request {
+                params,
+                query,
+                state,
+                payload,
+                metadata,
+                tasks: &mut tasks,
+                handle,
+            }))
+        };
+        let route = match route {
+            Ok(route) => route,
+            Err(redirect) => {
+                self.history_kind.replace(&redirect, new_state)?;
+                return self.navigate_history_impl(
+                    state,
+                    &redirect,
+                    transition_config,
+                    new_state,
+                    None,
+                    on_result,
+                    redirects + 1,
+                );
+            }
+        };
+        self.cover_active_route(options.overlay);
+        self.history.push(RouteState {
+            entry,
+            route,
+            id: ID.fetch_add(1, Ordering::SeqCst),
+            state: new_state,
+            mount: None,
+            transition: options.transition.clone(),
+            overlay: options.overlay,
+            on_result,
+            policy: self.route_policy(path),
+            stale: false,
+            _tasks: tasks,
+        });
+        self.trim_history();
 
-        result
-    }
+        self.current_transition = Some(CurrentTransition {
+            active_transition: ActiveTransition::forward(
+                options.transition.unwrap_or(transition_config),
+            )
+            .with_default_duration(self.default_duration)
+            .with_duration_scale(self.duration_scale),
+            leaving_route: None,
+        });
 
-    /// Replace the current route with the default transition
-    pub fn replace(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
-        self.replace_transition(state, path, self.replace_transition.clone())
+        Ok(())
     }
 
-    /// Render the router
-    pub fn ui(&mut self, ui: &mut Ui, state: &mut State) {
-        for e in self.history_kind.update(ui.ctx()) {
-            let state_index = e.state.unwrap_or(0);
-            let path = e.location;
+    /// Navigate to a path of a mounted router. If the mount is already active, only the child
+    /// router navigates (with its own transition). Otherwise, the child router is reset and
+    /// shown as a new route.
+    ///
+    /// `on_result` is set on the route the child router pushes, or on the mount itself if it
+    /// wasn't active, since going back then leaves the whole mount.
+    #[allow(clippy::too_many_arguments)]
+    fn navigate_mount_impl(
+        &mut self,
+        state: &mut State,
+        mount: &Rc<Mount<State>>,
+        full_path: &str,
+        transition_config: TransitionConfig,
+        new_state: u32,
+        payload: Option<Payload>,
+        on_result: Option<ResultCallback<State>>,
+        replace: bool,
+    ) -> RouterResult {
+        let child_path = mount.child_path(full_path);
 
-            if let Some(route_state) = self
-                .history
-                .iter()
-                .find(|r| r.path == path && r.state == state_index)
-                .map(|r| r.state)
+        if self
+            .active_mount()
+            .is_some_and(|active| Rc::ptr_eq(&active, mount))
+        {
             {
-                let active_state = self.history.last().map_or(0, |r| r.state);
-
-                // Retain all routes with a state less than or equal to the new state and the active state so that we can animate them out
-                self.history
-                    .retain(|r| r.state <= route_state || r.state == active_state);
-
-                if route_state < active_state {
-                    self.back_impl(self.backward_transition.clone());
+                let mut child = mount.router.borrow_mut();
+                if replace {
+                    let transition = child.replace_transition.clone();
+                    child.replace_transition_impl(state, child_path, transition, payload, 0)?;
+                } else {
+                    let transition = child.forward_transition.clone();
+                    child.navigate_transition_impl(
+                        state,
+                        &child_path,
+                        transition,
+                        payload,
+                        on_result,
+                    )?;
                 }
-            } else {
-                self.navigate_impl(state, &path, self.forward_transition.clone(), state_index)
-                    .ok();
             }
+            if let Some(last) = self.history.last_mut() {
+                last.entry = mount.entry();
+                last.state = new_state;
+            }
+            return Ok(());
+        }
+
+        {
+            let mut child = mount.router.borrow_mut();
+            child.history.clear();
+            child.current_transition = None;
+            child.navigate_transition_impl(
+                state,
+                &child_path,
+                TransitionConfig::none(),
+                payload,
+                None,
+            )?;
+        }
+
+        let leaving_route = if replace {
+            self.history.pop()
+        } else {
+            self.cover_active_route(false);
+            None
+        };
+        // The mounted router keeps its routes, so it is always kept alive
+        self.history.push(RouteState {
+            entry: mount.entry(),
+            route: Ok(Box::new(mount.route())),
+            id: ID.fetch_add(1, Ordering::SeqCst),
+            state: new_state,
+            mount: Some(mount.clone()),
+            transition: None,
+            overlay: false,
+            on_result,
+            policy: RoutePolicy::KeepAlive,
+            stale: false,
+            _tasks: TaskScope::default(),
+        });
+        self.trim_history();
+
+        self.current_transition = Some(CurrentTransition {
+            active_transition: ActiveTransition::forward(transition_config)
+                .with_default_duration(self.default_duration)
+                .with_duration_scale(self.duration_scale),
+            leaving_route,
+        });
+
+        Ok(())
+    }
+
+    fn navigate_transition_impl(
+        &mut self,
+        state: &mut State,
+        path: &str,
+        transition_config: TransitionConfig,
+        payload: Option<Payload>,
+        on_result: Option<ResultCallback<State>>,
+    ) -> RouterResult {
+        if self.dedup_navigation && self.is_active_url(path) {
+            return Ok(());
+        }
+        let redirect = self.resolve_guards(state, path)?;
+        let path = redirect.as_deref().unwrap_or(path);
+        self.forward_history.clear();
+
+        let current_state = self.history.last().map_or(0, |r| r.state);
+        let new_state = current_state + 1;
+        self.history_kind.push(path, new_state)?;
+        self.navigate_impl(
+            state,
+            path,
+            transition_config,
+            new_state,
+            payload,
+            on_result,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Navigate with a custom transition
+    pub fn navigate_transition(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        transition_config: TransitionConfig,
+    ) -> RouterResult {
+        let result =
+            self.navigate_transition_impl(state, &path.into(), transition_config, None, None);
+        self.notify_navigation();
+        result
+    }
+
+    /// Run the handler of `route` in advance, without navigating, so the next navigation to it
+    /// (with the same url) shows the route right away. E.g. call this when the user hovers a
+    /// link, so the data of async routes is already loading when they click it. See also
+    /// [`crate::RouterLink::preload`] and [`RouterHandle::preload`].
+    ///
+    /// The guards run as usual; if they cancel or redirect, nothing is preloaded. Routes that
+    /// redirect, mounted routers and the active route are not preloaded either.
+    ///
+    /// Only the last preloaded route is kept, so preloading another route drops it (e.g. when
+    /// the user hovers several links). It is also dropped on the next navigation (including
+    /// going back), and it is not used when navigating with a payload.
+    pub fn preload(&mut self, state: &mut State, route: impl Into<String>) -> RouterResult {
+        let url = route.into();
+        if self.preloaded.as_ref().is_some_and(|p| p.url == url) || self.is_active_url(&url) {
+            return Ok(());
+        }
+        if self.resolve_guards(state, &url)?.is_some() {
+            return Ok(());
+        }
+
+        let (path, query) = Self::parse_path(&url);
+        let metadata = self.metadata.at(path);
+        let handle = self.handle();
+        let (handler, params) = match self.router.at_mut(path) {
+            Ok(match_) => match match_.value {
+                RouteKind::Route(handler, _) => (handler, match_.params),
+                RouteKind::Redirect(_) | RouteKind::Mount(_) => return Ok(()),
+            },
+            Err(MatchError::NotFound) => match &mut self.fallback {
+                Some(fallback) => (fallback, matchit::Params::new()),
+                None => return Err(RouterError::NotFound),
+            },
+        };
+
+        let mut tasks = TaskScope::default();
+        let route = split_redirect(handler(Request {
+            params,
+            query,
+            state,
+            payload: None,
+            metadata,
+            tasks: &mut tasks,
+            handle,
+        }));
+        // A redirect is followed when navigating
+        if let Ok(route) = route {
+            self.preloaded = Some(PreloadedRoute { url, route, tasks });
+        }
+        Ok(())
+    }
+
+    /// Takes the route preloaded for `url` (unless a payload is passed, which the preloaded route
+    /// didn't get), dropping a route preloaded for another url
+    fn take_preloaded(&mut self, url: &str, with_payload: bool) -> Option<PreloadedRoute<State>> {
+        self.preloaded
+            .take()
+            .filter(|preloaded| !with_payload && preloaded.url == url)
+    }
+
+    /// Navigate with the default transition
+    pub fn navigate(&mut self, state: &mut State, route: impl Into<String>) -> RouterResult {
+        self.navigate_transition(state, route, self.forward_transition.clone())
+    }
+
+    /// Navigate with a custom transition, passing a payload to the route handler.
+    /// The handler can access it via [`Request::payload`].
+    pub fn navigate_transition_with(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        transition_config: TransitionConfig,
+        payload: impl Any + Send + Sync,
+    ) -> RouterResult {
+        let result = self.navigate_transition_impl(
+            state,
+            &path.into(),
+            transition_config,
+            Some(Box::new(payload)),
+            None,
+        );
+        self.notify_navigation();
+        result
+    }
+
+    /// Navigate with the default transition, passing a payload to the route handler.
+    /// The handler can access it via [`Request::payload`].
+    ///
+    /// This is useful to pass objects that can't easily be encoded in the path or query.
+    /// Note that the payload is not stored in the history, so if the route is opened via a link
+    /// or the browser history, the handler will receive no payload.
+    pub fn navigate_with(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        payload: impl Any + Send + Sync,
+    ) -> RouterResult {
+        self.navigate_transition_with(state, path, self.forward_transition.clone(), payload)
+    }
+
+    fn back_impl(&mut self, transition_config: TransitionConfig, count: usize) {
+        self.preloaded = None;
+        if count > 0 && self.history.len() > count {
+            let forward = self.history[self.history.len() - count..]
+                .iter()
+                .rev()
+                .map(|r| r.entry.url());
+            self.forward_history.extend(forward);
+
+            match self.current_transition.take() {
+                // The route we're leaving is still animating in, so we just play its
+                // transition in reverse from where it currently is
+                Some(CurrentTransition {
+                    active_transition,
+                    leaving_route: None,
+                }) if count == 1 && !active_transition.is_backward() => {
+                    self.current_transition = Some(CurrentTransition {
+                        active_transition: active_transition.reversed(),
+                        leaving_route: self.history.pop(),
+                    });
+                    return;
+                }
+                // We're already going back (e.g. the back button was pressed repeatedly), so the
+                // leaving route keeps animating out and the routes in between are skipped
+                Some(CurrentTransition {
+                    active_transition,
+                    leaving_route: Some(leaving_route),
+                }) if active_transition.is_backward() => {
+                    self.history.truncate(self.history.len() - count);
+                    self.current_transition = Some(CurrentTransition {
+                        active_transition,
+                        leaving_route: Some(leaving_route),
+                    });
+                    return;
+                }
+                _ => {}
+            }
+
+            let leaving_route = self.history.pop();
+            // Any routes in between are removed without animating them
+            self.history.truncate(self.history.len() + 1 - count);
+            // A route with its own transition also leaves with it
+            let transition_config = leaving_route
+                .as_ref()
+                .and_then(|r| r.transition.clone())
+                .unwrap_or(transition_config);
+            self.current_transition = Some(CurrentTransition {
+                active_transition: ActiveTransition::backward(transition_config)
+                    .with_default_duration(self.default_duration)
+                    .with_duration_scale(self.duration_scale),
+                leaving_route,
+            });
+        }
+    }
+
+    /// Follows the navigations of the [`History`], e.g. via the browser's back and forward buttons
+    fn handle_history_events(&mut self, ctx: &Context, state: &mut State) {
+        for e in self.history_kind.update(ctx) {
+            let state_index = e.state.unwrap_or(0);
+            let path = e.location;
+
+            if self.back_in_mount(&path, state_index) {
+                continue;
+            }
+
+            if let Some(route_state) = self
+                .history
+                .iter()
+                .find(|r| r.entry.url() == path && r.state == state_index)
+                .map(|r| r.state)
+            {
+                let active_state = self.history.last().map_or(0, |r| r.state);
+
+                // Retain all routes with a state less than or equal to the new state and the active state so that we can animate them out
+                self.history
+                    .retain(|r| r.state <= route_state || r.state == active_state);
+
+                if route_state < active_state {
+                    self.back_impl(self.backward_transition.clone(), 1);
+                }
+            } else {
+                // The browser's forward button goes to the route we went back from
+                if self.forward_history.last() == Some(&path) {
+                    self.forward_history.pop();
+                } else {
+                    self.forward_history.clear();
+                }
+                self.navigate_history_impl(
+                    state,
+                    &path,
+                    self.forward_transition.clone(),
+                    state_index,
+                    None,
+                    None,
+                    0,
+                )
+                .ok();
+            }
+        }
+    }
+
+    /// Handles a history event going back to an earlier route of the active mounted router
+    /// (e.g. the browser's back button), by going back in the mounted router.
+    /// `url` is the full url, including the prefix of the mount.
+    fn back_in_mount(&mut self, url: &str, state_index: u32) -> bool {
+        let Some(mount) = self.active_mount() else {
+            return false;
+        };
+        let leaving_url = mount.entry().url();
+        let mut child = mount.router.borrow_mut();
+        let Some(count) = child
+            .history
+            .iter()
+            .rev()
+            .skip(1)
+            .position(|r| mount.parent_entry(&r.entry).url() == url)
+        else {
+            return false;
+        };
+        let transition = child.backward_transition.clone();
+        child.back_impl(transition, count + 1);
+        drop(child);
+
+        self.forward_history.push(leaving_url);
+        if let Some(last) = self.history.last_mut() {
+            last.entry = mount.entry();
+            last.state = state_index;
+        }
+        true
+    }
+
+    /// Go back with a custom transition.
+    /// If a mounted router is active and has a previous route, the mounted router goes back
+    /// instead (with its own transition).
+    /// Returns [`RouterError::NoPreviousRoute`] if the current route is the first one.
+    pub fn back_transition(&mut self, transition_config: TransitionConfig) -> RouterResult {
+        if let Some(mount) = self.active_mount() {
+            let url = mount.entry().url();
+            let mut child = mount.router.borrow_mut();
+            if child.can_go_back() {
+                child.back()?;
+                self.forward_history.push(url);
+                drop(child);
+                self.history_kind.back()?;
+                if let Some(last) = self.history.last_mut() {
+                    last.entry = mount.entry();
+                    last.state = last.state.saturating_sub(1);
+                }
+                self.notify_navigation();
+                return Ok(());
+            }
+        }
+
+        if self.history.len() <= 1 {
+            return Err(RouterError::NoPreviousRoute);
+        }
+        self.history_kind.back()?;
+        self.back_impl(transition_config, 1);
+        self.notify_navigation();
+        Ok(())
+    }
+
+    /// Go back with the default transition
+    pub fn back(&mut self) -> RouterResult {
+        self.back_transition(self.backward_transition.clone())
+    }
+
+    /// Navigate with the default transition, calling `on_result` once the opened route returns
+    /// a result of type `T` via [`Self::back_with_result`], e.g. to open a route to pick a
+    /// contact and receive the selection in the route that opened it.
+    ///
+    /// `on_result` isn't called if the route is left without a result (e.g. via
+    /// [`Self::back`] or a swipe back gesture), or if the result isn't of type `T`.
+    /// It is dropped if no route is opened, e.g. because the route is already active and
+    /// [`crate::RouterBuilder::dedup_navigation`] is set. If the route redirects, the route it
+    /// redirects to returns the result.
+    /// To deliver the result to a route, capture e.g. a [`egui_inbox::UiInboxSender`] of the
+    /// route in `on_result`.
+    ///
+    /// ```
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// fn page(_req: Request<Option<String>>) -> impl Route<Option<String>> {
+    ///     |ui: &mut egui::Ui, _: &mut Option<String>| {}
+    /// }
+    ///
+    /// let mut picked = None;
+    /// let mut router: EguiRouter<Option<String>> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/pick_contact", page)
+    ///     .default_path("/")
+    ///     .build(&mut picked);
+    ///
+    /// router
+    ///     .navigate_for_result(&mut picked, "/pick_contact", |picked, contact: String| {
+    ///         *picked = Some(contact);
+    ///     })
+    ///     .unwrap();
+    /// // In the contact picker, once a contact was selected:
+    /// router
+    ///     .back_with_result(&mut picked, "Alice".to_owned())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(router.active_route(), Some("/"));
+    /// assert_eq!(picked.as_deref(), Some("Alice"));
+    /// ```
+    pub fn navigate_for_result<T: Any>(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        on_result: impl FnOnce(&mut State, T) + 'static,
+    ) -> RouterResult {
+        let on_result: ResultCallback<State> = Box::new(move |state, result| {
+            if let Ok(result) = result.downcast::<T>() {
+                on_result(state, *result);
+            }
+        });
+        let transition = self.forward_transition.clone();
+        let result =
+            self.navigate_transition_impl(state, &path.into(), transition, None, Some(on_result));
+        self.notify_navigation();
+        result
+    }
+
+    /// Go back with the default transition, passing `result` to the callback of the route we
+    /// go back from, see [`Self::navigate_for_result`]. The callback is called once the previous
+    /// route is active again. If the route has no callback, the result is dropped.
+    pub fn back_with_result(
+        &mut self,
+        state: &mut State,
+        result: impl Any + Send + Sync,
+    ) -> RouterResult {
+        if !self.can_go_back() {
+            return Err(RouterError::NoPreviousRoute);
+        }
+        let on_result = self.take_result_callback();
+        self.back()?;
+        if let Some(on_result) = on_result {
+            on_result(state, Box::new(result));
+        }
+        Ok(())
+    }
+
+    /// Takes the result callback of the route [`Self::back`] would leave
+    fn take_result_callback(&mut self) -> Option<ResultCallback<State>> {
+        if let Some(mount) = self.active_mount() {
+            let mut child = mount.router.borrow_mut();
+            if child.can_go_back() {
+                return child.take_result_callback();
+            }
+        }
+        self.history.last_mut()?.on_result.take()
+    }
+
+    /// Go forward to the route we last went back from, with a custom transition.
+    /// The route's handler is run again, since routes are dropped when going back.
+    /// Returns [`RouterError::NoForwardRoute`] if we didn't go back or navigated since.
+    /// If the navigation fails (e.g. a guard rejects it), the forward history is kept.
+    pub fn forward_transition(
+        &mut self,
+        state: &mut State,
+        transition_config: TransitionConfig,
+    ) -> RouterResult {
+        let url = self
+            .forward_history
+            .pop()
+            .ok_or(RouterError::NoForwardRoute)?;
+        let rest = std::mem::take(&mut self.forward_history);
+        let result = self.navigate_transition_impl(state, &url, transition_config, None, None);
+        self.forward_history = rest;
+        // Like in a browser, the route stays in the forward history if we couldn't go there
+        if result.is_err() {
+            self.forward_history.push(url);
+        }
+        self.notify_navigation();
+        result
+    }
+
+    /// Go forward with the default transition, see [`Self::forward_transition`]
+    pub fn forward(&mut self, state: &mut State) -> RouterResult {
+        self.forward_transition(state, self.forward_transition.clone())
+    }
+
+    /// Go back to the most recent route in the history whose path matches `predicate`, with a
+    /// custom transition. All routes above it are removed with a single backward transition.
+    ///
+    /// The predicate receives the [`HistoryEntry`] of each route, starting with the current one.
+    /// The routes of an active mounted router come first, with the full path including the
+    /// prefix of the mount. If one of them matches, the mounted router goes back instead (with its
+    /// own transition).
+    /// Returns [`RouterError::NotFound`] if no route matches, the history is left unchanged then.
+    pub fn pop_until_transition(
+        &mut self,
+        mut predicate: impl FnMut(&HistoryEntry) -> bool,
+        transition_config: TransitionConfig,
+    ) -> RouterResult {
+        if let Some(mount) = self.active_mount() {
+            let mut child = mount.router.borrow_mut();
+            if let Some(index) = child
+                .history
+                .iter()
+                .rposition(|route| predicate(&mount.parent_entry(&route.entry)))
+            {
+                let count = child.history.len() - 1 - index;
+                if count > 0 {
+                    self.history_kind.go_back(count)?;
+                    let forward = child.history[index + 1..]
+                        .iter()
+                        .rev()
+                        .map(|route| mount.parent_entry(&route.entry).url());
+                    self.forward_history.extend(forward);
+                    let transition = child.backward_transition.clone();
+                    child.back_impl(transition, count);
+                    drop(child);
+
+                    if let Some(last) = self.history.last_mut() {
+                        last.entry = mount.entry();
+                        last.state = last.state.saturating_sub(count as u32);
+                    }
+                    self.notify_navigation();
+                }
+                return Ok(());
+            }
+        }
+
+        let index = self
+            .history
+            .iter()
+            .rposition(|route| predicate(&route.entry))
+            .ok_or(RouterError::NotFound)?;
+        let count = self.history.len() - 1 - index;
+        if count > 0 {
+            self.history_kind.go_back(count)?;
+            self.back_impl(transition_config, count);
+            self.notify_navigation();
+        }
+        Ok(())
+    }
+
+    /// Go back to the most recent route in the history whose path matches `predicate`, with the
+    /// default transition. See [`Self::pop_until_transition`].
+    pub fn pop_until(&mut self, predicate: impl FnMut(&HistoryEntry) -> bool) -> RouterResult {
+        self.pop_until_transition(predicate, self.backward_transition.clone())
+    }
+
+    /// Go back to the most recent route with the given path, with the default transition.
+    /// This is useful to e.g. return to "/" after finishing a wizard with multiple steps.
+    /// Returns [`RouterError::NotFound`] if the path is not in the history.
+    pub fn back_to(&mut self, path: &str) -> RouterResult {
+        let path = path.split('?').next().unwrap_or_default();
+        self.pop_until(|entry| entry.path == path)
+    }
+
+    /// Replace the current route with a custom transition
+    pub fn replace_transition(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        transition_config: TransitionConfig,
+    ) -> RouterResult {
+        let result = self.replace_transition_impl(state, path.into(), transition_config, None, 0);
+        self.notify_navigation();
+        result
+    }
+
+    /// Replace the current route with a custom transition, passing a payload to the route
+    /// handler. The handler can access it via [`Request::payload`].
+    pub fn replace_transition_with(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        transition_config: TransitionConfig,
+        payload: impl Any + Send + Sync,
+    ) -> RouterResult {
+        let result = self.replace_transition_impl(
+            state,
+            path.into(),
+            transition_config,
+            Some(Box::new(payload)),
+            0,
+        );
+        self.notify_navigation();
+        result
+    }
+
+    fn replace_transition_impl(
+        &mut self,
+        state: &mut State,
+        full_path: String,
+        transition_config: TransitionConfig,
+        payload: Option<Payload>,
+        redirects: usize,
+    ) -> RouterResult {
+        if redirects > MAX_REDIRECTS {
+            return Err(RouterError::TooManyRedirects);
         }
+        let full_path = self.resolve_guards(state, &full_path)?.unwrap_or(full_path);
+        let (path, query) = Self::parse_path(&full_path);
+
+        let current_state = self.history.last().map_or(0, |r| r.state);
+        let new_state = current_state;
+
+        let preloaded = self.take_preloaded(&full_path, payload.is_some());
+        let metadata = self.metadata.at(path);
+        let handle = self.handle();
+        let (handler, params, options) = match self.router.at_mut(path) {
+            Ok(match_) => match match_.value {
+                RouteKind::Route(handler, options) => (handler, match_.params, options.clone()),
+                RouteKind::Redirect(redirect) => {
+                    let redirect = redirect.clone();
+                    return self.replace_transition_impl(
+                        state,
+                        redirect,
+                        transition_config,
+                        payload,
+                        redirects + 1,
+                    );
+                }
+                RouteKind::Mount(mount) => {
+                    let mount = mount.clone();
+                    self.history_kind.replace(&full_path, new_state)?;
+                    return self.navigate_mount_impl(
+                        state,
+                        &mount,
+                        &full_path,
+                        transition_config,
+                        new_state,
+                        payload,
+                        None,
+                        true,
+                    );
+                }
+            },
+            Err(MatchError::NotFound) => match &mut self.fallback {
+                Some(fallback) => (fallback, matchit::Params::new(), RouteOptions::default()),
+                None => return Err(RouterError::NotFound),
+            },
+        };
+
+        let entry = Self::history_entry(path, &params, &query);
+        let mut tasks = TaskScope::default();
+        let route = if let Some(preloaded) = preloaded {
+            tasks = preloaded.tasks;
+            Ok(preloaded.route)
+        } else {
+            split_redirect(handler(Request {
+                params,
+                query,
+                state,
+                payload,
+                metadata,
+                tasks: &mut tasks,
+                handle,
+            }))
+        };
+        let route = match route {
+            Ok(route) => route,
+            Err(redirect) => {
+                return self.replace_transition_impl(
+                    state,
+                    redirect,
+                    transition_config,
+                    None,
+                    redirects + 1,
+                );
+            }
+        };
+
+        self.history_kind.replace(&full_path, new_state)?;
+        let leaving_route = self.history.pop();
+        self.history.push(RouteState {
+            entry,
+            route,
+            id: ID.fetch_add(1, Ordering::SeqCst),
+            state: new_state,
+            mount: None,
+            transition: options.transition.clone(),
+            overlay: options.overlay,
+            on_result: None,
+            policy: self.route_policy(path),
+            stale: false,
+            _tasks: tasks,
+        });
+
+        self.current_transition = Some(CurrentTransition {
+            active_transition: ActiveTransition::forward(
+                options.transition.unwrap_or(transition_config),
+            )
+            .with_default_duration(self.default_duration)
+            .with_duration_scale(self.duration_scale),
+            leaving_route,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the current route with the default transition.
+    ///
+    /// Unlike [`Self::navigate`], this replaces the current history entry instead of pushing a
+    /// new one, so going back won't return to the replaced route (e.g. after a login screen).
+    pub fn replace(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
+        self.replace_transition(state, path, self.replace_transition.clone())
+    }
+
+    /// Replace the current route with the default transition, passing a payload to the route
+    /// handler. Check [`Self::navigate_with`] for more info on payloads.
+    pub fn replace_with(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        payload: impl Any + Send + Sync,
+    ) -> RouterResult {
+        self.replace_transition_with(state, path, self.replace_transition.clone(), payload)
+    }
 
+    /// Makes the shared elements of our routes and the routes of the current transition or swipe
+    /// back gesture available to [`crate::shared_element`]
+    fn update_shared_elements(&self, ui: &Ui, swipe_back_transition: Option<&ActiveTransition>) {
+        let transition = if let Some(transition) = &self.current_transition {
+            let leaving = transition
+                .leaving_route
+                .as_ref()
+                .or_else(|| self.history.iter().rev().nth(1));
+            self.history
+                .last()
+                .zip(leaving)
+                .map(|(arriving, leaving)| SharedTransition {
+                    arriving: arriving.id,
+                    leaving: leaving.id,
+                    progress: transition.active_transition.eased_progress(),
+                })
+        } else if let Some(swipe) = swipe_back_transition {
+            let mut routes = self.history.iter().rev();
+            routes
+                .next()
+                .zip(routes.next())
+                .map(|(leaving, arriving)| SharedTransition {
+                    arriving: arriving.id,
+                    leaving: leaving.id,
+                    progress: swipe.eased_progress(),
+                })
+        } else {
+            None
+        };
+
+        let mut routes: Vec<_> = self.history.iter().map(|r| r.id).collect();
+        routes.extend(
+            self.current_transition
+                .as_ref()
+                .and_then(|t| t.leaving_route.as_ref())
+                .map(|r| r.id),
+        );
+        self.scroll_positions.lock().retain_routes(&routes);
+        let mut elements = self.shared_elements.lock();
+        elements.retain_routes(&routes);
+        // Only the elements of the route that is arriving right now are moving
+        elements
+            .moving
+            .retain(|(route, _)| transition.is_some_and(|t| t.arriving == *route));
+        drop(elements);
+
+        let context = SharedElementContext {
+            elements: self.shared_elements.clone(),
+            scroll_positions: self.scroll_positions.clone(),
+            focus: self.focus.clone(),
+            transition,
+            route: self.history.last().map_or(0, |r| r.id),
+        };
+        ui.data_mut(|data| data.insert_temp(SharedElementContext::id(), context));
+    }
+
+    /// Shows the active route, or the routes of the current transition or swipe back gesture
+    fn routes_ui(&mut self, ui: &mut Ui, state: &mut State) {
+        let swipe_back_transition = self.swipe_back_progress.map(|progress| {
+            ActiveTransition::backward(self.swipe_back_transition())
+                .with_progress(progress)
+                .with_easing(|t| t)
+        });
+
+        self.update_shared_elements(ui, swipe_back_transition.as_ref());
+
+        // Labels the region of the active route for screen readers
+        let title = self.title();
+
+        let mut overlay_dim_clicked = false;
         if let Some((last, previous)) = self.history.split_last_mut() {
+            let error_ui = &self.error_ui;
             let result = if let Some(transition) = &mut self.current_transition {
+                let active_transition = &mut transition.active_transition;
+                // The dim fades in and out with the overlay
+                let overlay_dim = if active_transition.is_backward() {
+                    transition
+                        .leaving_route
+                        .as_ref()
+                        .is_some_and(|r| r.overlay)
+                        .then(|| 1.0 - active_transition.eased_progress())
+                } else {
+                    last.overlay.then(|| active_transition.eased_progress())
+                };
                 let leaving_route_state = transition.leaving_route.as_mut().or(previous.last_mut());
-                Some(transition.active_transition.show(
+                let backward = active_transition.is_backward();
+                Some(active_transition.show(
                     ui,
                     state,
-                    (last.id, |ui, state| match &mut last.route {
-                        Ok(route) => {
-                            route.ui(ui, state);
-                        }
-                        Err(err) => {
-                            (self.error_ui)(ui, state, err);
+                    (last.id, |ui: &mut Ui, state: &mut _| match overlay_dim {
+                        Some(dim) if backward => {
+                            underlay_ui(last, ui, state, error_ui, dim);
                         }
+                        _ => route_ui(last, ui, state, error_ui, title.as_deref()),
                     }),
                     leaving_route_state.map(|r| {
-                        (r.id, |ui: &mut Ui, state: &mut _| match &mut r.route {
-                            Ok(route) => {
-                                route.ui(ui, state);
-                            }
-                            Err(err) => {
-                                (self.error_ui)(ui, state, err);
+                        (r.id, |ui: &mut Ui, state: &mut _| match overlay_dim {
+                            Some(dim) if !backward => {
+                                underlay_ui(r, ui, state, error_ui, dim);
                             }
+                            _ => route_ui(r, ui, state, error_ui, None),
                         })
                     }),
                 ))
+            } else if let Some((transition, previous)) =
+                swipe_back_transition.zip(previous.last_mut())
+            {
+                let overlay_dim = last.overlay.then(|| 1.0 - transition.eased_progress());
+                transition.render(
+                    ui,
+                    state,
+                    (previous.id, |ui: &mut Ui, state: &mut _| {
+                        if let Some(dim) = overlay_dim {
+                            underlay_ui(previous, ui, state, error_ui, dim);
+                        } else {
+                            route_ui(previous, ui, state, error_ui, None);
+                        }
+                    }),
+                    Some((last.id, |ui: &mut Ui, state: &mut _| {
+                        route_ui(last, ui, state, error_ui, title.as_deref());
+                    })),
+                );
+                None
             } else {
-                ActiveTransition::show_default(ui, last.id, |ui| match &mut last.route {
-                    Ok(route) => {
-                        route.ui(ui, state);
-                    }
-                    Err(err) => {
-                        (self.error_ui)(ui, state, err);
-                    }
+                let mut dim_response = None;
+                if let Some(previous) = previous.last_mut().filter(|_| last.overlay) {
+                    ActiveTransition::show_default(ui, previous.id, |ui| {
+                        dim_response = Some(underlay_ui(previous, ui, state, error_ui, 1.0));
+                    });
+                }
+                let mut content_rect = Rect::NOTHING;
+                ActiveTransition::show_default(ui, last.id, |ui| {
+                    route_ui(last, ui, state, error_ui, title.as_deref());
+                    content_rect = ui.min_rect();
+                });
+                // Clicks on non-interactive parts of the overlay (e.g. its background) also
+                // reach the dimmed area, so only clicks outside the overlay's content dismiss it
+                overlay_dim_clicked = dim_response.is_some_and(|response| {
+                    response.clicked()
+                        && response
+                            .interact_pointer_pos()
+                            .is_some_and(|pos| !content_rect.contains(pos))
                 });
                 None
             };
@@ -306,5 +1946,431 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                 Some(ActiveTransitionResult::Continue) | None => {}
             }
         }
+
+        if overlay_dim_clicked {
+            self.back().ok();
+        }
     }
+
+    /// Open `path` in a new native window (an egui viewport), e.g. to pop out a panel in a
+    /// desktop app. The window shows a router with the routes, guards and transitions of this
+    /// router, starting at `path` and with its own history, so it can navigate on its own (e.g.
+    /// via [`crate::RouterLink`]s). Its routes get the same `State` as this router.
+    ///
+    /// The window is shown while this router is shown, as an immediate viewport. When the user
+    /// closes it, its routes are dropped (cancelling their tasks). If the backend doesn't support
+    /// multiple viewports, an [`egui::Window`] is shown instead.
+    ///
+    /// Mounted routers are shared with this router, so only open paths of a mounted router in a
+    /// viewport if it isn't shown here at the same time.
+    ///
+    /// Returns the id of the viewport, e.g. to close it via [`Context::send_viewport_cmd_to`].
+    pub fn open_in_viewport(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        builder: ViewportBuilder,
+    ) -> RouterResult<ViewportId> {
+        let mut router = self.viewport_router();
+        self.swap_routes(&mut router);
+        let result = router.navigate_transition(state, path, TransitionConfig::none());
+        self.swap_routes(&mut router);
+        result?;
+        router.current_transition = None;
+
+        let id =
+            ViewportId::from_hash_of(("egui_router_viewport", ID.fetch_add(1, Ordering::SeqCst)));
+        self.viewports.push(ViewportRouter {
+            id,
+            builder,
+            router,
+        });
+        Ok(id)
+    }
+
+    /// An empty router with the settings of this router, for [`Self::open_in_viewport`].
+    /// It uses our routes via [`Self::swap_routes`].
+    fn viewport_router(&self) -> EguiRouter<State, MemoryHistory> {
+        EguiRouter {
+            router: RouteTable::new(),
+            history: Vec::new(),
+            forward_history: Vec::new(),
+            history_kind: MemoryHistory::default(),
+            current_transition: None,
+            forward_transition: self.forward_transition.clone(),
+            backward_transition: self.backward_transition.clone(),
+            replace_transition: self.replace_transition.clone(),
+            default_duration: self.default_duration,
+            duration_scale: self.duration_scale,
+            swipe_back: self.swipe_back,
+            swipe_back_progress: None,
+            navigation_input: self.navigation_input.clone(),
+            max_history: self.max_history,
+            dedup_navigation: self.dedup_navigation,
+            error_ui: self.error_ui.clone(),
+            fallback: None,
+            guards: Vec::new(),
+            titles: RouteTable::new(),
+            on_title_change: None,
+            last_title: None,
+            on_navigate: None,
+            last_navigation: NavigationSnapshot::default(),
+            progress: ProgressTracker::default(),
+            metadata: MetadataRegistry::new(Vec::new()),
+            default_route_policy: self.default_route_policy,
+            route_policies: RouteTable::new(),
+            patterns: RouteTable::new(),
+            shared_elements: Arc::default(),
+            scroll_positions: Arc::default(),
+            focus_on_navigate: self.focus_on_navigate,
+            focus: Arc::default(),
+            #[cfg(feature = "accesskit")]
+            announcer: accessibility::RouteAnnouncer::default(),
+            preloaded: None,
+            inbox: UiInbox::new(),
+            viewports: Vec::new(),
+        }
+    }
+
+    /// Lends our routes to the router of a viewport while it navigates or is shown. Call again
+    /// to take them back.
+    fn swap_routes<H2>(&mut self, router: &mut EguiRouter<State, H2>) {
+        std::mem::swap(&mut self.router, &mut router.router);
+        std::mem::swap(&mut self.fallback, &mut router.fallback);
+        std::mem::swap(&mut self.guards, &mut router.guards);
+        std::mem::swap(&mut self.titles, &mut router.titles);
+        std::mem::swap(&mut self.metadata, &mut router.metadata);
+        std::mem::swap(&mut self.route_policies, &mut router.route_policies);
+        std::mem::swap(&mut self.patterns, &mut router.patterns);
+    }
+
+    /// Shows the routers opened via [`Self::open_in_viewport`], dropping the closed ones
+    fn viewports_ui(&mut self, ctx: &Context, state: &mut State) {
+        let mut viewports = std::mem::take(&mut self.viewports);
+        viewports.retain_mut(|viewport| {
+            self.swap_routes(&mut viewport.router);
+            let open = viewport.ui(ctx, state);
+            self.swap_routes(&mut viewport.router);
+            open
+        });
+        self.viewports = viewports;
+    }
+
+    /// Calls `on_title_change` if the title of the active route changed
+    fn notify_title_change(&mut self, ctx: &Context) {
+        if self.on_title_change.is_some() {
+            let title = self.title();
+            if title != self.last_title {
+                if let (Some(on_title_change), Some(title)) = (&mut self.on_title_change, &title) {
+                    on_title_change(ctx, title);
+                }
+                self.last_title = title;
+            }
+        }
+    }
+
+    /// The state of the ongoing navigation, or `None` if there is none. A navigation is ongoing
+    /// while its transition runs or the new route is loading (see [`crate::Route::loading`]),
+    /// including navigations of the active mounted router. Use this to show a progress bar, e.g.
+    /// at the top of the window like a browser. It's updated when the router is shown.
+    ///
+    /// ```
+    /// # use egui_router::EguiRouter;
+    /// fn progress_bar(ui: &mut egui::Ui, router: &EguiRouter<()>) {
+    ///     if let Some(progress) = router.navigation_progress() {
+    ///         ui.add(egui::ProgressBar::new(progress.fraction()).desired_height(2.0));
+    ///     }
+    /// }
+    /// ```
+    pub fn navigation_progress(&self) -> Option<NavigationProgress> {
+        self.progress.progress
+    }
+
+    /// Updates the progress of the ongoing navigation, see [`Self::navigation_progress`]
+    fn update_navigation_progress(&mut self, ctx: &Context) {
+        let active = self.history.last();
+        let mount_progress = active
+            .and_then(|route| route.mount.as_ref())
+            .and_then(|mount| mount.router.try_borrow().ok()?.navigation_progress());
+        let transition = self
+            .current_transition
+            .as_ref()
+            .map(|transition| transition.active_transition.progress())
+            .or(mount_progress.and_then(|progress| progress.transition));
+        let loading = active
+            .and_then(|route| route.route.as_ref().ok())
+            .is_some_and(|route| route.loading())
+            || mount_progress.is_some_and(|progress| progress.loading);
+        self.progress
+            .update(ctx.input(|input| input.time), transition, loading);
+    }
+
+    /// Moves the focus into the new route if the active route changed, see
+    /// [`RouterBuilder::focus_on_navigate`]
+    fn update_focus(&self, ctx: &Context) {
+        if self.focus_on_navigate {
+            let routes: Vec<_> = self.history.iter().map(|route| route.id).collect();
+            self.focus.lock().update(ctx, &routes);
+        }
+    }
+
+    /// Announces the title of the new route (or its path if it has none) to screen readers
+    #[cfg(feature = "accesskit")]
+    fn announce_navigation(&mut self, ui: &Ui) {
+        let mut announcer = std::mem::take(&mut self.announcer);
+        announcer.ui(ui, self.history.last().map(|route| route.id), || {
+            self.title()
+                .or_else(|| self.active_route().map(ToOwned::to_owned))
+                .unwrap_or_default()
+        });
+        self.announcer = announcer;
+    }
+
+    /// Show the state of the router, e.g. to find out why going back skipped a screen: the
+    /// history (the active route last), the forward history, the running transition and the
+    /// registered routes. Mounted routers are shown below the route that shows them.
+    ///
+    /// The panel is collapsed by default and only shown in debug builds, so it can stay in
+    /// the app.
+    ///
+    /// ```
+    /// # use egui_router::EguiRouter;
+    /// # fn show(router: &mut EguiRouter<()>, ctx: &egui::Context) {
+    /// egui::Window::new("Router").show(ctx, |ui| {
+    ///     router.debug_ui(ui);
+    /// });
+    /// # }
+    /// ```
+    pub fn debug_ui(&self, ui: &mut Ui) {
+        if cfg!(debug_assertions) {
+            egui::CollapsingHeader::new("Router")
+                .id_salt(ui.id().with("egui_router_debug"))
+                .show(ui, |ui| self.debug_ui_content(ui));
+        }
+    }
+
+    fn debug_ui_content(&self, ui: &mut Ui) {
+        ui.strong(format!("History ({})", self.history.len()));
+        for (index, route) in self.history.iter().enumerate() {
+            let mut flags = Vec::new();
+            if route.overlay {
+                flags.push("overlay");
+            }
+            if route.policy == RoutePolicy::Rebuild {
+                flags.push("rebuild");
+            }
+            if route.stale {
+                flags.push("stale");
+            }
+            if route.route.is_err() {
+                flags.push("error");
+            }
+            let text = if flags.is_empty() {
+                format!("{index}: {}", route.entry.url())
+            } else {
+                format!("{index}: {} ({})", route.entry.url(), flags.join(", "))
+            };
+            if index + 1 == self.history.len() {
+                ui.strong(text);
+            } else {
+                ui.label(text);
+            }
+            if let Some(router) = route
+                .mount
+                .as_ref()
+                .and_then(|mount| mount.router.try_borrow().ok())
+            {
+                ui.indent(route.id, |ui| router.debug_ui_content(ui));
+            }
+        }
+
+        if !self.forward_history.is_empty() {
+            ui.strong(format!("Forward history ({})", self.forward_history.len()));
+            for url in self.forward_history.iter().rev() {
+                ui.label(url);
+            }
+        }
+
+        ui.strong("Transition");
+        if let Some(transition) = &self.current_transition {
+            let active = &transition.active_transition;
+            ui.label(format!(
+                "{} {:.0}% of {:.2}s{}",
+                if active.is_backward() {
+                    "Backward"
+                } else {
+                    "Forward"
+                },
+                active.progress() * 100.0,
+                active.duration(ui),
+                transition
+                    .leaving_route
+                    .as_ref()
+                    .map(|route| format!(", leaving {}", route.entry.url()))
+                    .unwrap_or_default(),
+            ));
+        } else if let Some(progress) = self.swipe_back_progress {
+            ui.label(format!("Swiping back {:.0}%", progress * 100.0));
+        } else {
+            ui.label("None");
+        }
+        ui.label(format!(
+            "Default duration: {}, scale: {}",
+            self.default_duration
+                .map_or_else(|| "animation time".to_owned(), |d| format!("{d}s")),
+            self.duration_scale,
+        ));
+
+        ui.strong("Routes");
+        for (route, kind) in self.router.iter() {
+            match kind {
+                RouteKind::Route(..) => ui.label(route),
+                RouteKind::Redirect(target) => ui.label(format!("{route} -> {target}")),
+                // Mounts have a second entry for the paths below the prefix
+                RouteKind::Mount(mount) if mount.prefix == route => {
+                    ui.label(format!("{route} (mount)"))
+                }
+                RouteKind::Mount(_) => continue,
+            };
+        }
+        if self.fallback.is_some() {
+            ui.label("Fallback");
+        }
+        if !self.guards.is_empty() {
+            ui.label(format!("Guards: {}", self.guards.len()));
+        }
+    }
+
+    /// Render the router
+    pub fn ui(&mut self, ui: &mut Ui, state: &mut State) {
+        for command in self.inbox.read(ui) {
+            match command {
+                RouterCommand::Navigate(path) => {
+                    self.navigate(state, path).ok();
+                }
+                RouterCommand::Replace(path) => {
+                    self.replace(state, path).ok();
+                }
+                RouterCommand::Preload(path) => {
+                    self.preload(state, path).ok();
+                }
+                RouterCommand::Back => {
+                    self.back().ok();
+                }
+            }
+        }
+
+        self.handle_history_events(ui.ctx(), state);
+
+        // The active mounted router might have navigated on its own, e.g. via a RouterLink
+        if let Some(last) = self.history.last_mut() {
+            if let Some(mount) = &last.mount {
+                let entry = mount.entry();
+                if entry != last.entry {
+                    self.forward_history.clear();
+                    last.entry = entry;
+                }
+            }
+        }
+
+        match self
+            .navigation_input
+            .as_ref()
+            .and_then(|input| input.read(ui))
+        {
+            Some(true) => {
+                self.back().ok();
+            }
+            Some(false) => {
+                self.forward(state).ok();
+            }
+            None => {}
+        }
+
+        self.rebuild_active_route(state);
+
+        // Make the active route available to any RouterLink rendered by our routes.
+        // The previous context is restored afterward, so links work with nested routers.
+        let previous_link_context = RouterLinkContext::get(ui);
+        let previous_shared_element_context = SharedElementContext::get(ui);
+        let link_context = RouterLinkContext {
+            active_route: self.active_route().map(ToOwned::to_owned),
+            handle: self.handle(),
+        };
+        ui.data_mut(|data| data.insert_temp(RouterLinkContext::id(), link_context));
+
+        self.routes_ui(ui, state);
+
+        // Checked after the routes are shown, so the edge is on top of their widgets
+        self.swipe_back_ui(ui);
+
+        // Catches navigations that happened in the ui, e.g. via the browser history, a swipe
+        // back gesture or a mounted router
+        self.notify_navigation();
+
+        self.notify_title_change(ui.ctx());
+
+        self.update_focus(ui.ctx());
+        self.update_navigation_progress(ui.ctx());
+
+        #[cfg(feature = "accesskit")]
+        self.announce_navigation(ui);
+
+        ui.data_mut(|data| {
+            if let Some(previous_link_context) = previous_link_context {
+                data.insert_temp(RouterLinkContext::id(), previous_link_context);
+            } else {
+                data.remove::<RouterLinkContext>(RouterLinkContext::id());
+            }
+            if let Some(previous_shared_element_context) = previous_shared_element_context {
+                data.insert_temp(SharedElementContext::id(), previous_shared_element_context);
+            } else {
+                data.remove::<SharedElementContext>(SharedElementContext::id());
+            }
+        });
+
+        self.viewports_ui(ui.ctx(), state);
+    }
+}
+
+/// Shows a route. `title` labels its region for screen readers (only set for the active route).
+fn route_ui<State>(
+    route_state: &mut RouteState<State>,
+    ui: &mut Ui,
+    state: &mut State,
+    error_ui: &ErrorUi<State>,
+    title: Option<&str>,
+) {
+    SharedElementContext::set_route(ui, route_state.id);
+    let content = |ui: &mut Ui| match &mut route_state.route {
+        Ok(route) => {
+            focus::focus_anchor(ui, title);
+            route.ui(ui, state);
+        }
+        Err(err) => {
+            error_ui(ui, state, err);
+        }
+    };
+    accessibility::route_region(ui, title, content);
+}
+
+/// Shows the route underneath an overlay route, dimmed by `dim` (0.0 - 1.0) and blocking
+/// interaction. Returns the response of the dimmed area.
+fn underlay_ui<State>(
+    route_state: &mut RouteState<State>,
+    ui: &mut Ui,
+    state: &mut State,
+    error_ui: &ErrorUi<State>,
+    dim: f32,
+) -> Response {
+    route_ui(route_state, ui, state, error_ui, None);
+
+    let rect = ui.max_rect();
+    ui.painter()
+        .rect_filled(rect, 0.0, OVERLAY_DIM_COLOR.gamma_multiply(dim));
+    ui.interact(
+        rect,
+        Id::new("egui_router_overlay").with(route_state.id),
+        Sense::click(),
+    )
 }
@@ -1,7 +1,19 @@
-use crate::handler::MakeHandler;
-use crate::history::History;
-use crate::route_kind::RouteKind;
-use crate::{EguiRouter, TransitionConfig};
+use crate::guard::Guard;
+use crate::handler::{Handler, MakeHandler};
+use crate::history::{History, MemoryHistory};
+use crate::metadata::RouteMetadata;
+use crate::mount::Mount;
+use crate::navigation_event::NavigateCallback;
+use crate::route_kind::{RouteKind, RouteOptions, RoutePolicy};
+use crate::route_table::RouteTable;
+use crate::title::{set_window_title, TitleCallback};
+use crate::{
+    EguiRouter, GuardAction, NavigationEvent, NavigationInput, Request, RouteTitle,
+    TransitionConfig,
+};
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::Arc;
 
 pub(crate) type ErrorUi<State> =
@@ -10,7 +22,7 @@ pub(crate) type LoadingUi<State> = Arc<Box<dyn Fn(&mut egui::Ui, &State) + Send
 
 /// Builder to create a [`EguiRouter`]
 pub struct RouterBuilder<State, H> {
-    pub(crate) router: matchit::Router<RouteKind<State>>,
+    pub(crate) router: RouteTable<RouteKind<State>>,
     pub(crate) default_route: Option<String>,
 
     pub(crate) forward_transition: TransitionConfig,
@@ -18,11 +30,29 @@ pub struct RouterBuilder<State, H> {
     pub(crate) replace_transition: TransitionConfig,
 
     pub(crate) default_duration: Option<f32>,
+    pub(crate) duration_scale: f32,
+    pub(crate) swipe_back: bool,
+    pub(crate) navigation_input: Option<NavigationInput>,
+    pub(crate) max_history: Option<usize>,
+    pub(crate) dedup_navigation: bool,
+    pub(crate) focus_on_navigate: bool,
 
     pub(crate) history_kind: Option<H>,
 
     pub(crate) error_ui: ErrorUi<State>,
     pub(crate) loading_ui: LoadingUi<State>,
+
+    pub(crate) fallback: Option<Handler<State>>,
+    pub(crate) guards: Vec<Guard<State>>,
+
+    pub(crate) titles: RouteTable<RouteTitle>,
+    pub(crate) on_title_change: Option<TitleCallback>,
+    pub(crate) on_navigate: Option<NavigateCallback>,
+    pub(crate) metadata: Vec<(String, RouteMetadata)>,
+    pub(crate) default_route_policy: RoutePolicy,
+    pub(crate) route_policies: RouteTable<RoutePolicy>,
+    /// The route each path is handled by, see [`EguiRouter::matched_route`]
+    pub(crate) patterns: RouteTable<String>,
 }
 
 impl<State: 'static, H: History + Default> Default for RouterBuilder<State, H> {
@@ -35,12 +65,18 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     /// Create a new router builder
     pub fn new() -> Self {
         Self {
-            router: matchit::Router::new(),
+            router: RouteTable::new(),
             default_route: None,
             forward_transition: TransitionConfig::default(),
             backward_transition: TransitionConfig::default(),
             replace_transition: TransitionConfig::fade(),
             default_duration: None,
+            duration_scale: 1.0,
+            swipe_back: false,
+            navigation_input: None,
+            max_history: None,
+            dedup_navigation: false,
+            focus_on_navigate: false,
             history_kind: None,
             error_ui: Arc::new(Box::new(|ui, _, err| {
                 ui.label(format!("Error: {err}"));
@@ -48,6 +84,15 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
             loading_ui: Arc::new(Box::new(|ui, _| {
                 ui.spinner();
             })),
+            fallback: None,
+            guards: Vec::new(),
+            titles: RouteTable::new(),
+            on_title_change: None,
+            on_navigate: None,
+            metadata: Vec::new(),
+            default_route_policy: RoutePolicy::default(),
+            route_policies: RouteTable::new(),
+            patterns: RouteTable::new(),
         }
     }
 
@@ -82,6 +127,61 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Scale the duration of all transitions, e.g. `0.5` to make navigation animations twice as fast.
+    /// If `egui_animation::reduce_motion` is set (requires the `animation` feature), transitions
+    /// are skipped regardless of this.
+    pub fn duration_scale(mut self, scale: f32) -> Self {
+        self.duration_scale = scale;
+        self
+    }
+
+    /// Go back by dragging from the left edge of the router, like the iOS navigation gesture.
+    /// The backward transition follows the pointer and is completed or cancelled on release,
+    /// depending on how far (or how fast) the route was dragged.
+    /// Works with touch and mouse input. The default is false.
+    pub fn swipe_back(mut self, swipe_back: bool) -> Self {
+        self.swipe_back = swipe_back;
+        self
+    }
+
+    /// Go back and forward via keyboard shortcuts and the extra mouse buttons, e.g.
+    /// `.navigation_input(NavigationInput::default())` for Escape / Alt+Left to go back, Alt+Right
+    /// to go forward and the mouse back / forward buttons. Disabled by default.
+    ///
+    /// The input is read globally, so only enable it for one router (mounted routers are handled
+    /// by their parent).
+    pub fn navigation_input(mut self, input: NavigationInput) -> Self {
+        self.navigation_input = Some(input);
+        self
+    }
+
+    /// Limit the number of routes kept in the history. When a navigation exceeds the limit, the
+    /// oldest routes are dropped, so going back stops at the oldest remaining route.
+    /// Useful for long-running apps (e.g. kiosks), where the history would otherwise grow without
+    /// bound. By default, the history is unlimited.
+    pub fn max_history(mut self, max: usize) -> Self {
+        self.max_history = Some(max.max(1));
+        self
+    }
+
+    /// Ignore navigations to the active route (same path and query), e.g. when a link is
+    /// double-clicked, so no identical history entry is pushed and the transition isn't replayed.
+    /// Replacing the active route is not affected. The default is false.
+    pub fn dedup_navigation(mut self, dedup: bool) -> Self {
+        self.dedup_navigation = dedup;
+        self
+    }
+
+    /// Move the keyboard focus into the new route when navigating, so keyboard and screen reader
+    /// users don't stay on a widget of the old route. The focus moves to the widget marked via
+    /// [`crate::route_autofocus`], or to the start of the route, so pressing tab focuses its
+    /// first focusable widget. Going back focuses the widget that was focused when the route was
+    /// left. The default is false.
+    pub fn focus_on_navigate(mut self, focus_on_navigate: bool) -> Self {
+        self.focus_on_navigate = focus_on_navigate;
+        self
+    }
+
     /// Set the default route (when using [`history::BrowserHistory`], window.location.pathname will be used instead)
     pub fn default_path(mut self, route: impl Into<String>) -> Self {
         self.default_route = Some(route.into());
@@ -95,7 +195,8 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     }
 
     /// Set the error UI
-    /// Call this *before* you call `.async_route()`, otherwise the error UI will not be used in async routes.
+    /// Call this *before* you call `.async_route()` or `.loader_route()`, otherwise the error UI
+    /// will not be used in these routes.
     pub fn error_ui(
         mut self,
         f: impl Fn(&mut egui::Ui, &State, &crate::handler::HandlerError) + 'static + Send + Sync,
@@ -104,16 +205,32 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
-    /// Set the loading UI
-    /// Call this *before* you call `.async_route()`, otherwise the loading UI will not be used in async routes.
+    /// Set the loading UI, e.g. a spinner or a skeleton of the page
+    /// Call this *before* you call `.async_route()` or `.loader_route()`, otherwise the loading
+    /// UI will not be used in these routes.
     pub fn loading_ui(mut self, f: impl Fn(&mut egui::Ui, &State) + 'static + Send + Sync) -> Self {
         self.loading_ui = Arc::new(Box::new(f));
         self
     }
 
-    /// Add a route. Check the [matchit] documentation for information about the route syntax.
+    /// Add a route. Check the [matchit] documentation for details about the route syntax.
     /// The handler will be called with [`crate::Request`] and should return a [Route].
     ///
+    /// A route consists of segments separated by `/`:
+    /// - static segments like `/about` match themselves
+    /// - params like `/post/{id}` match a single segment, available via [`crate::Request::params`]
+    /// - a wildcard (catch-all) like `/files/{*path}` matches the rest of the path including
+    ///   slashes (at least one character), e.g. `path` is `docs/intro.md` for
+    ///   `/files/docs/intro.md`. It has to be the last segment of the route
+    ///
+    /// The order the routes are added in doesn't matter, at each segment the most specific match
+    /// wins: static segments take precedence over params and wildcards, params over wildcards.
+    /// If the rest of the path doesn't match, a less specific route at a previous segment is
+    /// tried, e.g. with `/{*rest}` and `/files/{*path}`, `/files/` is handled by `/{*rest}`.
+    /// Routes that would be ambiguous, like `/{id}` and `/{name}` or `/files/{name}` and
+    /// `/files/{*path}`, panic when they are added. Use [`EguiRouter::matched_route`] to find out
+    /// which route handles a path.
+    ///
     /// # Example
     /// ```rust
     /// # use egui::Ui;
@@ -134,22 +251,122 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     ///
     /// let router: EguiRouter<()> = EguiRouter::builder()
     ///     .route("/", my_handler)
-    ///     .route("/:post", my_fallible_handler)
+    ///     .route("/{post}", my_fallible_handler)
     ///     .build(&mut ());
     pub fn route<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
+        self,
+        route: &str,
+        handler: Han,
+    ) -> Self {
+        self.insert_route(route, handler, RouteOptions::default())
+    }
+
+    /// Add a route that always uses `transition`, regardless of the transition the caller
+    /// navigates with. Going back from this route uses the same transition, reversed.
+    /// Useful for e.g. modals that should always fade in, see [`Self::route`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, Request, Route, TransitionConfig};
+    /// fn modal(_req: Request) -> impl Route {
+    ///     |ui: &mut Ui, _: &mut ()| {
+    ///         ui.label("Modal");
+    ///     }
+    /// }
+    ///
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", |_req: Request| |ui: &mut Ui, _: &mut ()| { ui.label("Home"); })
+    ///     .route_with_transition("/modal", modal, TransitionConfig::fade())
+    ///     .build(&mut ());
+    /// ```
+    pub fn route_with_transition<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
+        self,
+        route: &str,
+        handler: Han,
+        transition: TransitionConfig,
+    ) -> Self {
+        self.insert_route(
+            route,
+            handler,
+            RouteOptions {
+                transition: Some(transition),
+                overlay: false,
+            },
+        )
+    }
+
+    /// Add a route that is shown on top of the previous route, e.g. a dialog or bottom sheet.
+    /// The previous route stays visible (dimmed and non-interactive) underneath, clicking the
+    /// dimmed area outside of the content of the overlay goes back.
+    ///
+    /// Only the overlay is animated, using the in transition of `transition` when it is opened
+    /// and the reverse when going back. The route underneath stays in place.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui::{Ui, Vec2};
+    /// # use egui_router::{EguiRouter, Request, Route, TransitionConfig};
+    /// # use egui_router::transition::{NoTransition, SlideTransition};
+    /// fn bottom_sheet(_req: Request) -> impl Route {
+    ///     |ui: &mut Ui, _: &mut ()| {
+    ///         ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+    ///             ui.label("Bottom sheet");
+    ///         });
+    ///     }
+    /// }
+    ///
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", |_req: Request| |ui: &mut Ui, _: &mut ()| { ui.label("Home"); })
+    ///     .overlay_route(
+    ///         "/sheet",
+    ///         bottom_sheet,
+    ///         TransitionConfig::new(SlideTransition::new(Vec2::Y), NoTransition),
+    ///     )
+    ///     .build(&mut ());
+    /// ```
+    pub fn overlay_route<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
+        self,
+        route: &str,
+        handler: Han,
+        transition: TransitionConfig,
+    ) -> Self {
+        self.insert_route(
+            route,
+            handler,
+            RouteOptions {
+                // The route underneath stays in place
+                transition: Some(TransitionConfig {
+                    out: crate::transition::NoTransition.into(),
+                    ..transition
+                }),
+                overlay: true,
+            },
+        )
+    }
+
+    fn insert_route<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
         mut self,
         route: &str,
         mut handler: Han,
+        options: RouteOptions,
     ) -> Self {
-        self.router
-            .insert(
-                route,
-                RouteKind::Route(Box::new(move |req| handler.handle(req))),
-            )
-            .unwrap();
+        self.insert_kind(
+            route,
+            route,
+            RouteKind::Route(Box::new(move |req| handler.handle(req)), options),
+        );
         self
     }
 
+    /// Adds a route to the router, `pattern` is reported by [`EguiRouter::matched_route`]
+    fn insert_kind(&mut self, route: &str, pattern: &str, kind: RouteKind<State>) {
+        if let Err(err) = self.router.insert(route, kind) {
+            panic!("Can't add the route {route:?}: {err}");
+        }
+        self.patterns.insert(route, pattern.to_owned()).ok();
+    }
+
     /// Add an async route. Check the [matchit] documentation for information about the route syntax.
     /// The handler will be called with [`crate::OwnedRequest`] and should return a [Route].
     ///
@@ -175,7 +392,7 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     /// # #[cfg(feature = "async")]
     /// let router: EguiRouter<()> = EguiRouter::builder()
     ///    .async_route("/", my_handler)
-    ///    .async_route("/:post", my_fallible_handler)
+    ///    .async_route("/{post}", my_fallible_handler)
     ///    .build(&mut ());
     #[cfg(feature = "async")]
     pub fn async_route<HandlerArgs, Han>(mut self, route: &str, handler: Han) -> Self
@@ -185,13 +402,15 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     {
         let loading_ui = self.loading_ui.clone();
         let error_ui = self.error_ui.clone();
-        self.router
-            .insert(
-                route,
-                RouteKind::Route(Box::new(move |req| {
+        self.insert_kind(
+            route,
+            route,
+            RouteKind::Route(
+                Box::new(move |req| {
                     let loading_ui = loading_ui.clone();
                     let error_ui = error_ui.clone();
 
+                    let handle = req.handle.clone();
                     let owned = crate::OwnedRequest {
                         params: req
                             .params
@@ -204,6 +423,9 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
                             .map(|(k, v)| (k.into_owned(), v.into_owned()))
                             .collect(),
                         state: req.state.clone(),
+                        payload: req.payload,
+                        metadata: req.metadata,
+                        handle: req.handle,
                     };
 
                     let handler = handler.clone();
@@ -217,20 +439,369 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
                         })
                         .loading_ui(move |ui| loading_ui(ui, &state_clone))
                         .error_ui(move |ui, err, _| error_ui(ui, &state_clone2, err)),
+                        handle,
+                        redirected: false,
+                    };
+
+                    Ok(crate::RouteOrRedirect::Route(Box::new(route)))
+                }),
+                RouteOptions::default(),
+            ),
+        );
+        self
+    }
+
+    /// Add a route that loads its data before it's shown. When the route is opened, `loader` is
+    /// called with the request and the returned future is spawned. Once it resolves, `view` is
+    /// called with the data every frame.
+    ///
+    /// Until then, the [`Self::loading_ui`] is shown (e.g. during the forward transition). If the
+    /// loader fails, the [`Self::error_ui`] is shown. The loader runs again whenever the route is
+    /// opened or rebuilt (see [`RoutePolicy::Rebuild`]).
+    ///
+    /// ```rust
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, HandlerError, HandlerResult, Request};
+    /// async fn load_post(id: String) -> HandlerResult<String> {
+    ///     Ok(format!("Post {id}"))
+    /// }
+    ///
+    /// # #[cfg(feature = "async")]
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .loading_ui(|ui, _| {
+    ///         ui.spinner();
+    ///     })
+    ///     .loader_route(
+    ///         "/post/{id}",
+    ///         |req: &mut Request| {
+    ///             let id = req.params.get("id").unwrap_or_default().to_owned();
+    ///             load_post(id)
+    ///         },
+    ///         |ui: &mut Ui, post: &mut String, _state: &mut ()| {
+    ///             ui.label(post.as_str());
+    ///         },
+    ///     )
+    ///     .build(&mut ());
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn loader_route<T, Fut>(
+        mut self,
+        route: &str,
+        loader: impl Fn(&mut Request<State>) -> Fut + 'static,
+        view: impl Fn(&mut egui::Ui, &mut T, &mut State) + 'static,
+    ) -> Self
+    where
+        T: hello_egui_utils::MaybeSend + hello_egui_utils::MaybeSync + 'static,
+        Fut: std::future::Future<Output = crate::HandlerResult<T>>
+            + hello_egui_utils::MaybeSend
+            + hello_egui_utils::MaybeSync
+            + 'static,
+    {
+        let loading_ui = self.loading_ui.clone();
+        let error_ui = self.error_ui.clone();
+        let view: crate::loader_route::ViewFn<State, T> = Rc::new(view);
+        self.insert_kind(
+            route,
+            route,
+            RouteKind::Route(
+                Box::new(move |mut req| {
+                    let future = loader(&mut req);
+                    let route = crate::loader_route::LoaderRoute {
+                        suspense: egui_suspense::EguiSuspense::single_try_async(future)
+                            .no_loading_ui()
+                            .error_ui(|_, _, _| {}),
+                        view: view.clone(),
+                        loading_ui: loading_ui.clone(),
+                        error_ui: error_ui.clone(),
                     };
+                    Ok(crate::RouteOrRedirect::Route(Box::new(route)))
+                }),
+                RouteOptions::default(),
+            ),
+        );
+        self
+    }
+
+    /// Set a fallback handler that is called when navigating to a path that matches no route,
+    /// e.g. to show a "not found" page. The handler gets the query params but no path params.
+    ///
+    /// Without a fallback, navigating to an unknown path returns [`crate::RouterError::NotFound`]
+    /// and the current route stays active.
+    pub fn fallback<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
+        mut self,
+        mut handler: Han,
+    ) -> Self {
+        self.fallback = Some(Box::new(move |req| handler.handle(req)));
+        self
+    }
 
-                    Ok(Box::new(route))
-                })),
-            )
-            .unwrap();
+    /// Add a guard that runs before the handler whenever a route is opened, e.g. to centralize
+    /// checking whether the user is logged in. The guard gets the path (without the query) and
+    /// the request, and can allow, cancel or redirect the navigation (see [`GuardAction`]).
+    ///
+    /// Guards run in the order they were added, until one cancels or redirects.
+    /// They run before [`Self::route_redirect`]s are applied.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui_router::{EguiRouter, GuardAction, Request, Route};
+    /// struct AppState {
+    ///     logged_in: bool,
+    /// }
+    ///
+    /// fn page(_req: Request<AppState>) -> impl Route<AppState> {
+    ///     |ui: &mut egui::Ui, _: &mut AppState| {
+    ///         ui.label("Hello!");
+    ///     }
+    /// }
+    ///
+    /// let mut state = AppState { logged_in: false };
+    /// let router: EguiRouter<AppState> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/login", page)
+    ///     .guard(|path, req| {
+    ///         if req.state.logged_in || path == "/login" {
+    ///             GuardAction::Allow
+    ///         } else {
+    ///             GuardAction::Redirect("/login".to_string())
+    ///         }
+    ///     })
+    ///     .default_path("/")
+    ///     .build(&mut state);
+    ///
+    /// assert_eq!(router.active_route(), Some("/login"));
+    /// ```
+    pub fn guard(
+        mut self,
+        guard: impl FnMut(&str, &mut Request<State>) -> GuardAction + 'static,
+    ) -> Self {
+        self.guards.push(Box::new(guard));
         self
     }
 
     /// Add a redirect route. Whenever this route matches, it'll redirect to the route you specified.
     pub fn route_redirect(mut self, route: &str, redirect: impl Into<String>) -> Self {
-        self.router
-            .insert(route, RouteKind::Redirect(redirect.into()))
-            .unwrap();
+        self.insert_kind(route, route, RouteKind::Redirect(redirect.into()));
+        self
+    }
+
+    /// Mount a child router under a path prefix, so a section of the app can have its own route
+    /// table and transitions, while this router shows the outer shell.
+    ///
+    /// Navigating to `{prefix}` or `{prefix}/...` opens the child router (with this router's
+    /// transition) at the remaining path, e.g. `/settings/general` opens `/general` in the child.
+    /// While the child router is active, navigating to other paths below the prefix and going
+    /// back only navigate the child router, using its transitions.
+    /// [`crate::RouterLink`]s shown by the child router's routes navigate the child router,
+    /// so their paths are relative to the prefix.
+    ///
+    /// A prefix of `/` mounts the child router at the root, so it handles all paths that have
+    /// no route in this router.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut egui::Ui, _: &mut ()| {
+    ///         ui.label("Hello!");
+    ///     }
+    /// }
+    ///
+    /// let settings = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/general", page)
+    ///     .build(&mut ());
+    ///
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .mount("/settings", settings)
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// router.navigate(&mut (), "/settings/general").unwrap();
+    /// assert_eq!(router.active_route(), Some("/settings/general"));
+    /// ```
+    pub fn mount(mut self, prefix: &str, router: EguiRouter<State, MemoryHistory>) -> Self {
+        let base = prefix.trim_end_matches('/');
+        // Mounting at the root gives the child router all paths without a route of their own
+        let prefix = if base.is_empty() { "/" } else { base };
+        let mount = Rc::new(Mount {
+            prefix: prefix.to_owned(),
+            router: RefCell::new(router),
+        });
+        self.insert_kind(prefix, prefix, RouteKind::Mount(mount.clone()));
+        self.insert_kind(
+            &format!("{base}/{{*egui_router_mount_path}}"),
+            prefix,
+            RouteKind::Mount(mount),
+        );
+        self
+    }
+
+    /// Set the title of a route, available via [`EguiRouter::title`] and
+    /// [`EguiRouter::breadcrumbs`]. The route uses the same syntax as in [`Self::route`].
+    ///
+    /// If a mounted router is active and its path has no title here, the title of the mounted
+    /// router's active route is used.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui_router::{EguiRouter, Request, Route, RouteTitle};
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut egui::Ui, _: &mut ()| {
+    ///         ui.label("Hello!");
+    ///     }
+    /// }
+    ///
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/post/{id}", page)
+    ///     .title("/", "Home")
+    ///     .title(
+    ///         "/post/{id}",
+    ///         RouteTitle::new(|entry| format!("Post {}", entry.params["id"])),
+    ///     )
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// router.navigate(&mut (), "/post/1").unwrap();
+    /// assert_eq!(router.title().as_deref(), Some("Post 1"));
+    /// ```
+    pub fn title(mut self, route: &str, title: impl Into<RouteTitle>) -> Self {
+        self.titles.insert(route, title.into()).unwrap();
+        self
+    }
+
+    /// Attach typed metadata to a route, e.g. an icon, a required permission or an analytics
+    /// name. A route can have one value of each type, setting it again replaces the value.
+    ///
+    /// The metadata is available via [`EguiRouter::metadata_for`] and [`Request::metadata`]
+    /// (also in guards, e.g. to check a required permission). Use
+    /// [`EguiRouter::routes_with_metadata`] to generate a sidebar or command palette from the
+    /// routes. The `route` uses the same syntax as [`Self::route`].
+    ///
+    /// ```
+    /// # use egui_router::{EguiRouter, Request, Route};
+    /// struct NavItem {
+    ///     label: &'static str,
+    /// }
+    ///
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut egui::Ui, _: &mut ()| {}
+    /// }
+    ///
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/post/{id}", page)
+    ///     .route("/settings", page)
+    ///     .metadata("/", NavItem { label: "Home" })
+    ///     .metadata("/settings", NavItem { label: "Settings" })
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// let labels: Vec<_> = router
+    ///     .routes_with_metadata::<NavItem>()
+    ///     .map(|(route, item)| (route, item.label))
+    ///     .collect();
+    /// assert_eq!(labels, [("/", "Home"), ("/settings", "Settings")]);
+    /// assert!(router.metadata_for::<NavItem>("/post/1").is_none());
+    /// ```
+    pub fn metadata(mut self, route: &str, value: impl Any + Send + Sync) -> Self {
+        if let Some((_, metadata)) = self.metadata.iter_mut().find(|(r, _)| r == route) {
+            metadata.insert(value);
+        } else {
+            let mut metadata = RouteMetadata::default();
+            metadata.insert(value);
+            self.metadata.push((route.to_owned(), metadata));
+        }
+        self
+    }
+
+    /// Set the [`RoutePolicy`] of all routes without their own policy (see
+    /// [`Self::route_policy`]). Default is [`RoutePolicy::KeepAlive`].
+    pub fn default_route_policy(mut self, policy: RoutePolicy) -> Self {
+        self.default_route_policy = policy;
+        self
+    }
+
+    /// Set whether a route keeps its state while another route is opened on top of it, or is
+    /// rebuilt (running its handler again) when going back to it. The `route` uses the same
+    /// syntax as [`Self::route`].
+    ///
+    /// ```
+    /// # use egui_router::{EguiRouter, Request, Route, RoutePolicy};
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut egui::Ui, _: &mut ()| {}
+    /// }
+    ///
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/inbox", page)
+    ///     .route("/message/{id}", page)
+    ///     // Reload the inbox when going back to it, e.g. after deleting a message
+    ///     .route_policy("/inbox", RoutePolicy::Rebuild)
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    /// ```
+    pub fn route_policy(mut self, route: &str, policy: RoutePolicy) -> Self {
+        self.route_policies.insert(route, policy).unwrap();
+        self
+    }
+
+    /// Called with the title of the active route (see [`Self::title`]) whenever it changes.
+    pub fn on_title_change(mut self, f: impl FnMut(&egui::Context, &str) + 'static) -> Self {
+        self.on_title_change = Some(Box::new(f));
+        self
+    }
+
+    /// Keep the native window title (or the document title on the web) in sync with the title
+    /// of the active route. This replaces the callback set via [`Self::on_title_change`].
+    pub fn sync_window_title(self) -> Self {
+        self.on_title_change(set_window_title)
+    }
+
+    /// Called whenever the active route changes, with the [`NavigationEvent`] describing whether
+    /// a route was pushed, replaced or popped and the routes it went from and to.
+    /// Use this for analytics, logging or to sync external state.
+    ///
+    /// It is also called for the initial route (as a push without a `from` route) and for
+    /// navigations in the ui, e.g. via the browser history, a swipe back gesture or a mounted
+    /// router. Those are reported when [`EguiRouter::ui`] is called.
+    ///
+    /// ```
+    /// # use egui_router::{EguiRouter, NavigationKind, Request, Route};
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// fn page(_req: Request) -> impl Route {
+    ///     |ui: &mut egui::Ui, _: &mut ()| {}
+    /// }
+    ///
+    /// let events = Rc::new(RefCell::new(Vec::new()));
+    /// let events_clone = events.clone();
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", page)
+    ///     .route("/settings", page)
+    ///     .default_path("/")
+    ///     .on_navigate(move |event| {
+    ///         events_clone
+    ///             .borrow_mut()
+    ///             .push((event.kind, event.to.path.clone()));
+    ///     })
+    ///     .build(&mut ());
+    ///
+    /// router.navigate(&mut (), "/settings").unwrap();
+    /// router.back().unwrap();
+    /// assert_eq!(
+    ///     *events.borrow(),
+    ///     [
+    ///         (NavigationKind::Push, "/".to_owned()),
+    ///         (NavigationKind::Push, "/settings".to_owned()),
+    ///         (NavigationKind::Pop, "/".to_owned()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn on_navigate(mut self, f: impl FnMut(&NavigationEvent) + 'static) -> Self {
+        self.on_navigate = Some(Box::new(f));
         self
     }
 
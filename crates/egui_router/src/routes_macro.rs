@@ -0,0 +1,151 @@
+/// Declare the routes of a [`crate::RouterBuilder`] in one place, calling
+/// [`crate::RouterBuilder::route`] for each entry.
+///
+/// Handlers are passed as usual, or as `handler(name: Type, ...)`, in which case the path params
+/// are parsed via [`crate::Request::param`] and passed to the handler after the request. If a
+/// param can't be parsed, the router's error ui is shown.
+///
+/// Mistakes in the table are caught at compile time: a route that is declared twice (also when
+/// only the param names differ) or a typed param that isn't part of the route fail the build.
+///
+/// ```
+/// # use egui::Ui;
+/// # use egui_router::{routes, EguiRouter, Request, Route};
+/// fn home(_request: Request) -> impl Route {
+///     |ui: &mut Ui, _: &mut ()| {
+///         ui.label("Home");
+///     }
+/// }
+///
+/// fn post(_request: Request, id: u64) -> impl Route {
+///     move |ui: &mut Ui, _: &mut ()| {
+///         ui.label(format!("Post {id}"));
+///     }
+/// }
+///
+/// let router: EguiRouter<()> = routes! {
+///     EguiRouter::builder(),
+///     "/" => home,
+///     "/post/{id}" => post(id: u64),
+///     "/about" => |_request: Request| |ui: &mut Ui, _: &mut ()| {
+///         ui.label("About");
+///     },
+/// }
+/// .build(&mut ());
+/// ```
+///
+/// ```compile_fail
+/// # use egui_router::{routes, EguiRouter, Request, Route};
+/// # fn post(_request: Request, id: u64) -> impl Route { |_: &mut egui::Ui, _: &mut ()| {} }
+/// // `/post/{id}` and `/post/{slug}` are the same route
+/// let router: EguiRouter<()> = routes! {
+///     EguiRouter::builder(),
+///     "/post/{id}" => post(id: u64),
+///     "/post/{slug}" => post(slug: u64),
+/// }
+/// .build(&mut ());
+/// ```
+#[macro_export]
+macro_rules! routes {
+    (@route [$($paths:literal)*] $builder:expr;) => {{
+        const _: () = assert!(
+            !$crate::__private::has_duplicate_route(&[$($paths),*]),
+            "routes! contains the same route twice",
+        );
+        $builder
+    }};
+    (
+        @route [$($paths:literal)*] $builder:expr;
+        $path:literal => $($handler:ident)::+ ($($param:ident: $ty:ty),* $(,)?)
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::routes!(
+            @route [$($paths)* $path]
+            $builder.route($path, |request: $crate::Request<_>| -> $crate::HandlerResult<_> {
+                $(
+                    const _: () = assert!(
+                        $crate::__private::has_param($path, stringify!($param)),
+                        "{}",
+                        concat!("route ", $path, " has no param `", stringify!($param), "`"),
+                    );
+                    let $param: $ty = request.param(stringify!($param))?;
+                )*
+                Ok($($handler)::+(request, $($param),*))
+            });
+            $($($rest)*)?
+        )
+    };
+    (
+        @route [$($paths:literal)*] $builder:expr;
+        $path:literal => $handler:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::routes!(@route [$($paths)* $path] $builder.route($path, $handler); $($($rest)*)?)
+    };
+    ($builder:expr, $($routes:tt)*) => {
+        $crate::routes!(@route [] $builder; $($routes)*)
+    };
+}
+
+/// Used by [`routes!`], checked at compile time
+#[doc(hidden)]
+pub mod __private {
+    /// Whether two routes are the same, ignoring the names of their params
+    const fn same_route(a: &[u8], b: &[u8]) -> bool {
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if a[i] == b'{' && b[j] == b'{' {
+                while i < a.len() && a[i] != b'}' {
+                    i += 1;
+                }
+                while j < b.len() && b[j] != b'}' {
+                    j += 1;
+                }
+            } else if a[i] != b[j] {
+                return false;
+            }
+            i += 1;
+            j += 1;
+        }
+        i == a.len() && j == b.len()
+    }
+
+    pub const fn has_duplicate_route(routes: &[&str]) -> bool {
+        let mut i = 0;
+        while i < routes.len() {
+            let mut j = i + 1;
+            while j < routes.len() {
+                if same_route(routes[i].as_bytes(), routes[j].as_bytes()) {
+                    return true;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Whether `route` has a param (or wildcard) called `name`
+    pub const fn has_param(route: &str, name: &str) -> bool {
+        let (route, name) = (route.as_bytes(), name.as_bytes());
+        let mut i = 0;
+        while i < route.len() {
+            if route[i] == b'{' {
+                i += 1;
+                if i < route.len() && route[i] == b'*' {
+                    i += 1;
+                }
+                let mut j = 0;
+                while i < route.len() && j < name.len() && route[i] == name[j] {
+                    i += 1;
+                    j += 1;
+                }
+                if j == name.len() && i < route.len() && route[i] == b'}' {
+                    return true;
+                }
+            }
+            i += 1;
+        }
+        false
+    }
+}
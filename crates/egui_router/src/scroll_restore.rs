@@ -0,0 +1,110 @@
+use crate::shared_element::SharedElementContext;
+use egui::scroll_area::ScrollAreaOutput;
+use egui::{Id, ScrollArea, Ui, Vec2};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The scroll offsets of the scroll areas shown via [`route_scroll_area`], by route id and
+/// scroll area id
+#[derive(Debug, Default)]
+pub(crate) struct ScrollPositions {
+    offsets: HashMap<(usize, Id), SavedOffset>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SavedOffset {
+    offset: Vec2,
+    /// The offset is applied until the scroll area reached it (or the user scrolled)
+    restoring: bool,
+}
+
+impl ScrollPositions {
+    /// Removes the offsets of routes that aren't in the history anymore
+    pub(crate) fn retain_routes(&mut self, routes: &[usize]) {
+        self.offsets.retain(|(route, _), _| routes.contains(route));
+    }
+
+    /// Restores the saved offsets of `route` the next time its scroll areas are shown
+    pub(crate) fn restore_route(&mut self, route: usize) {
+        for ((id, _), saved) in &mut self.offsets {
+            if *id == route {
+                saved.restoring = true;
+            }
+        }
+    }
+
+    fn restoring(&self, key: (usize, Id)) -> Option<Vec2> {
+        self.offsets
+            .get(&key)
+            .filter(|saved| saved.restoring)
+            .map(|saved| saved.offset)
+    }
+
+    fn save(&mut self, key: (usize, Id), offset: Vec2) {
+        self.offsets.insert(
+            key,
+            SavedOffset {
+                offset,
+                restoring: false,
+            },
+        );
+    }
+}
+
+/// Show a [`ScrollArea`] whose scroll offset is restored when going back to its route.
+///
+/// Routes that are kept alive (see [`crate::RoutePolicy::KeepAlive`]) keep their scroll offsets
+/// anyway, but when a route is rebuilt, its content is often shorter for a few frames (e.g.
+/// while reloading data), so the scroll area would be reset to the top. Here, the offset of the
+/// route instance is saved while it's shown and applied again once the route is rebuilt, until
+/// the content is large enough to scroll there (or the user scrolls).
+///
+/// `id_salt` is passed to [`ScrollArea::id_salt`] and has to be unique within the route.
+/// Outside of routes rendered by a [`crate::EguiRouter`], the scroll area is shown as usual.
+///
+/// ```
+/// # use egui_router::route_scroll_area;
+/// fn posts(ui: &mut egui::Ui, posts: &[String]) {
+///     route_scroll_area(ui, "posts", egui::ScrollArea::vertical(), |ui| {
+///         for post in posts {
+///             ui.label(post);
+///         }
+///     });
+/// }
+/// ```
+pub fn route_scroll_area<R>(
+    ui: &mut Ui,
+    id_salt: impl Hash,
+    scroll_area: ScrollArea,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> ScrollAreaOutput<R> {
+    let id = Id::new(id_salt);
+    let scroll_area = scroll_area.id_salt(id);
+    let Some(context) = SharedElementContext::get(ui) else {
+        return scroll_area.show(ui, add_contents);
+    };
+    let key = (context.route, id);
+
+    let restoring = context.scroll_positions.lock().restoring(key);
+    let scroll_area = match restoring {
+        Some(offset) => scroll_area.scroll_offset(offset),
+        None => scroll_area,
+    };
+    let output = scroll_area.show(ui, add_contents);
+
+    let restored = restoring.is_none_or(|offset| {
+        let user_scrolled = ui.rect_contains_pointer(output.inner_rect)
+            && ui.input(|input| {
+                input.smooth_scroll_delta != Vec2::ZERO || input.pointer.any_pressed()
+            });
+        (output.state.offset - offset).length() < 0.5 || user_scrolled
+    });
+    if restored {
+        context
+            .scroll_positions
+            .lock()
+            .save(key, output.state.offset);
+    }
+
+    output
+}
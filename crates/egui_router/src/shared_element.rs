@@ -0,0 +1,165 @@
+use crate::focus::RouteFocus;
+use crate::scroll_restore::ScrollPositions;
+use egui::emath::TSTransform;
+use egui::mutex::Mutex;
+use egui::{Id, InnerResponse, LayerId, Rect, Ui, UiBuilder};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// The shared elements of a router's routes, by route id and element id
+#[derive(Debug, Default)]
+pub(crate) struct SharedElements {
+    /// The rects of the elements, from the last frame where their route wasn't transitioning
+    pub rects: HashMap<(usize, Id), Rect>,
+    /// The elements of the arriving route that are moving during the current transition
+    pub moving: HashSet<(usize, Id)>,
+}
+
+impl SharedElements {
+    /// Removes the elements of routes that aren't shown anymore
+    pub(crate) fn retain_routes(&mut self, routes: &[usize]) {
+        self.rects.retain(|(route, _), _| routes.contains(route));
+        self.moving.retain(|(route, _)| routes.contains(route));
+    }
+}
+
+/// The routes of the running transition of a router
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SharedTransition {
+    /// The route that is being shown
+    pub arriving: usize,
+    /// The route that is being hidden
+    pub leaving: usize,
+    /// The eased progress from the leaving to the arriving route
+    pub progress: f32,
+}
+
+/// Made available to the routes of a router while they are shown, like the
+/// [`crate::link::RouterLinkContext`]
+#[derive(Clone)]
+pub(crate) struct SharedElementContext {
+    pub elements: Arc<Mutex<SharedElements>>,
+    /// The scroll offsets of the routes, see [`crate::route_scroll_area`]
+    pub scroll_positions: Arc<Mutex<ScrollPositions>>,
+    /// The focus of the routes, see [`crate::route_autofocus`]
+    pub focus: Arc<Mutex<RouteFocus>>,
+    pub transition: Option<SharedTransition>,
+    /// The id of the route that is currently being shown
+    pub route: usize,
+}
+
+impl SharedElementContext {
+    pub(crate) fn id() -> Id {
+        Id::new("egui_router_shared_element_context")
+    }
+
+    pub(crate) fn get(ui: &Ui) -> Option<Self> {
+        ui.data(|data| data.get_temp(Self::id()))
+    }
+
+    /// Marks `route` as the route that is currently being shown
+    pub(crate) fn set_route(ui: &Ui, route: usize) {
+        ui.data_mut(|data| {
+            if let Some(mut context) = data.get_temp::<Self>(Self::id()) {
+                context.route = route;
+                data.insert_temp(Self::id(), context);
+            }
+        });
+    }
+}
+
+/// Show a shared element (also called hero element). If two routes contain a shared element
+/// with the same `id`, the element moves from its position in the old route to its position in
+/// the new route while navigating between them, e.g. to expand a thumbnail in a list into the
+/// header image of a detail page.
+///
+/// During the transition, the element of the new route is shown on top of both routes, moved
+/// and scaled (keeping its aspect ratio) from the rect of the old route's element, while the old
+/// route's element is hidden. Outside of routes rendered by a [`crate::EguiRouter`], the
+/// contents are shown as usual.
+///
+/// ```
+/// # use egui_router::shared_element;
+/// fn list_item(ui: &mut egui::Ui, post_id: u64) {
+///     shared_element(ui, ("post_image", post_id), |ui| {
+///         ui.label("🖼");
+///     });
+/// }
+/// ```
+pub fn shared_element<R>(
+    ui: &mut Ui,
+    id: impl Hash,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> InnerResponse<R> {
+    let id = Id::new(id);
+    let Some(context) = SharedElementContext::get(ui) else {
+        return ui.scope(add_contents);
+    };
+    let route = context.route;
+
+    let transition = context
+        .transition
+        .filter(|transition| transition.arriving == route || transition.leaving == route);
+    let mut elements = context.elements.lock();
+    let from = transition.and_then(|transition| {
+        if transition.arriving == route {
+            elements.rects.get(&(transition.leaving, id)).copied()
+        } else {
+            None
+        }
+    });
+    let hidden = transition.is_some_and(|transition| {
+        transition.leaving == route && elements.moving.contains(&(transition.arriving, id))
+    });
+    if from.is_some() {
+        elements.moving.insert((route, id));
+    }
+    drop(elements);
+
+    match (transition, from) {
+        (Some(transition), Some(from)) => {
+            // Shown in its own layer, so it can be moved on top of both routes
+            let layer_id = LayerId::new(
+                ui.layer_id().order,
+                Id::new("egui_router_shared_element").with(route).with(id),
+            );
+            let response = ui.scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
+                ui.set_clip_rect(ui.ctx().screen_rect());
+                ui.set_opacity(1.0);
+                add_contents(ui)
+            });
+
+            let to = response.response.rect;
+            let rect = from.lerp_towards(&to, transition.progress);
+            let scale = if to.width() > 0.0 {
+                rect.width() / to.width()
+            } else {
+                1.0
+            };
+            ui.ctx().set_transform_layer(
+                layer_id,
+                TSTransform::new(rect.min.to_vec2() - to.min.to_vec2() * scale, scale),
+            );
+            response
+        }
+        _ if hidden => {
+            // The element of the arriving route is moving in its place
+            ui.scope(|ui| {
+                ui.set_invisible();
+                add_contents(ui)
+            })
+        }
+        (transition, _) => {
+            let response = ui.scope(add_contents);
+            if transition.is_none() {
+                context
+                    .elements
+                    .lock()
+                    .rects
+                    .insert((route, id), response.response.rect);
+            }
+            response
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::Route;
+use egui::Ui;
+use egui_suspense::EguiSuspense;
+use hello_egui_utils::{MaybeSend, MaybeSync};
+use std::fmt::{Debug, Display};
+
+type ViewFn<State, T> = Box<dyn FnMut(&mut Ui, &mut T, &mut State)>;
+
+/// A [Route] that loads its data with an [`EguiSuspense`] and shows it via `view` once loaded.
+///
+/// While loading (e.g. during the forward transition), the suspense's loading ui is shown as
+/// the page content. If loading fails, the suspense's error ui is shown, which offers a retry
+/// button if the suspense was created via [`EguiSuspense::reloadable`].
+///
+/// # Example
+/// ```rust
+/// # use egui_router::{EguiRouter, Request, Route, SuspenseRoute};
+/// # use egui_suspense::EguiSuspense;
+/// fn user(req: Request) -> impl Route {
+///     let id = req.params.get("id").unwrap_or_default().to_owned();
+///     SuspenseRoute::new(
+///         EguiSuspense::reloadable(move |callback| {
+///             // Load the user, e.g. from a background thread
+///             callback(Ok::<_, String>(format!("User {id}")));
+///         }),
+///         |ui, user: &mut String, _state: &mut ()| {
+///             ui.label(user.as_str());
+///         },
+///     )
+/// }
+///
+/// let router: EguiRouter<()> = EguiRouter::builder()
+///     .route("/user/{id}", user)
+///     .default_path("/user/1")
+///     .build(&mut ());
+/// ```
+pub struct SuspenseRoute<State, T, E: Display + Debug = String> {
+    suspense: EguiSuspense<T, E>,
+    view: ViewFn<State, T>,
+}
+
+impl<State, T, E> SuspenseRoute<State, T, E>
+where
+    T: MaybeSend + MaybeSync + 'static,
+    E: Display + Debug + MaybeSend + MaybeSync + 'static,
+{
+    /// Create a new suspense route. `view` is called with the loaded data.
+    pub fn new(
+        suspense: EguiSuspense<T, E>,
+        view: impl FnMut(&mut Ui, &mut T, &mut State) + 'static,
+    ) -> Self {
+        Self {
+            suspense,
+            view: Box::new(view),
+        }
+    }
+}
+
+impl<State, T, E> Route<State> for SuspenseRoute<State, T, E>
+where
+    T: MaybeSend + MaybeSync + 'static,
+    E: Display + Debug + MaybeSend + MaybeSync + 'static,
+{
+    fn ui(&mut self, ui: &mut Ui, state: &mut State) {
+        let view = &mut self.view;
+        self.suspense.ui(ui, |ui, data, _suspense_state| {
+            view(ui, data, state);
+        });
+    }
+
+    fn loading(&self) -> bool {
+        self.suspense.loading()
+    }
+}
@@ -0,0 +1,214 @@
+use crate::history::MemoryHistory;
+use crate::transition::{ActiveTransition, ActiveTransitionResult};
+use crate::{EguiRouter, RouterError, RouterResult, TransitionConfig, ID};
+use egui::Ui;
+use std::any::Any;
+use std::sync::atomic::Ordering;
+
+struct Tab<State> {
+    id: usize,
+    router: EguiRouter<State, MemoryHistory>,
+}
+
+struct TabSwitch {
+    transition: ActiveTransition,
+    from: usize,
+}
+
+/// Multiple routers with independent history stacks, one per tab, like mobile apps with a
+/// bottom navigation bar. Switching tabs animates between them and keeps each tab's history
+/// (and e.g. scroll positions) intact.
+///
+/// The tab bar itself is up to you, call [`TabRouter::set_active`] when a tab is selected.
+///
+/// # Example
+/// ```rust
+/// # use egui::Ui;
+/// # use egui_router::{EguiRouter, Request, TabRouter};
+/// # use egui_router::history::MemoryHistory;
+/// fn tab(name: &'static str) -> EguiRouter<(), MemoryHistory> {
+///     EguiRouter::builder()
+///         .route("/", move |_req: Request| move |ui: &mut Ui, _: &mut ()| {
+///             ui.label(name);
+///         })
+///         .default_path("/")
+///         .build(&mut ())
+/// }
+///
+/// fn tabs_ui(ui: &mut Ui, tabs: &mut TabRouter<()>) {
+///     ui.horizontal(|ui| {
+///         for (index, name) in ["Home", "Settings"].into_iter().enumerate() {
+///             if ui.selectable_label(tabs.active() == index, name).clicked() {
+///                 tabs.set_active(index).ok();
+///             }
+///         }
+///     });
+///     tabs.ui(ui, &mut ());
+/// }
+///
+/// let tabs = TabRouter::new().tab(tab("Home")).tab(tab("Settings"));
+/// ```
+pub struct TabRouter<State> {
+    tabs: Vec<Tab<State>>,
+    active: usize,
+    transition: TransitionConfig,
+    switch: Option<TabSwitch>,
+}
+
+impl<State: 'static> Default for TabRouter<State> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<State: 'static> TabRouter<State> {
+    /// Create a new tab router without tabs
+    pub fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: 0,
+            transition: TransitionConfig::fade(),
+            switch: None,
+        }
+    }
+
+    /// Add a tab with its own router
+    pub fn tab(mut self, router: EguiRouter<State, MemoryHistory>) -> Self {
+        self.tabs.push(Tab {
+            id: ID.fetch_add(1, Ordering::SeqCst),
+            router,
+        });
+        self
+    }
+
+    /// Set the transition used when switching tabs. Switching to a tab with a higher index
+    /// plays it forward, switching to a lower index plays it backward. The default is a fade.
+    pub fn transition(mut self, transition: TransitionConfig) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// The index of the active tab
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// The number of tabs
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Returns true if no tabs were added
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Switch to the tab at `index`. The history of the previous tab is kept.
+    /// Returns [`RouterError::NotFound`] if there is no tab at `index`.
+    pub fn set_active(&mut self, index: usize) -> RouterResult {
+        if index >= self.tabs.len() {
+            return Err(RouterError::NotFound);
+        }
+        if index != self.active {
+            let config = self.transition.clone();
+            self.switch = Some(TabSwitch {
+                transition: if index > self.active {
+                    ActiveTransition::forward(config)
+                } else {
+                    ActiveTransition::backward(config)
+                },
+                from: self.active,
+            });
+            self.active = index;
+        }
+        Ok(())
+    }
+
+    /// The router of the tab at `index`
+    pub fn router(&self, index: usize) -> Option<&EguiRouter<State, MemoryHistory>> {
+        self.tabs.get(index).map(|tab| &tab.router)
+    }
+
+    /// The router of the tab at `index`, e.g. to navigate in a tab that isn't active
+    pub fn router_mut(&mut self, index: usize) -> Option<&mut EguiRouter<State, MemoryHistory>> {
+        self.tabs.get_mut(index).map(|tab| &mut tab.router)
+    }
+
+    /// The router of the active tab
+    pub fn active_router(&self) -> Option<&EguiRouter<State, MemoryHistory>> {
+        self.router(self.active)
+    }
+
+    /// The router of the active tab
+    pub fn active_router_mut(&mut self) -> Option<&mut EguiRouter<State, MemoryHistory>> {
+        self.router_mut(self.active)
+    }
+
+    /// Navigate in the active tab, see [`EguiRouter::navigate`]
+    pub fn navigate(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
+        self.active_router_mut()
+            .ok_or(RouterError::NotFound)?
+            .navigate(state, path)
+    }
+
+    /// Navigate in the active tab, passing a payload to the route handler, see
+    /// [`EguiRouter::navigate_with`]
+    pub fn navigate_with(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        payload: impl Any + Send + Sync,
+    ) -> RouterResult {
+        self.active_router_mut()
+            .ok_or(RouterError::NotFound)?
+            .navigate_with(state, path, payload)
+    }
+
+    /// Go back in the active tab, see [`EguiRouter::back`]
+    pub fn back(&mut self) -> RouterResult {
+        self.active_router_mut()
+            .ok_or(RouterError::NoPreviousRoute)?
+            .back()
+    }
+
+    /// Go forward in the active tab, see [`EguiRouter::forward`]
+    pub fn forward(&mut self, state: &mut State) -> RouterResult {
+        self.active_router_mut()
+            .ok_or(RouterError::NoForwardRoute)?
+            .forward(state)
+    }
+
+    /// Render the active tab, or both tabs while switching
+    pub fn ui(&mut self, ui: &mut Ui, state: &mut State) {
+        let active = self.active;
+        if let Some(switch) = &mut self.switch {
+            let (active_tab, previous_tab) = pair_mut(&mut self.tabs, active, switch.from);
+            let result = switch.transition.show(
+                ui,
+                state,
+                (active_tab.id, |ui: &mut Ui, state: &mut State| {
+                    active_tab.router.ui(ui, state);
+                }),
+                Some((previous_tab.id, |ui: &mut Ui, state: &mut State| {
+                    previous_tab.router.ui(ui, state);
+                })),
+            );
+            if let ActiveTransitionResult::Done = result {
+                self.switch = None;
+            }
+        } else if let Some(tab) = self.tabs.get_mut(active) {
+            ActiveTransition::show_default(ui, tab.id, |ui| tab.router.ui(ui, state));
+        }
+    }
+}
+
+/// Mutable references to two different tabs
+fn pair_mut<T>(items: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    if a < b {
+        let (left, right) = items.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = items.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
@@ -0,0 +1,23 @@
+/// The tasks spawned via [`crate::Request::spawn_scoped`] for a route.
+/// Dropping the scope (when the route is removed from the history) cancels the tasks.
+#[derive(Default)]
+pub(crate) struct TaskScope {
+    #[cfg(feature = "async")]
+    cancel: Vec<futures::channel::oneshot::Sender<()>>,
+}
+
+#[cfg(feature = "async")]
+impl TaskScope {
+    pub(crate) fn spawn(
+        &mut self,
+        future: impl std::future::Future<Output = ()> + hello_egui_utils::MaybeSend + 'static,
+    ) {
+        let (tx, rx) = futures::channel::oneshot::channel::<()>();
+        self.cancel.push(tx);
+
+        hello_egui_utils::spawn(async move {
+            // rx resolves once the sender is dropped, so whichever finishes first wins
+            futures::future::select(std::pin::pin!(future), rx).await;
+        });
+    }
+}
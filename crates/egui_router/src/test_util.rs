@@ -0,0 +1,141 @@
+use crate::history::{DefaultHistory, History};
+use crate::{EguiRouter, RouterResult};
+use egui_kittest::Harness;
+
+/// How many frames [`RouterHarness::settle`] runs at most before giving up
+const MAX_SETTLE_STEPS: usize = 1000;
+
+/// Returns the router and its state from the state of the harness
+type RouterParts<App, State, H> = fn(&mut App) -> (&mut EguiRouter<State, H>, &mut State);
+
+/// Drives an [`EguiRouter`] in an [`egui_kittest::Harness`], so the flows of your app can be
+/// tested (or snapshot tested) without depending on timing: navigating runs frames until the
+/// transition is done.
+///
+/// ```
+/// use egui_router::history::MemoryHistory;
+/// use egui_router::test_util::RouterHarness;
+/// use egui_router::{EguiRouter, Request, Route};
+///
+/// fn page(_request: Request) -> impl Route {
+///     |ui: &mut egui::Ui, _state: &mut ()| {
+///         ui.label("Page");
+///     }
+/// }
+///
+/// let router: EguiRouter<(), MemoryHistory> = EguiRouter::builder()
+///     .route("/", page)
+///     .route("/settings", page)
+///     .default_path("/")
+///     .build(&mut ());
+///
+/// let mut harness = RouterHarness::new(router, ());
+/// harness.assert_route("/");
+///
+/// harness.navigate("/settings").unwrap();
+/// harness.assert_route("/settings");
+///
+/// harness.back().unwrap();
+/// harness.assert_route("/");
+/// ```
+pub struct RouterHarness<'a, App, State, H = DefaultHistory> {
+    /// The harness showing the router. Use it to query and interact with the widgets of the
+    /// active route, or to take snapshots.
+    pub harness: Harness<'a, App>,
+    parts: RouterParts<App, State, H>,
+}
+
+impl<State: 'static, H: History + Default + 'static>
+    RouterHarness<'_, (EguiRouter<State, H>, State), State, H>
+{
+    /// Show `router` in a new [`Harness`], with `state` as the state of the router.
+    pub fn new(router: EguiRouter<State, H>, state: State) -> Self {
+        let harness = Harness::new_ui_state(
+            |ui, (router, state): &mut (EguiRouter<State, H>, State)| router.ui(ui, state),
+            (router, state),
+        );
+        Self::from_harness(harness, |(router, state)| (router, state))
+    }
+}
+
+impl<'a, App, State: 'static, H: History + Default + 'static> RouterHarness<'a, App, State, H> {
+    /// Use a [`Harness`] that shows your whole app, e.g. with the router inside of a layout.
+    /// `parts` returns the router and its state from the state of the harness.
+    ///
+    /// Runs frames until the initial route is shown.
+    pub fn from_harness(harness: Harness<'a, App>, parts: RouterParts<App, State, H>) -> Self {
+        let mut this = Self { harness, parts };
+        this.settle();
+        this
+    }
+
+    /// The router shown in the harness
+    pub fn router(&mut self) -> &mut EguiRouter<State, H> {
+        (self.parts)(self.harness.state_mut()).0
+    }
+
+    /// Navigate to `path` (see [`EguiRouter::navigate`]) and wait until the transition is done.
+    pub fn navigate(&mut self, path: impl Into<String>) -> RouterResult {
+        let (router, state) = (self.parts)(self.harness.state_mut());
+        let result = router.navigate(state, path);
+        self.settle();
+        result
+    }
+
+    /// Go back (see [`EguiRouter::back`]) and wait until the transition is done.
+    pub fn back(&mut self) -> RouterResult {
+        let result = self.router().back();
+        self.settle();
+        result
+    }
+
+    /// Go forward (see [`EguiRouter::forward`]) and wait until the transition is done.
+    pub fn forward(&mut self) -> RouterResult {
+        let (router, state) = (self.parts)(self.harness.state_mut());
+        let result = router.forward(state);
+        self.settle();
+        result
+    }
+
+    /// Run frames until the running transition (if any) is done, then run one more frame so
+    /// only the active route is shown. The transitions advance by the step time of the
+    /// harness, so this is deterministic. Routes that are still loading (see
+    /// [`crate::Route::loading`]) aren't waited for.
+    ///
+    /// # Panics
+    /// If the transition doesn't finish within 1000 frames.
+    #[track_caller]
+    pub fn settle(&mut self) {
+        for _ in 0..MAX_SETTLE_STEPS {
+            self.harness.step();
+            let transitioning = self
+                .router()
+                .navigation_progress()
+                .is_some_and(|progress| progress.transition.is_some());
+            if !transitioning {
+                self.harness.step();
+                return;
+            }
+        }
+        panic!("The transition didn't finish within {MAX_SETTLE_STEPS} frames");
+    }
+
+    /// The path of the active route, see [`EguiRouter::active_route`]
+    pub fn active_route(&mut self) -> Option<String> {
+        self.router().active_route().map(ToOwned::to_owned)
+    }
+
+    /// Asserts that the route at `path` is active.
+    ///
+    /// # Panics
+    /// If another route (or no route) is active.
+    #[track_caller]
+    pub fn assert_route(&mut self, path: &str) {
+        let active = self.active_route();
+        assert_eq!(
+            active.as_deref(),
+            Some(path),
+            "Expected the route {path:?} to be active, but it was {active:?}",
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::HistoryEntry;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+pub(crate) type TitleCallback = Box<dyn FnMut(&egui::Context, &str)>;
+
+/// The title of a route, see [`crate::RouterBuilder::title`].
+///
+/// Create a static title from a `&str` or `String`, or compute it from the route's
+/// [`HistoryEntry`] (e.g. its params) via [`RouteTitle::new`].
+#[derive(Clone)]
+pub struct RouteTitle(Arc<dyn Fn(&HistoryEntry) -> String + Send + Sync>);
+
+impl RouteTitle {
+    /// A title computed from the history entry of the route
+    pub fn new(title: impl Fn(&HistoryEntry) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(title))
+    }
+
+    pub(crate) fn get(&self, entry: &HistoryEntry) -> String {
+        (self.0)(entry)
+    }
+}
+
+impl From<&str> for RouteTitle {
+    fn from(title: &str) -> Self {
+        title.to_owned().into()
+    }
+}
+
+impl From<String> for RouteTitle {
+    fn from(title: String) -> Self {
+        Self::new(move |_| title.clone())
+    }
+}
+
+impl Debug for RouteTitle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RouteTitle").finish_non_exhaustive()
+    }
+}
+
+/// A route in the history that has a title, see [`crate::EguiRouter::breadcrumbs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    /// The title of the route
+    pub title: String,
+    /// The history entry of the route, e.g. to go back to it via [`crate::EguiRouter::back_to`]
+    pub entry: HistoryEntry,
+}
+
+/// Sets the native window title or, on the web, the document title
+pub(crate) fn set_window_title(ctx: &egui::Context, title: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = ctx;
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            document.set_title(title);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.to_owned()));
+}
@@ -1,8 +1,14 @@
 use crate::TransitionConfig;
-use egui::{Id, Ui, UiBuilder, Vec2};
+use egui::emath::TSTransform;
+use egui::{lerp, Id, LayerId, Ui, UiBuilder, Vec2};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
 /// Trait for declaring a transition.
 /// Prefer [`ComposableTransitionTrait`] unless you need to create a new ui to apply the transition.
+///
+/// Implement this (or [`ComposableTransitionTrait`]) for your own transitions and use them via
+/// [`CustomTransition`].
 pub trait TransitionTrait {
     /// Create a child ui with the transition applied
     fn create_child_ui(&self, ui: &mut Ui, t: f32, with_id: Id) -> Ui;
@@ -33,6 +39,12 @@ pub enum Transition {
     Slide(SlideTransition),
     /// Combined slide and fade transitions
     SlideFade(SlideFadeTransition),
+    /// Scale (zoom) transition
+    Scale(ScaleTransition),
+    /// Combined scale and fade transitions
+    ScaleFade(ScaleFadeTransition),
+    /// A user defined transition
+    Custom(CustomTransition),
 }
 
 impl TransitionTrait for Transition {
@@ -44,6 +56,9 @@ impl TransitionTrait for Transition {
             }
             Transition::Slide(slide) => slide.create_child_ui(ui, t, with_id),
             Transition::SlideFade(slide_fade) => slide_fade.create_child_ui(ui, t, with_id),
+            Transition::Scale(scale) => scale.create_child_ui(ui, t, with_id),
+            Transition::ScaleFade(scale_fade) => scale_fade.create_child_ui(ui, t, with_id),
+            Transition::Custom(custom) => custom.0.create_child_ui(ui, t, with_id),
         }
     }
 }
@@ -67,6 +82,53 @@ pub struct SlideTransition {
 #[derive(Debug, Clone)]
 pub struct SlideFadeTransition(pub SlideTransition, pub FadeTransition);
 
+/// Scale (zoom) transition around the center of the page
+#[derive(Debug, Clone)]
+pub struct ScaleTransition {
+    /// The scale at the start of the transition, e.g. 0.8 to zoom in from 80% or 1.2 to zoom out
+    /// from 120%. Default is 0.8
+    pub scale: f32,
+}
+
+/// Combining scale and fade transitions
+#[derive(Debug, Clone)]
+pub struct ScaleFadeTransition(pub ScaleTransition, pub FadeTransition);
+
+/// A user defined transition, e.g. a flip, blur or circular reveal.
+///
+/// # Example
+/// ```rust
+/// # use egui::Ui;
+/// # use egui_router::TransitionConfig;
+/// # use egui_router::transition::{ComposableTransitionTrait, CustomTransition, NoTransition};
+/// /// Fades the page in, starting slowly
+/// struct SlowFade;
+///
+/// impl ComposableTransitionTrait for SlowFade {
+///     fn apply(&self, ui: &mut Ui, t: f32) {
+///         ui.multiply_opacity(t * t);
+///     }
+/// }
+///
+/// let config = TransitionConfig::new(CustomTransition::new(SlowFade), NoTransition);
+/// ```
+#[derive(Clone)]
+pub struct CustomTransition(Arc<dyn TransitionTrait + Send + Sync>);
+
+impl CustomTransition {
+    /// Create a new custom transition from a [`TransitionTrait`] (or [`ComposableTransitionTrait`])
+    /// implementation.
+    pub fn new(transition: impl TransitionTrait + Send + Sync + 'static) -> Self {
+        Self(Arc::new(transition))
+    }
+}
+
+impl Debug for CustomTransition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomTransition").finish_non_exhaustive()
+    }
+}
+
 impl Default for SlideTransition {
     fn default() -> Self {
         Self { amount: Vec2::X }
@@ -80,6 +142,19 @@ impl SlideTransition {
     }
 }
 
+impl Default for ScaleTransition {
+    fn default() -> Self {
+        Self { scale: 0.8 }
+    }
+}
+
+impl ScaleTransition {
+    /// Create a new scale transition, starting at `scale`
+    pub fn new(scale: f32) -> Self {
+        Self { scale }
+    }
+}
+
 impl ComposableTransitionTrait for FadeTransition {
     fn apply(&self, ui: &mut Ui, t: f32) {
         ui.set_opacity(t);
@@ -108,6 +183,38 @@ impl TransitionTrait for SlideFadeTransition {
     }
 }
 
+impl TransitionTrait for ScaleTransition {
+    fn create_child_ui(&self, ui: &mut Ui, t: f32, with_id: Id) -> Ui {
+        // The page is shown in its own layer, so it can be scaled via a layer transform
+        let layer_id = LayerId::new(ui.layer_id().order, with_id.with("scale_transition"));
+        ui.ctx().set_sublayer(ui.layer_id(), layer_id);
+
+        let center = ui.max_rect().center().to_vec2();
+        let scale = lerp(self.scale..=1.0, t);
+        ui.ctx().set_transform_layer(
+            layer_id,
+            TSTransform::from_translation(center)
+                * TSTransform::from_scaling(scale)
+                * TSTransform::from_translation(-center),
+        );
+
+        ui.new_child(
+            UiBuilder::new()
+                .max_rect(ui.max_rect())
+                .id_salt(with_id)
+                .layer_id(layer_id),
+        )
+    }
+}
+
+impl TransitionTrait for ScaleFadeTransition {
+    fn create_child_ui(&self, ui: &mut Ui, t: f32, with_id: Id) -> Ui {
+        let mut child = self.0.create_child_ui(ui, t, with_id);
+        self.1.apply(&mut child, t);
+        child
+    }
+}
+
 impl From<FadeTransition> for Transition {
     fn from(fade: FadeTransition) -> Self {
         Transition::Fade(fade)
@@ -132,6 +239,24 @@ impl From<SlideFadeTransition> for Transition {
     }
 }
 
+impl From<ScaleTransition> for Transition {
+    fn from(scale: ScaleTransition) -> Self {
+        Transition::Scale(scale)
+    }
+}
+
+impl From<ScaleFadeTransition> for Transition {
+    fn from(scale_fade: ScaleFadeTransition) -> Self {
+        Transition::ScaleFade(scale_fade)
+    }
+}
+
+impl From<CustomTransition> for Transition {
+    fn from(custom: CustomTransition) -> Self {
+        Transition::Custom(custom)
+    }
+}
+
 /// Configuration for a transition, containing the in and out transitions
 /// The in transition is the transition that will be applied to the page that is being navigated to
 /// The out transition is the transition that will be applied to the page that is being navigated from
@@ -152,12 +277,47 @@ pub enum TransitionType {
     },
 }
 
+/// The timing of the in or out transition, see [`TransitionConfig::with_in_duration`]
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct LayerTiming {
+    /// Falls back to the duration of the transition
+    pub duration: Option<f32>,
+    /// Falls back to the easing of the transition
+    pub easing: Option<fn(f32) -> f32>,
+    /// In seconds from the start of the (forward) transition
+    pub delay: f32,
+}
+
+impl LayerTiming {
+    /// The time from the start of the transition until this layer is done
+    fn end(&self, base_duration: f32) -> f32 {
+        self.delay + self.duration.unwrap_or(base_duration)
+    }
+
+    /// The progress of this layer (without easing) at `time` seconds into the transition
+    fn progress(&self, base_duration: f32, time: f32) -> f32 {
+        let duration = self.duration.unwrap_or(base_duration);
+        if duration <= 0.0 {
+            if time >= self.delay {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            ((time - self.delay) / duration).clamp(0.0, 1.0)
+        }
+    }
+}
+
 pub(crate) struct ActiveTransition {
     duration: Option<f32>,
+    duration_scale: f32,
     progress: f32,
     easing: fn(f32) -> f32,
     in_: Transition,
     out: Transition,
+    in_timing: LayerTiming,
+    out_timing: LayerTiming,
     backward: bool,
 }
 
@@ -170,10 +330,13 @@ impl ActiveTransition {
     pub fn forward(config: TransitionConfig) -> Self {
         Self {
             duration: config.duration,
+            duration_scale: 1.0,
             easing: config.easing,
             progress: 0.0,
             in_: config.in_,
             out: config.out,
+            in_timing: config.in_timing,
+            out_timing: config.out_timing,
             backward: false,
         }
     }
@@ -181,10 +344,13 @@ impl ActiveTransition {
     pub fn backward(config: TransitionConfig) -> Self {
         Self {
             duration: config.duration,
+            duration_scale: 1.0,
             easing: config.easing,
             progress: 0.0,
             in_: config.in_,
             out: config.out,
+            in_timing: config.in_timing,
+            out_timing: config.out_timing,
             backward: true,
         }
     }
@@ -196,6 +362,79 @@ impl ActiveTransition {
         self
     }
 
+    pub fn with_duration_scale(mut self, scale: f32) -> Self {
+        self.duration_scale = scale;
+        self
+    }
+
+    /// Start the transition at `progress` instead of 0.0, e.g. to finish a swipe gesture
+    pub fn with_progress(mut self, progress: f32) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Use `easing` for both pages, ignoring the timing of the in and out transition, so both
+    /// follow the progress directly, e.g. during a swipe gesture
+    pub fn with_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self.in_timing = LayerTiming::default();
+        self.out_timing = LayerTiming::default();
+        self
+    }
+
+    pub fn eased_progress(&self) -> f32 {
+        (self.easing)(self.progress.min(1.0))
+    }
+
+    pub fn is_backward(&self) -> bool {
+        self.backward
+    }
+
+    /// The progress from 0.0 to 1.0, without easing
+    pub fn progress(&self) -> f32 {
+        self.progress.min(1.0)
+    }
+
+    /// The duration of the transition, without the in and out timing, falling back to the
+    /// style's animation time
+    fn base_duration(&self, ui: &Ui) -> f32 {
+        self.duration.unwrap_or_else(|| ui.style().animation_time)
+    }
+
+    /// The duration in seconds, until both the in and out transition are done
+    pub fn duration(&self, ui: &Ui) -> f32 {
+        let base = self.base_duration(ui);
+        self.in_timing.end(base).max(self.out_timing.end(base)) * self.duration_scale
+    }
+
+    /// The eased progress of the in and out transition at `t`. Backward transitions play the
+    /// forward transition in reverse.
+    fn layer_progress(&self, ui: &Ui, t: f32) -> (f32, f32) {
+        let forward_t = if self.backward { 1.0 - t } else { t };
+        let base = self.base_duration(ui);
+        let total = self.in_timing.end(base).max(self.out_timing.end(base));
+        let (in_t, out_t) = if total <= 0.0 {
+            (forward_t, forward_t)
+        } else {
+            let time = forward_t * total;
+            (
+                self.in_timing.progress(base, time),
+                self.out_timing.progress(base, time),
+            )
+        };
+        (
+            (self.in_timing.easing.unwrap_or(self.easing))(in_t),
+            (self.out_timing.easing.unwrap_or(self.easing))(1.0 - out_t),
+        )
+    }
+
+    /// The same transition, played in the other direction from where it currently is
+    pub fn reversed(mut self) -> Self {
+        self.backward = !self.backward;
+        self.progress = 1.0 - self.progress.min(1.0);
+        self
+    }
+
     pub fn show<State>(
         &mut self,
         ui: &mut Ui,
@@ -205,58 +444,67 @@ impl ActiveTransition {
     ) -> ActiveTransitionResult {
         let dt = ui.input(|i| i.stable_dt);
 
-        self.progress += dt / self.duration.unwrap_or_else(|| ui.style().animation_time);
+        let duration = self.duration(ui);
+        if reduce_motion(ui.ctx()) || duration <= 0.0 {
+            self.progress = 1.0;
+        } else {
+            self.progress += dt / duration;
+        }
+
+        self.render(ui, state, (in_id, content_in), content_out);
+
+        if self.progress >= 1.0 {
+            ActiveTransitionResult::Done
+        } else {
+            ActiveTransitionResult::Continue
+        }
+    }
 
+    /// Render the transition at its current progress, without advancing it
+    pub fn render<State>(
+        &self,
+        ui: &mut Ui,
+        state: &mut State,
+        (in_id, content_in): (usize, impl FnOnce(&mut Ui, &mut State)),
+        content_out: Option<(usize, impl FnOnce(&mut Ui, &mut State))>,
+    ) {
         let t = self.progress.min(1.0);
+        let (in_t, out_t) = self.layer_progress(ui, t);
         ui.ctx().request_repaint();
 
         if self.backward {
             with_temp_auto_id(ui, in_id, |ui| {
-                let mut out_ui = self.out.create_child_ui(
-                    ui,
-                    (self.easing)(t),
-                    Id::new("router_child").with(in_id),
-                );
+                let mut out_ui =
+                    self.out
+                        .create_child_ui(ui, out_t, Id::new("router_child").with(in_id));
                 content_in(&mut out_ui, state);
             });
 
             if let Some((out_id, content_out)) = content_out {
                 with_temp_auto_id(ui, out_id, |ui| {
-                    let mut in_ui = self.in_.create_child_ui(
-                        ui,
-                        (self.easing)(1.0 - t),
-                        Id::new("router_child").with(out_id),
-                    );
+                    let mut in_ui =
+                        self.in_
+                            .create_child_ui(ui, in_t, Id::new("router_child").with(out_id));
                     content_out(&mut in_ui, state);
                 });
             }
         } else {
             if let Some((out_id, content_out)) = content_out {
                 with_temp_auto_id(ui, out_id, |ui| {
-                    let mut out_ui = self.out.create_child_ui(
-                        ui,
-                        (self.easing)(1.0 - t),
-                        Id::new("router_child").with(out_id),
-                    );
+                    let mut out_ui =
+                        self.out
+                            .create_child_ui(ui, out_t, Id::new("router_child").with(out_id));
                     content_out(&mut out_ui, state);
                 });
             }
 
             with_temp_auto_id(ui, in_id, |ui| {
-                let mut in_ui = self.in_.create_child_ui(
-                    ui,
-                    (self.easing)(t),
-                    Id::new("router_child").with(in_id),
-                );
+                let mut in_ui =
+                    self.in_
+                        .create_child_ui(ui, in_t, Id::new("router_child").with(in_id));
                 content_in(&mut in_ui, state);
             });
         }
-
-        if self.progress >= 1.0 {
-            ActiveTransitionResult::Done
-        } else {
-            ActiveTransitionResult::Continue
-        }
     }
 
     pub fn show_default(ui: &mut Ui, with_id: usize, content: impl FnOnce(&mut Ui)) {
@@ -271,6 +519,17 @@ impl ActiveTransition {
     }
 }
 
+/// Returns true if [`egui_animation::reduce_motion`] is set
+#[cfg(feature = "animation")]
+fn reduce_motion(ctx: &egui::Context) -> bool {
+    egui_animation::reduce_motion(ctx)
+}
+
+#[cfg(not(feature = "animation"))]
+fn reduce_motion(_ctx: &egui::Context) -> bool {
+    false
+}
+
 fn with_temp_auto_id(ui: &mut Ui, id: usize, content: impl FnOnce(&mut Ui)) {
     ui.skip_ahead_auto_ids(id);
     content(ui);
@@ -0,0 +1,36 @@
+use crate::history::MemoryHistory;
+use crate::EguiRouter;
+use egui::{CentralPanel, Context, ViewportBuilder, ViewportClass, ViewportId, Window};
+
+/// A router shown in its own viewport, see [`EguiRouter::open_in_viewport`]
+pub(crate) struct ViewportRouter<State> {
+    pub(crate) id: ViewportId,
+    pub(crate) builder: ViewportBuilder,
+    pub(crate) router: EguiRouter<State, MemoryHistory>,
+}
+
+impl<State: 'static> ViewportRouter<State> {
+    /// Shows the viewport. Returns false once the user closed it.
+    pub(crate) fn ui(&mut self, ctx: &Context, state: &mut State) -> bool {
+        let mut open = true;
+        ctx.show_viewport_immediate(self.id, self.builder.clone(), |ctx, class| {
+            if class == ViewportClass::Embedded {
+                // The backend doesn't support multiple viewports, so we show a window instead
+                Window::new(self.builder.title.clone().unwrap_or_default())
+                    .id(egui::Id::new(self.id))
+                    .open(&mut open)
+                    .show(ctx, |ui| {
+                        self.router.ui(ui, state);
+                    });
+            } else {
+                CentralPanel::default().show(ctx, |ui| {
+                    self.router.ui(ui, state);
+                });
+                if ctx.input(|input| input.viewport().close_requested()) {
+                    open = false;
+                }
+            }
+        });
+        open
+    }
+}
@@ -0,0 +1,100 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, HistoryEntry, Request, Route, RouterError};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn router() -> EguiRouter<(), MemoryHistory> {
+    let settings = EguiRouter::builder()
+        .route("/", page)
+        .route("/general", page)
+        .route("/account", page)
+        .default_path("/")
+        .build(&mut ());
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/wizard/{step}", page)
+        .mount("/settings", settings)
+        .default_path("/")
+        .build(&mut ())
+}
+
+fn urls(router: &EguiRouter<(), MemoryHistory>) -> Vec<String> {
+    router.history().map(HistoryEntry::url).collect()
+}
+
+#[test]
+fn back_to() {
+    let mut router = router();
+    for step in 1..=3 {
+        router.navigate(&mut (), format!("/wizard/{step}")).unwrap();
+    }
+
+    router.back_to("/wizard/1").unwrap();
+    assert_eq!(router.active_route(), Some("/wizard/1"));
+    // All routes above are removed with a single navigation
+    assert_eq!(urls(&router), ["/", "/wizard/1"]);
+    assert!(router.can_go_forward());
+}
+
+#[test]
+fn pop_until() {
+    let mut router = router();
+    router.navigate(&mut (), "/wizard/1?draft=1").unwrap();
+    router.navigate(&mut (), "/wizard/2").unwrap();
+    router.navigate(&mut (), "/wizard/3").unwrap();
+
+    router
+        .pop_until(|entry| entry.query.contains_key("draft"))
+        .unwrap();
+    assert_eq!(urls(&router), ["/", "/wizard/1?draft=1"]);
+
+    // The current route matches, so nothing happens
+    router.pop_until(|entry| entry.path == "/wizard/1").unwrap();
+    assert_eq!(urls(&router), ["/", "/wizard/1?draft=1"]);
+}
+
+#[test]
+fn target_missing() {
+    let mut router = router();
+    router.navigate(&mut (), "/wizard/1").unwrap();
+    router.navigate(&mut (), "/wizard/2").unwrap();
+
+    assert!(matches!(
+        router.back_to("/wizard/3"),
+        Err(RouterError::NotFound)
+    ));
+    assert!(matches!(
+        router.pop_until(|entry| entry.path == "/settings"),
+        Err(RouterError::NotFound)
+    ));
+    assert_eq!(urls(&router), ["/", "/wizard/1", "/wizard/2"]);
+    assert!(!router.can_go_forward());
+}
+
+#[test]
+fn back_to_mount() {
+    let mut router = router();
+    router.navigate(&mut (), "/wizard/1").unwrap();
+    router.navigate(&mut (), "/settings").unwrap();
+    router.navigate(&mut (), "/settings/general").unwrap();
+    router.navigate(&mut (), "/settings/account").unwrap();
+
+    // Going back to a route of the mounted router
+    router.back_to("/settings/general").unwrap();
+    assert_eq!(router.active_route(), Some("/settings/general"));
+    router.forward(&mut ()).unwrap();
+    assert_eq!(router.active_route(), Some("/settings/account"));
+    router.back_to("/settings/general").unwrap();
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/settings"));
+
+    // Going back past the mounted router
+    router.navigate(&mut (), "/settings/account").unwrap();
+    router.back_to("/wizard/1").unwrap();
+    assert_eq!(router.active_route(), Some("/wizard/1"));
+    assert_eq!(urls(&router), ["/", "/wizard/1"]);
+}
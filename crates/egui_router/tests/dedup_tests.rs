@@ -0,0 +1,42 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn router(dedup: bool) -> EguiRouter<(), MemoryHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/item", page)
+        .dedup_navigation(dedup)
+        .default_path("/")
+        .build(&mut ())
+}
+
+#[test]
+fn dedup_navigation() {
+    let mut router = router(true);
+
+    router.navigate(&mut (), "/item?id=1").unwrap();
+    router.navigate(&mut (), "/item?id=1").unwrap();
+    assert_eq!(router.depth(), 2);
+
+    // A different query is a different url
+    router.navigate(&mut (), "/item?id=2").unwrap();
+    assert_eq!(router.depth(), 3);
+}
+
+#[test]
+fn no_dedup_navigation() {
+    let mut router = router(false);
+
+    router.navigate(&mut (), "/item").unwrap();
+    router.navigate(&mut (), "/item").unwrap();
+    assert_eq!(router.depth(), 3);
+
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/item"));
+}
@@ -0,0 +1,40 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, HistoryEntry, Request, Route, RouterError};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn history(router: &EguiRouter<(), MemoryHistory>) -> Vec<String> {
+    router.history().map(HistoryEntry::url).collect()
+}
+
+#[test]
+fn deep_links() {
+    let mut router: EguiRouter<(), MemoryHistory> = EguiRouter::builder()
+        .route("/", page)
+        .route("/settings", page)
+        .route("/settings/{section}", page)
+        .default_path("/")
+        .build(&mut ());
+
+    // The parent routes are added to the history, so going back works as expected
+    router
+        .navigate_initial(&mut (), "myapp://settings/account?tab=2")
+        .unwrap();
+    assert_eq!(router.active_route(), Some("/settings/account"));
+    assert_eq!(
+        history(&router),
+        ["/", "/settings", "/settings/account?tab=2"]
+    );
+
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/settings"));
+
+    // An unknown link falls back to the default path
+    let result = router.navigate_initial(&mut (), "/unknown");
+    assert!(matches!(result, Err(RouterError::InvalidDeepLink { .. })));
+    assert_eq!(history(&router), ["/"]);
+}
@@ -0,0 +1,62 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route, RouterError};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn router() -> EguiRouter<(), MemoryHistory> {
+    let settings = EguiRouter::builder()
+        .route("/", page)
+        .route("/general", page)
+        .default_path("/")
+        .build(&mut ());
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/about", page)
+        .mount("/settings", settings)
+        .default_path("/")
+        .build(&mut ())
+}
+
+#[test]
+fn forward() {
+    let mut router = router();
+
+    router.navigate(&mut (), "/about").unwrap();
+    router.back().unwrap();
+    assert!(router.can_go_forward());
+    router.forward(&mut ()).unwrap();
+    assert_eq!(router.active_route(), Some("/about"));
+    assert!(matches!(
+        router.forward(&mut ()),
+        Err(RouterError::NoForwardRoute)
+    ));
+
+    // Navigating clears the forward history
+    router.back().unwrap();
+    router.navigate(&mut (), "/settings").unwrap();
+    assert!(!router.can_go_forward());
+}
+
+#[test]
+fn forward_with_mounts() {
+    let mut router = router();
+
+    router.navigate(&mut (), "/settings").unwrap();
+    router.navigate(&mut (), "/settings/general").unwrap();
+    router.back().unwrap();
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/"));
+
+    router.forward(&mut ()).unwrap();
+    assert_eq!(router.active_route(), Some("/settings"));
+    router.forward(&mut ()).unwrap();
+    assert_eq!(router.active_route(), Some("/settings/general"));
+    assert!(matches!(
+        router.forward(&mut ()),
+        Err(RouterError::NoForwardRoute)
+    ));
+}
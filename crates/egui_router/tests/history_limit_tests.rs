@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use egui_router::history::{History, HistoryError, HistoryEvent};
+use egui_router::{EguiRouter, HistoryEntry, Request, Route, RouterError};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+/// Records the urls like a browser history, which can't drop entries
+#[derive(Debug, Clone, Default)]
+struct RecordingHistory {
+    /// The urls and the index of the current one
+    entries: Rc<RefCell<(Vec<String>, usize)>>,
+}
+
+impl RecordingHistory {
+    fn current(&self) -> String {
+        let (urls, index) = &*self.entries.borrow();
+        urls[*index].clone()
+    }
+
+    fn len(&self) -> usize {
+        self.entries.borrow().0.len()
+    }
+}
+
+impl History for RecordingHistory {
+    fn update(&mut self, _ctx: &egui::Context) -> impl Iterator<Item = HistoryEvent> + 'static {
+        std::iter::empty()
+    }
+
+    fn active_route(&self) -> Option<(String, Option<u32>)> {
+        None
+    }
+
+    fn push(&mut self, url: &str, _state: u32) -> Result<(), HistoryError> {
+        let (urls, index) = &mut *self.entries.borrow_mut();
+        if !urls.is_empty() {
+            *index += 1;
+        }
+        urls.truncate(*index);
+        urls.push(url.to_owned());
+        Ok(())
+    }
+
+    fn replace(&mut self, url: &str, _state: u32) -> Result<(), HistoryError> {
+        let (urls, index) = &mut *self.entries.borrow_mut();
+        match urls.get_mut(*index) {
+            Some(current) => url.clone_into(current),
+            None => urls.push(url.to_owned()),
+        }
+        Ok(())
+    }
+
+    fn back(&mut self) -> Result<(), HistoryError> {
+        let (_, index) = &mut *self.entries.borrow_mut();
+        *index = index.saturating_sub(1);
+        Ok(())
+    }
+
+    fn forward(&mut self) -> Result<(), HistoryError> {
+        let (urls, index) = &mut *self.entries.borrow_mut();
+        *index = (*index + 1).min(urls.len() - 1);
+        Ok(())
+    }
+}
+
+fn router(history: &RecordingHistory) -> EguiRouter<(), RecordingHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/{page}", page)
+        .history(history.clone())
+        .max_history(3)
+        .default_path("/")
+        .build(&mut ())
+}
+
+fn urls(router: &EguiRouter<(), RecordingHistory>) -> Vec<String> {
+    router.history().map(HistoryEntry::url).collect()
+}
+
+#[test]
+fn max_history() {
+    let history = RecordingHistory::default();
+    let mut router = router(&history);
+
+    for page in ["/a", "/b", "/c", "/d"] {
+        router.navigate(&mut (), page).unwrap();
+    }
+    // The oldest routes were dropped
+    assert_eq!(urls(&router), ["/b", "/c", "/d"]);
+    assert_eq!(history.current(), "/d");
+
+    router.back().unwrap();
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/b"));
+    assert_eq!(history.current(), "/b");
+    assert!(matches!(router.back(), Err(RouterError::NoPreviousRoute)));
+}
+
+#[test]
+fn clear_history() {
+    let history = RecordingHistory::default();
+    let mut router = router(&history);
+
+    router.navigate(&mut (), "/a").unwrap();
+    router.navigate(&mut (), "/b").unwrap();
+    router.back().unwrap();
+    router.clear_history();
+
+    assert_eq!(urls(&router), ["/a"]);
+    assert!(!router.can_go_back());
+    assert!(!router.can_go_forward());
+    assert_eq!(history.current(), "/a");
+}
+
+#[test]
+fn reset_to() {
+    let history = RecordingHistory::default();
+    let mut router = router(&history);
+
+    router.navigate(&mut (), "/a").unwrap();
+    router.navigate(&mut (), "/b").unwrap();
+    let len = history.len();
+    router.reset_to(&mut (), "/start").unwrap();
+
+    assert_eq!(urls(&router), ["/start"]);
+    assert!(!router.can_go_back());
+    // The current entry of the history is replaced
+    assert_eq!(history.current(), "/start");
+    assert_eq!(history.len(), len);
+}
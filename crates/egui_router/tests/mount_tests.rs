@@ -0,0 +1,71 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn child() -> EguiRouter<(), MemoryHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/general", page)
+        .build(&mut ())
+}
+
+#[test]
+fn mount_at_root() {
+    let mut router: EguiRouter<(), MemoryHistory> = EguiRouter::builder()
+        .route("/about", page)
+        .mount("/", child())
+        .default_path("/")
+        .build(&mut ());
+
+    assert_eq!(router.active_route(), Some("/"));
+    assert_eq!(router.matched_route("/general"), Some("/"));
+    assert_eq!(router.matched_route("/about"), Some("/about"));
+
+    router.navigate(&mut (), "/general").unwrap();
+    assert_eq!(router.active_route(), Some("/general"));
+
+    router.navigate(&mut (), "/about").unwrap();
+    assert_eq!(router.active_route(), Some("/about"));
+
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/general"));
+}
+
+#[test]
+fn mount_prefix_trailing_slash() {
+    let mut router: EguiRouter<(), MemoryHistory> = EguiRouter::builder()
+        .route("/", page)
+        .mount("/settings/", child())
+        .default_path("/")
+        .build(&mut ());
+
+    router.navigate(&mut (), "/settings").unwrap();
+    assert_eq!(router.active_route(), Some("/settings"));
+
+    router.navigate(&mut (), "/settings/general").unwrap();
+    assert_eq!(router.active_route(), Some("/settings/general"));
+}
+
+#[test]
+fn back_within_mount() {
+    let mut router: EguiRouter<(), MemoryHistory> = EguiRouter::builder()
+        .route("/", page)
+        .mount("/settings", child())
+        .default_path("/")
+        .build(&mut ());
+
+    router.navigate(&mut (), "/settings").unwrap();
+    router.navigate(&mut (), "/settings/general").unwrap();
+    assert_eq!(router.active_route(), Some("/settings/general"));
+
+    // Going back within the mount only goes back in the mounted router
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/settings"));
+    router.back().unwrap();
+    assert_eq!(router.active_route(), Some("/"));
+}
@@ -0,0 +1,68 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route};
+
+/// How often the handler of the details route ran
+type State = usize;
+
+fn page(_request: Request<State>) -> impl Route<State> {
+    |ui: &mut egui::Ui, _state: &mut State| {
+        ui.label("Page");
+    }
+}
+
+fn details(request: Request<State>) -> impl Route<State> {
+    *request.state += 1;
+    page(request)
+}
+
+fn router(state: &mut State) -> EguiRouter<State, MemoryHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/details", details)
+        .route("/other", page)
+        .default_path("/")
+        .build(state)
+}
+
+#[test]
+fn preloaded_route_is_reused() {
+    let mut state = 0;
+    let mut router = router(&mut state);
+
+    router.preload(&mut state, "/details").unwrap();
+    assert_eq!(state, 1);
+    // Preloading the same route again does nothing
+    router.preload(&mut state, "/details").unwrap();
+    assert_eq!(state, 1);
+
+    router.navigate(&mut state, "/details").unwrap();
+    assert_eq!(router.active_route(), Some("/details"));
+    assert_eq!(state, 1);
+}
+
+#[test]
+fn preloaded_route_is_dropped_on_navigation() {
+    let mut state = 0;
+    let mut router = router(&mut state);
+
+    router.preload(&mut state, "/details").unwrap();
+    router.navigate(&mut state, "/other").unwrap();
+    router.navigate(&mut state, "/details").unwrap();
+    assert_eq!(state, 2);
+}
+
+#[test]
+fn preload_via_handle() {
+    let mut state = 0;
+    let mut router = router(&mut state);
+
+    router.handle().preload("/details");
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| router.ui(ui, &mut state));
+    });
+    assert_eq!(state, 1);
+
+    router.navigate(&mut state, "/details").unwrap();
+    assert_eq!(state, 1);
+}
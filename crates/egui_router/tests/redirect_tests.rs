@@ -0,0 +1,60 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, GuardAction, HistoryEntry, Request, Route, RouteOrRedirect};
+
+/// True once the user finished the onboarding
+type State = bool;
+
+fn page(_request: Request<State>) -> impl Route<State> {
+    |ui: &mut egui::Ui, _state: &mut State| {
+        ui.label("Page");
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)] // Handlers get the request by value
+fn start(request: Request<State>) -> RouteOrRedirect<impl Route<State>> {
+    if *request.state {
+        RouteOrRedirect::Route(|_ui: &mut egui::Ui, _state: &mut State| {})
+    } else {
+        RouteOrRedirect::Redirect("/onboarding".to_owned())
+    }
+}
+
+fn router(state: &mut State) -> EguiRouter<State, MemoryHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/start", start)
+        .route("/onboarding", page)
+        .route("/login", page)
+        .route("/admin", page)
+        .route_redirect("/old", "/start")
+        .guard(|path, _request| {
+            if path == "/admin" {
+                GuardAction::Redirect("/login".to_owned())
+            } else {
+                GuardAction::Allow
+            }
+        })
+        .default_path("/")
+        .build(state)
+}
+
+#[test]
+fn redirects() {
+    let mut state = false;
+    let mut router = router(&mut state);
+
+    // A route redirect, followed by a redirect of the handler
+    router.navigate(&mut state, "/old").unwrap();
+    assert_eq!(router.active_route(), Some("/onboarding"));
+
+    router.navigate(&mut state, "/admin").unwrap();
+    assert_eq!(router.active_route(), Some("/login"));
+
+    state = true;
+    router.navigate(&mut state, "/old").unwrap();
+    assert_eq!(router.active_route(), Some("/start"));
+
+    // Redirects don't add history entries
+    let history: Vec<_> = router.history().map(HistoryEntry::url).collect();
+    assert_eq!(history, ["/", "/onboarding", "/login", "/start"]);
+}
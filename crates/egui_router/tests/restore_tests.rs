@@ -0,0 +1,66 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, HistoryEntry, Request, Route, RouterBuilder};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn builder() -> RouterBuilder<(), MemoryHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .route("/a", page)
+        .route("/b", page)
+        .default_path("/")
+}
+
+fn urls(router: &EguiRouter<(), MemoryHistory>) -> Vec<String> {
+    router.history().map(HistoryEntry::url).collect()
+}
+
+#[test]
+fn save_and_restore() {
+    let mut router = builder().route("/c", page).build(&mut ());
+    router.navigate(&mut (), "/a?tab=2").unwrap();
+    router.navigate(&mut (), "/b").unwrap();
+    router.navigate(&mut (), "/c").unwrap();
+    router.back().unwrap();
+
+    let saved = router.save_state();
+    let mut restored = EguiRouter::restore(&saved, builder().route("/c", page), &mut ());
+    assert_eq!(urls(&restored), ["/", "/a?tab=2", "/b"]);
+    assert_eq!(restored.save_state(), saved);
+
+    restored.forward(&mut ()).unwrap();
+    assert_eq!(restored.active_route(), Some("/c"));
+    restored.back().unwrap();
+    restored.back().unwrap();
+    assert_eq!(restored.active_route(), Some("/a"));
+}
+
+#[test]
+fn restore_removed_routes() {
+    let mut router = builder().route("/c", page).build(&mut ());
+    router.navigate(&mut (), "/c").unwrap();
+    router.navigate(&mut (), "/a").unwrap();
+    router.navigate(&mut (), "/c").unwrap();
+    router.back().unwrap();
+
+    // "/c" isn't registered anymore, so it's skipped in the history and the forward history
+    let restored = EguiRouter::restore(&router.save_state(), builder(), &mut ());
+    assert_eq!(urls(&restored), ["/", "/a"]);
+    assert!(!restored.can_go_forward());
+}
+
+#[test]
+fn restore_nothing() {
+    let mut router = builder().route("/c", page).build(&mut ());
+    router.navigate(&mut (), "/c").unwrap();
+    let mut saved = router.save_state();
+    saved.history.remove(0);
+
+    // If no entry can be restored, the router starts at the default path
+    let restored = EguiRouter::restore(&saved, builder(), &mut ());
+    assert_eq!(urls(&restored), ["/"]);
+}
@@ -0,0 +1,74 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route};
+
+/// The results received via `navigate_for_result`
+type State = Vec<String>;
+
+fn page(_request: Request<State>) -> impl Route<State> {
+    |ui: &mut egui::Ui, _state: &mut State| {
+        ui.label("Page");
+    }
+}
+
+fn on_picked(state: &mut State, picked: String) {
+    state.push(picked);
+}
+
+#[test]
+fn navigate_for_result() {
+    let mut state = State::new();
+    let mut router: EguiRouter<State, MemoryHistory> = EguiRouter::builder()
+        .route("/", page)
+        .route("/pick", page)
+        .default_path("/")
+        .build(&mut state);
+
+    router
+        .navigate_for_result(&mut state, "/pick", on_picked)
+        .unwrap();
+    assert_eq!(router.active_route(), Some("/pick"));
+
+    router
+        .back_with_result(&mut state, "Alice".to_owned())
+        .unwrap();
+    assert_eq!(router.active_route(), Some("/"));
+    assert_eq!(state, ["Alice"]);
+
+    // The callback is only called once, and results of another type are dropped
+    router
+        .navigate_for_result(&mut state, "/pick", on_picked)
+        .unwrap();
+    router.back_with_result(&mut state, 42).unwrap();
+    router.navigate(&mut state, "/pick").unwrap();
+    router
+        .back_with_result(&mut state, "Bob".to_owned())
+        .unwrap();
+    assert_eq!(state, ["Alice"]);
+}
+
+#[test]
+fn navigate_for_result_in_mount() {
+    let mut state = State::new();
+    let settings = EguiRouter::builder()
+        .route("/", page)
+        .route("/pick", page)
+        .default_path("/")
+        .build(&mut state);
+    let mut router: EguiRouter<State, MemoryHistory> = EguiRouter::builder()
+        .route("/", page)
+        .mount("/settings", settings)
+        .default_path("/")
+        .build(&mut state);
+
+    router.navigate(&mut state, "/settings").unwrap();
+    router
+        .navigate_for_result(&mut state, "/settings/pick", on_picked)
+        .unwrap();
+
+    // The callback belongs to the route of the mounted router
+    router
+        .back_with_result(&mut state, "Bob".to_owned())
+        .unwrap();
+    assert_eq!(router.active_route(), Some("/settings"));
+    assert_eq!(state, ["Bob"]);
+}
@@ -0,0 +1,69 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route, RoutePolicy, TransitionConfig};
+
+/// How often the handler of the inbox route ran
+type State = usize;
+
+fn page(_request: Request<State>) -> impl Route<State> {
+    |ui: &mut egui::Ui, _state: &mut State| {
+        ui.label("Page");
+    }
+}
+
+fn inbox(request: Request<State>) -> impl Route<State> {
+    *request.state += 1;
+    page(request)
+}
+
+fn router(state: &mut State, policy: Option<RoutePolicy>) -> EguiRouter<State, MemoryHistory> {
+    let builder = EguiRouter::builder()
+        .route("/", inbox)
+        .route("/message", page)
+        .overlay_route("/dialog", page, TransitionConfig::fade())
+        .default_path("/");
+    match policy {
+        Some(policy) => builder.route_policy("/", policy),
+        None => builder,
+    }
+    .build(state)
+}
+
+/// Shows the router, which rebuilds the active route if needed
+fn frame(router: &mut EguiRouter<State, MemoryHistory>, state: &mut State) {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| router.ui(ui, state));
+    });
+}
+
+#[test]
+fn rebuild_on_return() {
+    let mut state = 0;
+    let mut router = router(&mut state, Some(RoutePolicy::Rebuild));
+    assert_eq!(state, 1);
+
+    router.navigate(&mut state, "/message").unwrap();
+    frame(&mut router, &mut state);
+    router.back().unwrap();
+    frame(&mut router, &mut state);
+    assert_eq!(state, 2);
+
+    // Routes underneath an overlay stay visible, so they are kept alive
+    router.navigate(&mut state, "/dialog").unwrap();
+    frame(&mut router, &mut state);
+    router.back().unwrap();
+    frame(&mut router, &mut state);
+    assert_eq!(state, 2);
+}
+
+#[test]
+fn keep_alive_by_default() {
+    let mut state = 0;
+    let mut router = router(&mut state, None);
+
+    router.navigate(&mut state, "/message").unwrap();
+    frame(&mut router, &mut state);
+    router.back().unwrap();
+    frame(&mut router, &mut state);
+    assert_eq!(state, 1);
+}
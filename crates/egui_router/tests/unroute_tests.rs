@@ -0,0 +1,55 @@
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route, RouterError};
+
+fn page(_request: Request) -> impl Route {
+    |ui: &mut egui::Ui, _state: &mut ()| {
+        ui.label("Page");
+    }
+}
+
+fn router() -> EguiRouter<(), MemoryHistory> {
+    EguiRouter::builder()
+        .route("/", page)
+        .default_path("/")
+        .build(&mut ())
+}
+
+#[test]
+fn unroute() {
+    let mut router = router();
+
+    router.add_route("/plugin", page).unwrap();
+    router.navigate(&mut (), "/plugin").unwrap();
+    assert_eq!(router.active_route(), Some("/plugin"));
+
+    // The open route keeps showing until it's closed
+    assert!(router.unroute("/plugin"));
+    assert_eq!(router.active_route(), Some("/plugin"));
+    router.back().unwrap();
+
+    assert!(matches!(
+        router.navigate(&mut (), "/plugin"),
+        Err(RouterError::NotFound)
+    ));
+    assert_eq!(router.active_route(), Some("/"));
+    assert!(!router.unroute("/plugin"));
+}
+
+#[test]
+fn replace_route() {
+    let mut router = router();
+
+    router.add_route("/plugin", page).unwrap();
+    // Adding a route again swaps its handler
+    router.add_route("/plugin", page).unwrap();
+    router.navigate(&mut (), "/plugin").unwrap();
+    assert_eq!(router.active_route(), Some("/plugin"));
+
+    // A conflicting route is rejected and the existing route is kept
+    router.add_route("/items/{id}", page).unwrap();
+    assert!(matches!(
+        router.add_route("/items/{name}", page),
+        Err(RouterError::InvalidRoute(_))
+    ));
+    assert_eq!(router.matched_route("/items/1"), Some("/items/{id}"));
+}
@@ -0,0 +1,47 @@
+use eframe::{egui, NativeOptions};
+use egui::CentralPanel;
+use egui_inbox::UiInbox;
+
+use egui_snackbar::{Snackbar, SnackbarQueue};
+
+pub fn main() -> eframe::Result<()> {
+    let mut items: Vec<String> = (1..=10).map(|i| format!("Item {i}")).collect();
+    let mut snackbars = SnackbarQueue::new();
+    // The undo callbacks send the deleted items (and their index) back to us
+    let restore_inbox = UiInbox::<(usize, String)>::new();
+
+    eframe::run_simple_native(
+        "Snackbar undo example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                for (index, item) in restore_inbox.read(ui) {
+                    items.insert(index.min(items.len()), item);
+                }
+
+                let mut deleted = None;
+                for (index, item) in items.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(item);
+                        if ui.button("Delete").clicked() {
+                            deleted = Some(index);
+                        }
+                    });
+                }
+
+                if let Some(index) = deleted {
+                    let item = items.remove(index);
+                    let sender = restore_inbox.sender();
+                    snackbars.push(Snackbar::new(format!("Deleted {item}")).action(
+                        "Undo",
+                        move || {
+                            sender.send((index, item)).ok();
+                        },
+                    ));
+                }
+
+                snackbars.show(ctx);
+            });
+        },
+    )
+}
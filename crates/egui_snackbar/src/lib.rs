@@ -0,0 +1,478 @@
+#![doc = include_str!("../README.md")]
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use egui::{Align2, Area, Context, Frame, Id, Order, Sense, Vec2, WidgetText};
+
+/// The time in seconds it takes a snackbar to appear or disappear
+const ANIMATION_TIME: f32 = 0.2;
+/// The distance from the bottom of the screen
+const MARGIN: f32 = 16.0;
+/// How far the snackbar slides in from the bottom
+const SLIDE_DISTANCE: f32 = 24.0;
+const MAX_WIDTH: f32 = 480.0;
+/// The horizontal velocity (in points per second) that dismisses a snackbar when released,
+/// regardless of how far it was swiped
+const SWIPE_VELOCITY: f32 = 500.0;
+
+type Callback = Box<dyn FnOnce()>;
+
+/// A single snackbar, shown via [`SnackbarQueue::push`].
+pub struct Snackbar {
+    text: WidgetText,
+    action: Option<(WidgetText, Callback)>,
+    on_dismiss: Option<Callback>,
+    duration: Option<f32>,
+}
+
+impl Debug for Snackbar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Snackbar")
+            .field("text", &self.text.text())
+            .field("duration", &self.duration)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Snackbar {
+    /// Create a new snackbar showing `text`.
+    pub fn new(text: impl Into<WidgetText>) -> Self {
+        Self {
+            text: text.into(),
+            action: None,
+            on_dismiss: None,
+            duration: None,
+        }
+    }
+
+    /// Add an action button, e.g. "Undo". The callback is called when the button is clicked,
+    /// after which the snackbar is dismissed.
+    pub fn action(
+        mut self,
+        label: impl Into<WidgetText>,
+        callback: impl FnOnce() + 'static,
+    ) -> Self {
+        self.action = Some((label.into(), Box::new(callback)));
+        self
+    }
+
+    /// Called when the snackbar is dismissed without its action being clicked, e.g. because it
+    /// timed out or was swiped away. Useful to e.g. only delete an item for good once it can't be
+    /// restored anymore.
+    pub fn on_dismiss(mut self, callback: impl FnOnce() + 'static) -> Self {
+        self.on_dismiss = Some(Box::new(callback));
+        self
+    }
+
+    /// Set how many seconds the snackbar is shown before it is dismissed automatically.
+    /// Defaults to [`SnackbarQueue::default_duration`].
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    /// Never dismiss the snackbar automatically. It can still be dismissed via its action,
+    /// by swiping it away or via [`SnackbarQueue::dismiss`].
+    pub fn persistent(self) -> Self {
+        self.duration(f32::INFINITY)
+    }
+}
+
+#[derive(Debug)]
+struct ActiveSnackbar {
+    snackbar: Snackbar,
+    id: Id,
+    /// Seconds left until the snackbar is dismissed
+    remaining: f32,
+    last_time: f64,
+    visibility: f32,
+    /// The horizontal offset while swiping
+    offset: f32,
+    closing: bool,
+}
+
+impl ActiveSnackbar {
+    /// Starts hiding the snackbar. Calls the dismiss callback, unless the action was clicked.
+    fn close(&mut self, action_clicked: bool) {
+        if self.closing {
+            return;
+        }
+        self.closing = true;
+        if !action_clicked {
+            if let Some(on_dismiss) = self.snackbar.on_dismiss.take() {
+                on_dismiss();
+            }
+        }
+    }
+}
+
+/// A queue of [`Snackbar`]s. Only a single snackbar is visible at a time, the next one is shown
+/// once the current one is dismissed.
+///
+/// Store this in your app state, add snackbars via [`SnackbarQueue::push`] and call
+/// [`SnackbarQueue::show`] once per frame.
+#[derive(Debug)]
+pub struct SnackbarQueue {
+    queue: VecDeque<Snackbar>,
+    current: Option<ActiveSnackbar>,
+    id: Id,
+    default_duration: f32,
+    count: u64,
+}
+
+impl Default for SnackbarQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnackbarQueue {
+    /// Create a new, empty queue.
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            current: None,
+            id: Id::new("egui_snackbar"),
+            default_duration: 4.0,
+            count: 0,
+        }
+    }
+
+    /// Set the id salt. Only needed if you show multiple queues at once.
+    pub fn id_salt(mut self, id_salt: impl Hash) -> Self {
+        self.id = Id::new(id_salt);
+        self
+    }
+
+    /// Set how many seconds snackbars are shown before they are dismissed automatically.
+    /// Can be overridden per snackbar via [`Snackbar::duration`]. The default is 4 seconds.
+    pub fn default_duration(mut self, seconds: f32) -> Self {
+        self.default_duration = seconds;
+        self
+    }
+
+    /// Add a snackbar to the end of the queue.
+    pub fn push(&mut self, snackbar: Snackbar) {
+        self.queue.push_back(snackbar);
+    }
+
+    /// The number of snackbars that haven't been dismissed yet, including the visible one.
+    pub fn len(&self) -> usize {
+        self.queue.len() + usize::from(self.current.as_ref().is_some_and(|c| !c.closing))
+    }
+
+    /// Returns true if no snackbar is visible or queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Dismiss the visible snackbar, showing the next one in the queue.
+    pub fn dismiss(&mut self) {
+        if let Some(current) = &mut self.current {
+            current.close(false);
+        }
+    }
+
+    /// Dismiss the visible snackbar and remove all queued ones.
+    /// The [`Snackbar::on_dismiss`] callbacks of the removed snackbars are called as well.
+    pub fn clear(&mut self) {
+        self.dismiss();
+        for snackbar in self.queue.drain(..) {
+            if let Some(on_dismiss) = snackbar.on_dismiss {
+                on_dismiss();
+            }
+        }
+    }
+
+    /// Show the current snackbar at the bottom of the screen. Call this once per frame.
+    pub fn show(&mut self, ctx: &Context) {
+        let now = ctx.input(|i| i.time);
+
+        if self.current.is_none() {
+            if let Some(snackbar) = self.queue.pop_front() {
+                self.count += 1;
+                self.current = Some(ActiveSnackbar {
+                    remaining: snackbar.duration.unwrap_or(self.default_duration),
+                    snackbar,
+                    id: self.id.with(self.count),
+                    last_time: now,
+                    visibility: 0.0,
+                    offset: 0.0,
+                    closing: false,
+                });
+            }
+        }
+        let Some(current) = &mut self.current else {
+            return;
+        };
+
+        let dt = (now - current.last_time) as f32;
+        current.last_time = now;
+
+        let target = if current.closing { 0.0 } else { 1.0 };
+        current.visibility = if egui_animation::reduce_motion(ctx) {
+            target
+        } else if current.closing {
+            (current.visibility - dt / ANIMATION_TIME).max(target)
+        } else {
+            (current.visibility + dt / ANIMATION_TIME).min(target)
+        };
+
+        if current.closing && current.visibility <= 0.0 {
+            self.current = None;
+            // Show the next snackbar
+            ctx.request_repaint();
+            return;
+        }
+
+        let mut action_clicked = false;
+        // The area only senses drags (for swiping) and interacts before its content, so the
+        // action button is on top of it and can still be clicked
+        let area = Area::new(current.id)
+            .order(Order::Foreground)
+            .sense(Sense::drag())
+            .anchor(
+                Align2::CENTER_BOTTOM,
+                Vec2::new(
+                    current.offset,
+                    -MARGIN + (1.0 - current.visibility) * SLIDE_DISTANCE,
+                ),
+            )
+            .show(ctx, |ui| {
+                ui.set_opacity(current.visibility);
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(MAX_WIDTH);
+                    ui.horizontal(|ui| {
+                        ui.label(current.snackbar.text.clone());
+                        if let Some((label, _)) = &current.snackbar.action {
+                            if ui.button(label.clone()).clicked() {
+                                action_clicked = true;
+                            }
+                        }
+                    });
+                });
+            });
+        let swipe = area.response;
+
+        if action_clicked {
+            if let Some((_, callback)) = current.snackbar.action.take() {
+                callback();
+            }
+            current.close(true);
+        }
+
+        if swipe.dragged() {
+            current.offset += swipe.drag_delta().x;
+        } else if swipe.drag_stopped() {
+            let velocity = ctx.input(|i| i.pointer.velocity().x);
+            if current.offset.abs() > swipe.rect.width() * 0.4
+                || (velocity.abs() > SWIPE_VELOCITY && velocity.signum() == current.offset.signum())
+            {
+                current.close(false);
+            }
+        } else if !current.closing {
+            // Move back into place after an incomplete swipe
+            current.offset *= (1.0 - dt / ANIMATION_TIME).max(0.0);
+            if current.offset.abs() < 0.5 {
+                current.offset = 0.0;
+            }
+        }
+
+        let paused = swipe.contains_pointer() || swipe.dragged();
+        if !paused && !current.closing {
+            current.remaining -= dt;
+            if current.remaining <= 0.0 {
+                current.close(false);
+            }
+        }
+
+        if current.closing || current.visibility < 1.0 || current.offset != 0.0 || paused {
+            ctx.request_repaint();
+        } else if current.remaining.is_finite() {
+            ctx.request_repaint_after_secs(current.remaining);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use egui::{Context, Event, Order, PointerButton, Pos2, RawInput, Rect, Vec2};
+
+    use super::{Snackbar, SnackbarQueue};
+
+    /// Shows the queue for `seconds`, advancing the time in steps of 50 ms
+    fn run(ctx: &Context, queue: &mut SnackbarQueue, time: &mut f64, seconds: f64) {
+        let end = *time + seconds;
+        while *time < end {
+            *time += 0.05;
+            frame(ctx, queue, *time, vec![]);
+        }
+    }
+
+    fn frame(ctx: &Context, queue: &mut SnackbarQueue, time: f64, events: Vec<Event>) {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::splat(800.0))),
+            time: Some(time),
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| queue.show(ctx));
+    }
+
+    fn current_text(queue: &SnackbarQueue) -> Option<String> {
+        queue
+            .current
+            .as_ref()
+            .map(|current| current.snackbar.text.text().to_owned())
+    }
+
+    fn visible_snackbars(ctx: &Context) -> usize {
+        ctx.memory(|memory| memory.areas().visible_layer_ids())
+            .iter()
+            .filter(|layer| layer.order == Order::Foreground)
+            .count()
+    }
+
+    /// Returns a callback that counts how often it was called
+    fn counter() -> (Rc<Cell<u32>>, impl FnOnce() + 'static) {
+        let count = Rc::new(Cell::new(0));
+        let callback = {
+            let count = count.clone();
+            move || count.set(count.get() + 1)
+        };
+        (count, callback)
+    }
+
+    #[test]
+    fn shows_snackbars_in_order_one_at_a_time() {
+        let ctx = Context::default();
+        let mut time = 0.0;
+        let mut queue = SnackbarQueue::new().default_duration(1.0);
+        for text in ["a", "b", "c"] {
+            queue.push(Snackbar::new(text));
+        }
+
+        let mut shown = vec![];
+        while time < 10.0 {
+            run(&ctx, &mut queue, &mut time, 0.05);
+            assert!(visible_snackbars(&ctx) <= 1);
+            if let Some(text) = current_text(&queue) {
+                if shown.last() != Some(&text) {
+                    shown.push(text);
+                }
+            }
+        }
+
+        assert_eq!(shown, ["a", "b", "c"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn dismissed_after_duration() {
+        let ctx = Context::default();
+        let mut time = 0.0;
+        let mut queue = SnackbarQueue::new();
+        let (dismissed, on_dismiss) = counter();
+        queue.push(Snackbar::new("a").duration(2.0).on_dismiss(on_dismiss));
+
+        run(&ctx, &mut queue, &mut time, 1.5);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(visible_snackbars(&ctx), 1);
+        assert_eq!(dismissed.get(), 0);
+
+        run(&ctx, &mut queue, &mut time, 1.0);
+        assert!(queue.is_empty());
+        assert_eq!(dismissed.get(), 1);
+
+        // Hidden once the animation is done
+        run(&ctx, &mut queue, &mut time, 1.0);
+        assert!(queue.current.is_none());
+        assert_eq!(visible_snackbars(&ctx), 0);
+
+        queue.dismiss();
+        queue.clear();
+        assert_eq!(dismissed.get(), 1);
+    }
+
+    #[test]
+    fn persistent_snackbar_is_not_dismissed() {
+        let ctx = Context::default();
+        let mut time = 0.0;
+        let mut queue = SnackbarQueue::new();
+        queue.push(Snackbar::new("a").persistent());
+
+        run(&ctx, &mut queue, &mut time, 30.0);
+        assert_eq!(current_text(&queue).as_deref(), Some("a"));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn dismiss_callback_called_once() {
+        let ctx = Context::default();
+        let mut time = 0.0;
+        let mut queue = SnackbarQueue::new();
+        let (first, on_first) = counter();
+        let (second, on_second) = counter();
+        queue.push(Snackbar::new("a").persistent().on_dismiss(on_first));
+        queue.push(Snackbar::new("b").persistent().on_dismiss(on_second));
+
+        run(&ctx, &mut queue, &mut time, 0.5);
+        queue.dismiss();
+        queue.dismiss();
+        run(&ctx, &mut queue, &mut time, 0.5);
+        assert_eq!(first.get(), 1);
+        assert_eq!(current_text(&queue).as_deref(), Some("b"));
+
+        queue.clear();
+        run(&ctx, &mut queue, &mut time, 0.5);
+        assert_eq!((first.get(), second.get()), (1, 1));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn action_callback_called_once() {
+        let ctx = Context::default();
+        let mut time = 0.0;
+        let mut queue = SnackbarQueue::new();
+        let (action, on_action) = counter();
+        let (dismissed, on_dismiss) = counter();
+        queue.push(
+            Snackbar::new("a")
+                .persistent()
+                .action("Undo", on_action)
+                .on_dismiss(on_dismiss),
+        );
+        run(&ctx, &mut queue, &mut time, 0.5);
+
+        // The action button is the last widget in the snackbar
+        let id = queue.current.as_ref().unwrap().id;
+        let rect = ctx.memory(|memory| memory.area_rect(id)).unwrap();
+        let margin = ctx.style().spacing.menu_margin.right;
+        let pos = Pos2::new(rect.right() - f32::from(margin) - 2.0, rect.center().y);
+        for pressed in [true, false, true, false] {
+            time += 0.05;
+            let events = vec![
+                Event::PointerMoved(pos),
+                Event::PointerButton {
+                    pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: egui::Modifiers::NONE,
+                },
+            ];
+            frame(&ctx, &mut queue, time, events);
+        }
+        run(&ctx, &mut queue, &mut time, 0.5);
+
+        assert_eq!(action.get(), 1);
+        assert_eq!(dismissed.get(), 0);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::{Debug, Display};
+
+use egui::{Button, Response, Ui, WidgetText};
+
+use egui_inbox::UiInbox;
+use hello_egui_utils::{asyncify, CallbackType, MaybeSend, MaybeSync};
+
+/// The status of an [`ActionState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionStatus<T, E> {
+    /// The action hasn't been started yet.
+    Idle,
+    /// The action is currently running.
+    Pending,
+    /// The action completed successfully.
+    Success(T),
+    /// The action failed.
+    Error(E),
+}
+
+/// Tracks the state of a one-shot action, like saving or deleting something.
+///
+/// Where [`crate::EguiSuspense`] is meant for loading data that is required to show a ui,
+/// this is meant for actions that are triggered by the user, e.g. via a button.
+///
+/// Example:
+/// ```no_run
+/// # use egui::Ui;
+/// # use egui_suspense::ActionState;
+/// # use hello_egui_utils::CallbackType;
+/// fn save(callback: CallbackType<Result<(), String>>) {
+///     // Do the actual work, e.g. in a background thread
+///     callback(Ok(()));
+/// }
+///
+/// fn ui(ui: &mut Ui, action: &mut ActionState<()>) {
+///     if action.button(ui, "Save").clicked() {
+///         action.run(save);
+///     }
+/// }
+/// ```
+pub struct ActionState<T, E: Display + Debug = String> {
+    inbox: UiInbox<Result<T, E>>,
+    status: ActionStatus<T, E>,
+}
+
+impl<T: Debug, E: Display + Debug> Debug for ActionState<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionState")
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeSync + 'static>
+    Default for ActionState<T, E>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeSync + 'static>
+    ActionState<T, E>
+{
+    /// Create a new, idle action.
+    pub fn new() -> Self {
+        Self {
+            inbox: UiInbox::new(),
+            status: ActionStatus::Idle,
+        }
+    }
+
+    asyncify!(
+        /// Start the action.
+        /// If the action is already running, the result of the previous run will be discarded.
+        run,
+        callback_once: (impl FnOnce(CallbackType<Result<T, E>>) + MaybeSend + MaybeSync + 'static),
+        call_prefix: (Self::),
+        generics: (),
+        async_generics: (<F: std::future::Future<Output = Result<T, E>> + MaybeSend + MaybeSync + 'static>),
+        parameters: (),
+        future: F,
+        return_type: (()),
+        body: |(&mut self,)| {
+            // Create a new inbox so the result of a previous run can't overwrite this one
+            self.inbox = UiInbox::new();
+            self.status = ActionStatus::Pending;
+            let tx = self.inbox.sender();
+            callback_once(Box::new(move |result| {
+                tx.send(result).ok();
+            }));
+        },
+    );
+
+    /// Update the status with the result of the action, if it completed.
+    /// This is called by [`ActionState::button`], if you don't use that, call this once per frame.
+    pub fn update(&mut self, ui: &Ui) {
+        if let Some(result) = self.inbox.read(ui).last() {
+            self.status = match result {
+                Ok(data) => ActionStatus::Success(data),
+                Err(err) => ActionStatus::Error(err),
+            };
+        }
+    }
+
+    /// Show a button that is disabled while the action is pending.
+    /// While pending, a spinner is shown next to the button. If the action failed,
+    /// the error is shown below the button.
+    /// Returns the response of the button, so you can start the action when it was clicked.
+    pub fn button(&mut self, ui: &mut Ui, text: impl Into<WidgetText>) -> Response {
+        self.update(ui);
+
+        let pending = self.is_pending();
+        let response = ui
+            .horizontal(|ui| {
+                let response = ui.add_enabled(!pending, Button::new(text));
+                if pending {
+                    ui.spinner();
+                }
+                response
+            })
+            .inner;
+
+        if let ActionStatus::Error(err) = &self.status {
+            ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+        }
+
+        response
+    }
+
+    /// Returns the current status of the action.
+    pub fn status(&self) -> &ActionStatus<T, E> {
+        &self.status
+    }
+
+    /// Returns true if the action is currently running.
+    pub fn is_pending(&self) -> bool {
+        matches!(self.status, ActionStatus::Pending)
+    }
+
+    /// Returns the result if the action completed successfully.
+    pub fn success(&self) -> Option<&T> {
+        match &self.status {
+            ActionStatus::Success(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the error if the action failed.
+    pub fn error(&self) -> Option<&E> {
+        match &self.status {
+            ActionStatus::Error(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Reset the action to [`ActionStatus::Idle`].
+    /// The result of a currently running action will be discarded.
+    pub fn reset(&mut self) {
+        self.inbox = UiInbox::new();
+        self.status = ActionStatus::Idle;
+    }
+}
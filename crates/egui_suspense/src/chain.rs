@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use egui::mutex::Mutex;
+use egui::Ui;
+use egui_inbox::{UiInbox, UiInboxSender};
+use hello_egui_utils::{CallbackType, MaybeSend, MaybeSync};
+
+use crate::types::{ChainFn, ReloadFn};
+
+/// The first stage of a suspense created via [`crate::EguiSuspense::and_then`], type erased so
+/// the suspense can poll it
+pub(crate) trait ChainStage: MaybeSend + MaybeSync {
+    /// Forwards the result of the first stage's initial load, once it is done
+    fn poll(&self, ui: &Ui);
+
+    /// Returns false if reloading can't load anything, because the first stage failed and can't
+    /// be reloaded
+    fn reloadable(&self) -> bool;
+}
+
+/// The inbox of the first stage's running load and the sender of the chained suspense
+type Initial<T, U, E> = (UiInbox<Result<T, E>>, UiInboxSender<Result<U, E>>);
+
+pub(crate) struct Chain<T, U, E> {
+    first: Mutex<Option<Box<ReloadFn<T, E>>>>,
+    /// Whether the first stage can be reloaded, i.e. `first` is set
+    first_reloadable: bool,
+    second: Mutex<Box<ChainFn<T, U, E>>>,
+    /// The output of the first stage. It is kept until the second stage succeeded, so a retry
+    /// only re-runs the second stage. If the first stage can't be reloaded, it is kept forever.
+    loaded: Mutex<Option<T>>,
+    /// The first stage's load that was already running when the chain was created
+    initial: Mutex<Option<Initial<T, U, E>>>,
+    /// The first stage of the first stage, if it was created via `and_then` as well
+    upstream: Option<Box<dyn ChainStage>>,
+}
+
+impl<T, U, E> Chain<T, U, E>
+where
+    T: Clone + MaybeSend + MaybeSync + 'static,
+    U: MaybeSend + MaybeSync + 'static,
+    E: MaybeSend + MaybeSync + 'static,
+{
+    pub(crate) fn new(
+        first: Option<Box<ReloadFn<T, E>>>,
+        second: Box<ChainFn<T, U, E>>,
+        initial: Option<Initial<T, U, E>>,
+        upstream: Option<Box<dyn ChainStage>>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            first_reloadable: first.is_some(),
+            first: Mutex::new(first),
+            second: Mutex::new(second),
+            loaded: Mutex::new(None),
+            initial: Mutex::new(initial),
+            upstream,
+        })
+    }
+
+    /// Loads both stages, or only the second one if the first one already succeeded
+    pub(crate) fn load(self: &Arc<Self>, callback: CallbackType<Result<U, E>>) {
+        let loaded = self.loaded.lock().clone();
+        if let Some(value) = loaded {
+            self.load_second(value, callback);
+            return;
+        }
+
+        if let Some(first) = &mut *self.first.lock() {
+            let chain = self.clone();
+            first(Box::new(move |result| match result {
+                Ok(value) => chain.load_second(value, callback),
+                Err(err) => callback(Err(err)),
+            }));
+        }
+    }
+
+    pub(crate) fn load_second(self: &Arc<Self>, value: T, callback: CallbackType<Result<U, E>>) {
+        *self.loaded.lock() = Some(value.clone());
+        let chain = self.clone();
+        (self.second.lock())(
+            value,
+            Box::new(move |result| {
+                if result.is_ok() && chain.first_reloadable {
+                    *chain.loaded.lock() = None;
+                }
+                callback(result);
+            }),
+        );
+    }
+}
+
+impl<T, U, E> ChainStage for Arc<Chain<T, U, E>>
+where
+    T: Clone + MaybeSend + MaybeSync + 'static,
+    U: MaybeSend + MaybeSync + 'static,
+    E: MaybeSend + MaybeSync + 'static,
+{
+    fn poll(&self, ui: &Ui) {
+        if let Some(upstream) = &self.upstream {
+            upstream.poll(ui);
+        }
+
+        let mut initial = self.initial.lock();
+        let result = initial
+            .as_ref()
+            .and_then(|(inbox, _)| inbox.read(ui).last());
+        if let Some(result) = result {
+            if let Some((_, tx)) = initial.take() {
+                drop(initial);
+                match result {
+                    Ok(value) => self.load_second(
+                        value,
+                        Box::new(move |result| {
+                            tx.send(result).ok();
+                        }),
+                    ),
+                    Err(err) => {
+                        tx.send(Err(err)).ok();
+                    }
+                }
+            }
+        }
+    }
+
+    fn reloadable(&self) -> bool {
+        self.loaded.lock().is_some()
+            || (self.first_reloadable
+                && self
+                    .upstream
+                    .as_ref()
+                    .is_none_or(|upstream| upstream.reloadable()))
+    }
+}
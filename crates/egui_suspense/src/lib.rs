@@ -2,12 +2,19 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod action;
+mod chain;
+
 use std::fmt::{Debug, Display};
 
-use egui::Ui;
+use egui::{Context, Id, Ui};
 
+use chain::{Chain, ChainStage};
 use egui_inbox::UiInbox;
 use hello_egui_utils::{asyncify, CallbackType, MaybeSend, MaybeSync};
+
+pub use action::{ActionState, ActionStatus};
+
 #[cfg(target_arch = "wasm32")]
 mod types {
     use crate::State;
@@ -17,7 +24,9 @@ mod types {
     pub type ReloadFn<T, E> = dyn FnMut(Box<CallbackFn<Result<T, E>>>);
     pub type ErrorUiFn<E> = dyn Fn(&mut Ui, &E, &mut State<'_>);
     pub type LoadingUiFn = dyn Fn(&mut Ui);
+    pub type OfflineUiFn = dyn Fn(&mut Ui);
     pub type ReloadFnRef<'a> = &'a mut (dyn FnMut());
+    pub type ChainFn<T, U, E> = dyn FnMut(T, Box<CallbackFn<Result<U, E>>>);
 }
 #[cfg(not(target_arch = "wasm32"))]
 mod types {
@@ -28,10 +37,36 @@ mod types {
     pub type ReloadFn<T, E> = dyn FnMut(Box<CallbackFn<Result<T, E>>>) + Send + Sync;
     pub type ErrorUiFn<E> = dyn Fn(&mut Ui, &E, &mut State<'_>) + Send + Sync;
     pub type LoadingUiFn = dyn Fn(&mut Ui) + Send + Sync;
+    pub type OfflineUiFn = dyn Fn(&mut Ui) + Send + Sync;
     pub type ReloadFnRef<'a> = &'a mut (dyn FnMut() + Send + Sync);
+    pub type ChainFn<T, U, E> = dyn FnMut(T, Box<CallbackFn<Result<U, E>>>) + Send + Sync;
+}
+
+use types::{ErrorUiFn, LoadingUiFn, OfflineUiFn, ReloadFn, ReloadFnRef};
+
+fn offline_id() -> Id {
+    Id::new("egui_suspense_offline")
 }
 
-use types::{ErrorUiFn, LoadingUiFn, ReloadFn, ReloadFnRef};
+/// Mark all suspenses as offline, e.g. when your app detects that the network connection was lost.
+///
+/// While offline, reloads and retries are postponed, and suspenses whose data failed to load
+/// show their offline ui (see [`EguiSuspense::offline_ui`]) instead of the error ui.
+/// Once this is set to false again, the postponed reloads run and reloadable suspenses that
+/// failed to load retry automatically.
+///
+/// Loads that are already running when going offline can't be paused, since the running future
+/// or callback can't be interrupted. They finish as usual, and if they fail while offline, the
+/// offline ui is shown and they are retried once online again.
+pub fn set_offline(ctx: &Context, offline: bool) {
+    ctx.data_mut(|data| data.insert_temp(offline_id(), offline));
+    ctx.request_repaint();
+}
+
+/// Returns true if the suspenses are marked as offline. See [`set_offline`].
+pub fn is_offline(ctx: &Context) -> bool {
+    ctx.data(|data| data.get_temp(offline_id()).unwrap_or(false))
+}
 
 /// Helper struct to call the reload function.
 pub struct State<'a> {
@@ -54,9 +89,17 @@ pub struct EguiSuspense<T, E: Display + Debug = String> {
     data: Option<Result<T, E>>,
 
     reload_fn: Option<Box<ReloadFn<T, E>>>,
+    /// The first stage, if this suspense was created via [`EguiSuspense::and_then`]
+    chain: Option<Box<dyn ChainStage>>,
 
     error_ui: Option<Box<ErrorUiFn<E>>>,
     loading_ui: Option<Box<LoadingUiFn>>,
+    offline_ui: Option<Box<OfflineUiFn>>,
+
+    /// Whether we were offline when the suspense was last shown, see [`set_offline`]
+    offline: bool,
+    /// A reload was requested while offline, it's run once we're online again
+    reload_postponed: bool,
 }
 
 impl<T: Debug, E: Display + Debug> Debug for EguiSuspense<T, E> {
@@ -90,10 +133,14 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
                 data: None,
 
                 reload_fn: Some(Box::new(callback_mut)),
+                chain: None,
                 error_ui: None,
                 loading_ui: Some(Box::new(|ui| {
                     ui.spinner();
                 })),
+                offline_ui: None,
+                offline: false,
+                reload_postponed: false,
             }
         },
     );
@@ -119,14 +166,40 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
                 data: None,
 
                 reload_fn: None,
+                chain: None,
                 error_ui: None,
                 loading_ui: Some(Box::new(|ui| {
                     ui.spinner();
                 })),
+                offline_ui: None,
+                offline: false,
+                reload_postponed: false,
             }
         },
     );
 
+    /// Create a new suspense from a future, e.g. the result of calling an async fn.
+    /// The future is spawned on the async runtime right away (tokio on native, the browser on wasm).
+    /// This is the same as [`Self::single_try_async`], so the data can't be reloaded.
+    /// Use [`Self::reloadable_async`] if you need that.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use egui_suspense::EguiSuspense;
+    /// async fn load_user() -> Result<String, String> {
+    ///     Ok("Ferris".to_string())
+    /// }
+    ///
+    /// let suspense = EguiSuspense::from_future(load_user());
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn from_future<F>(future: F) -> Self
+    where
+        F: std::future::Future<Output = Result<T, E>> + MaybeSend + MaybeSync + 'static,
+    {
+        Self::single_try_async(future)
+    }
+
     /// Create a new suspense that is already loaded.
     pub fn loaded(data: T) -> Self {
         Self {
@@ -134,8 +207,12 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
             data: Some(Ok(data)),
 
             reload_fn: None,
+            chain: None,
             error_ui: None,
             loading_ui: None,
+            offline_ui: None,
+            offline: false,
+            reload_postponed: false,
         }
     }
 
@@ -164,6 +241,14 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
         self
     }
 
+    /// Use this to customize the ui that is shown while offline (see [`set_offline`]), instead of
+    /// the error ui if the data failed to load, or instead of the loading ui if a reload was
+    /// postponed.
+    pub fn offline_ui(mut self, f: impl Fn(&mut Ui) + 'static + MaybeSend + MaybeSync) -> Self {
+        self.offline_ui = Some(Box::new(f));
+        self
+    }
+
     /// Show the actual ui.
     /// The content closure will be called with the data and a [State] struct.
     pub fn ui<R>(
@@ -173,56 +258,56 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
     ) -> Option<R> {
         let mut result = None;
 
+        let offline = is_offline(ui.ctx());
+        let reconnected = self.offline && !offline;
+        self.offline = offline;
+        if reconnected && (self.reload_postponed || self.has_error()) {
+            self.reload_postponed = false;
+            self.reload();
+        }
+
+        if let Some(chain) = &self.chain {
+            chain.poll(ui);
+        }
         if let Some(result) = self.inbox.read(ui).last() {
             self.data = Some(result);
         }
 
-        let mut clear_data = false;
-        let clear_data_ref = &mut clear_data;
+        let reloadable = self.can_reload();
+        let mut reload = false;
+        let reload_ref = &mut reload;
 
         match &mut self.data {
+            None if offline && self.reload_postponed => {
+                Self::show_offline_ui(self.offline_ui.as_deref(), ui);
+            }
             None => {
                 if let Some(loading_ui) = &mut self.loading_ui {
                     loading_ui(ui);
                 }
             }
             Some(Ok(data)) => {
-                let tx = self.inbox.sender();
                 result = Some(content(
                     ui,
                     data,
                     &mut State {
-                        reloadable: self.reload_fn.is_some(),
-                        reload_fn: &mut || {
-                            if let Some(reload_fn) = &mut self.reload_fn {
-                                *clear_data_ref = true;
-                                let tx = tx.clone();
-                                reload_fn(Box::new(move |result| {
-                                    tx.send(result).ok();
-                                }));
-                            }
-                        },
+                        reloadable,
+                        reload_fn: &mut || *reload_ref = true,
                     },
                 ));
             }
+            Some(Err(_)) if offline => {
+                Self::show_offline_ui(self.offline_ui.as_deref(), ui);
+            }
             Some(Err(err)) => {
                 if let Some(err_ui) = &mut self.error_ui {
-                    let tx = self.inbox.sender();
-
-                    if let Some(reload) = &mut self.reload_fn {
+                    if reloadable {
                         err_ui(
                             ui,
                             err,
                             &mut State {
                                 reloadable: true,
-                                reload_fn: &mut move || {
-                                    *clear_data_ref = true;
-
-                                    let inbox = tx.clone();
-                                    reload(Box::new(move |result| {
-                                        inbox.send(result).ok();
-                                    }));
-                                },
+                                reload_fn: &mut || *reload_ref = true,
                             },
                         );
                     } else {
@@ -240,29 +325,41 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
                     ui.group(|ui| {
                         ui.label(err.to_string());
                     });
-                    if let Some(retry_fn) = &mut self.reload_fn {
-                        if ui.button("Retry").clicked() {
-                            self.data = None;
-                            let tx = self.inbox.sender();
-                            retry_fn(Box::new(move |result| {
-                                tx.send(result).ok();
-                            }));
-                        }
+                    if reloadable && ui.button("Retry").clicked() {
+                        *reload_ref = true;
                     }
                 }
             }
         }
 
-        if clear_data {
-            self.data = None;
+        if reload {
+            self.reload();
         }
 
         result
     }
 
+    fn show_offline_ui(offline_ui: Option<&OfflineUiFn>, ui: &mut Ui) {
+        if let Some(offline_ui) = offline_ui {
+            offline_ui(ui);
+        } else {
+            ui.label("You are offline.");
+            ui.label("Loading will continue once you are back online.");
+        }
+    }
+
     /// Reload the data.
     /// If this is a [`Self::single_try`], this does nothing.
+    /// While offline (see [`set_offline`]), the reload is postponed until we're online again.
     pub fn reload(&mut self) {
+        if !self.can_reload() {
+            return;
+        }
+        if self.offline {
+            self.data = None;
+            self.reload_postponed = true;
+            return;
+        }
         if let Some(reload_fn) = &mut self.reload_fn {
             self.data = None;
             let tx = self.inbox.sender();
@@ -272,9 +369,22 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
         }
     }
 
+    /// Returns false if reloading wouldn't load anything, e.g. for a [`Self::single_try`], or if
+    /// the first stage of an [`Self::and_then`] chain failed and can't be reloaded.
+    fn can_reload(&self) -> bool {
+        self.reload_fn.is_some() && self.chain.as_ref().is_none_or(|chain| chain.reloadable())
+    }
+
     /// Returns true if the data is loading.
+    /// A reload that is postponed while offline (see [`Self::reload_postponed`]) isn't loading.
     pub fn loading(&self) -> bool {
-        self.data.is_none()
+        self.data.is_none() && !self.reload_postponed
+    }
+
+    /// Returns true if a reload was requested while offline and runs once we're online again,
+    /// see [`set_offline`].
+    pub fn reload_postponed(&self) -> bool {
+        self.reload_postponed
     }
 
     /// Returns true if the data failed to load.
@@ -297,3 +407,78 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
         self.data.as_ref().and_then(|r| r.as_ref().err())
     }
 }
+
+impl<
+        T: Clone + MaybeSend + MaybeSync + 'static,
+        E: Display + Debug + MaybeSend + MaybeSync + 'static,
+    > EguiSuspense<T, E>
+{
+    asyncify!(
+        /// Load more data that depends on the data of this suspense, e.g. load a user and then
+        /// the user's orders. The loader is called with the loaded data once it is available.
+        ///
+        /// The returned suspense is loading until both stages are done and shows the error of
+        /// whichever stage failed. Retrying only re-runs the failed stage: if the second stage
+        /// failed, it is called again with the data the first stage loaded before. Reloading after
+        /// both stages succeeded re-runs both.
+        ///
+        /// The loading and error ui of this suspense are kept.
+        /// If this suspense can't be reloaded (e.g. a [`Self::single_try`]), only the second stage
+        /// is re-run on reload, and a failure of the first stage can't be retried.
+        ///
+        /// Example:
+        /// ```
+        /// # use egui_suspense::EguiSuspense;
+        /// let orders: EguiSuspense<Vec<String>> = EguiSuspense::reloadable(|cb| {
+        ///     cb(Ok("Ferris".to_string()));
+        /// })
+        /// .and_then(|user, cb| {
+        ///     cb(Ok(vec![format!("{user}'s crab cakes")]));
+        /// });
+        /// ```
+        and_then,
+        callback_mut: (impl FnMut(CallbackType<Result<U, E>>, value: T,) + MaybeSend + MaybeSync + 'static),
+        call_prefix: (Self::),
+        generics: (<U: MaybeSend + MaybeSync + 'static>),
+        async_generics: (<U: MaybeSend + MaybeSync + 'static, F: std::future::Future<Output = Result<U, E>> + MaybeSend + 'static>),
+        parameters: (),
+        future: impl FnMut(T) -> F + MaybeSend + MaybeSync + 'static,
+        return_type: (EguiSuspense<U, E>),
+        body: |(self,)| {
+            let inbox = UiInbox::new();
+            let tx = inbox.sender();
+            let mut data = None;
+            let mut initial = None;
+            let mut loaded = None;
+            match self.data {
+                Some(Ok(value)) => loaded = Some(value),
+                Some(Err(err)) => data = Some(Err(err)),
+                None => initial = Some((self.inbox, tx.clone())),
+            }
+
+            let chain = Chain::new(self.reload_fn, Box::new(callback_mut), initial, self.chain);
+            if let Some(value) = loaded {
+                chain.load_second(
+                    value,
+                    Box::new(move |result| {
+                        tx.send(result).ok();
+                    }),
+                );
+            }
+
+            let reload_chain = chain.clone();
+            EguiSuspense {
+                inbox,
+                data,
+
+                reload_fn: Some(Box::new(move |callback| reload_chain.load(callback))),
+                chain: Some(Box::new(chain)),
+                error_ui: self.error_ui,
+                loading_ui: self.loading_ui,
+                offline_ui: self.offline_ui,
+                offline: self.offline,
+                reload_postponed: false,
+            }
+        },
+    );
+}
@@ -0,0 +1,270 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use egui::ahash::HashMap;
+use egui::load::{ImageLoadResult, ImageLoader, ImagePoll, LoadError};
+use egui::mutex::Mutex;
+use egui::{ColorImage, Context, SizeHint};
+
+use crate::{decode_thumbhash, invalid_thumbhash, thumbhash_id, uri_to_thumbhash};
+
+/// The default of [`AsyncThumbhashImageLoader::upload_budget`]
+const DEFAULT_UPLOAD_BUDGET: usize = 16;
+
+#[derive(Default)]
+struct DecodeState {
+    /// The images that were passed to egui. `None` if the thumbhash is invalid.
+    images: HashMap<u64, Option<Arc<ColorImage>>>,
+    /// The decoded images that are waiting for their upload
+    decoded: HashMap<u64, Option<Arc<ColorImage>>>,
+    /// The thumbhashes waiting to be decoded
+    queue: Vec<(u64, Vec<u8>)>,
+    /// The ids of the thumbhashes in `queue` or currently being decoded
+    queued: HashSet<u64>,
+    worker_running: bool,
+    /// The pass of the last upload and the number of images uploaded in that pass
+    uploads: (u64, usize),
+}
+
+impl DecodeState {
+    /// Queues the thumbhash, unless it's already queued.
+    /// Returns true if a worker has to be started to decode the queue.
+    fn enqueue(&mut self, id: u64, bytes: Vec<u8>) -> bool {
+        if !self.queued.insert(id) {
+            return false;
+        }
+        self.queue.push((id, bytes));
+        !std::mem::replace(&mut self.worker_running, true)
+    }
+
+    /// Counts an upload in the given pass. Returns false if the budget of the pass is used up.
+    fn try_upload(&mut self, pass: u64, budget: usize) -> bool {
+        if self.uploads.0 != pass {
+            self.uploads = (pass, 0);
+        }
+        if self.uploads.1 < budget {
+            self.uploads.1 += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// An `ImageLoader` for thumbhash images that decodes the thumbhashes in the background, so
+/// showing a long list of thumbhashes for the first time doesn't block the frame.
+///
+/// The thumbhashes requested in a frame are queued and decoded in one batch on a background
+/// thread. At most [`AsyncThumbhashImageLoader::upload_budget`] decoded images are handed to
+/// egui per frame, the rest follow in the next frames. On the web, where there are no threads,
+/// the thumbhashes are decoded right before they are handed to egui instead, so the decoding is
+/// spread across the frames as well. Until then, [`crate::ThumbhashImage`] shows the average
+/// color of the thumbhash.
+///
+/// Register it via [`crate::register_async`], or via [`Context::add_image_loader`] to change the
+/// upload budget.
+pub struct AsyncThumbhashImageLoader {
+    state: Arc<Mutex<DecodeState>>,
+    upload_budget: usize,
+}
+
+impl Default for AsyncThumbhashImageLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncThumbhashImageLoader {
+    /// Create a new `AsyncThumbhashImageLoader`.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::default(),
+            upload_budget: DEFAULT_UPLOAD_BUDGET,
+        }
+    }
+
+    /// Set how many decoded images are handed to egui (and uploaded as textures) per frame.
+    /// Defaults to 16.
+    pub fn upload_budget(mut self, upload_budget: usize) -> Self {
+        self.upload_budget = upload_budget.max(1);
+        self
+    }
+}
+
+/// Decodes the queued thumbhashes until the queue is empty
+fn decode_queue(state: &Mutex<DecodeState>, ctx: &Context) {
+    loop {
+        let batch = {
+            let mut state = state.lock();
+            if state.queue.is_empty() {
+                state.worker_running = false;
+                return;
+            }
+            std::mem::take(&mut state.queue)
+        };
+
+        let decoded: Vec<_> = batch
+            .into_iter()
+            .map(|(id, bytes)| (id, decode_thumbhash(&bytes)))
+            .collect();
+
+        let mut state = state.lock();
+        for (id, image) in decoded {
+            state.queued.remove(&id);
+            state.decoded.insert(id, image);
+        }
+        drop(state);
+        ctx.request_repaint();
+    }
+}
+
+impl ImageLoader for AsyncThumbhashImageLoader {
+    fn id(&self) -> &'static str {
+        "thumbhash_async"
+    }
+
+    fn load(&self, ctx: &Context, uri: &str, _size_hint: SizeHint) -> ImageLoadResult {
+        let Some(bytes) = uri_to_thumbhash(uri) else {
+            return Err(LoadError::NotSupported);
+        };
+        let id = thumbhash_id(&bytes);
+
+        let mut state = self.state.lock();
+        if let Some(image) = state.images.get(&id) {
+            return match image {
+                Some(image) => Ok(ImagePoll::Ready {
+                    image: image.clone(),
+                }),
+                None => Err(invalid_thumbhash()),
+            };
+        }
+
+        // There are no threads on the web, so the thumbhashes are decoded within the budget there
+        if state.decoded.contains_key(&id) || cfg!(target_arch = "wasm32") {
+            if state.try_upload(ctx.cumulative_pass_nr(), self.upload_budget) {
+                let image = state
+                    .decoded
+                    .remove(&id)
+                    .unwrap_or_else(|| decode_thumbhash(&bytes));
+                state.images.insert(id, image.clone());
+                return match image {
+                    Some(image) => Ok(ImagePoll::Ready { image }),
+                    None => Err(invalid_thumbhash()),
+                };
+            }
+            // The budget of this frame is used up
+            ctx.request_repaint();
+        } else if state.enqueue(id, bytes) {
+            let state = self.state.clone();
+            let ctx = ctx.clone();
+            // Decoding is CPU bound, so it shouldn't block the threads of an async runtime
+            std::thread::spawn(move || decode_queue(&state, &ctx));
+        }
+
+        Ok(ImagePoll::Pending { size: None })
+    }
+
+    fn forget(&self, uri: &str) {
+        if let Some(bytes) = uri_to_thumbhash(uri) {
+            let id = thumbhash_id(&bytes);
+            let mut state = self.state.lock();
+            state.images.remove(&id);
+            state.decoded.remove(&id);
+        }
+    }
+
+    fn forget_all(&self) {
+        let mut state = self.state.lock();
+        state.images.clear();
+        state.decoded.clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        let state = self.state.lock();
+        state
+            .images
+            .values()
+            .chain(state.decoded.values())
+            .flatten()
+            .map(|image| image.width() * image.height() * 4)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use egui::load::{ImageLoader, ImagePoll};
+    use egui::{ColorImage, Context, SizeHint};
+
+    use super::{decode_queue, AsyncThumbhashImageLoader, DecodeState};
+    use crate::{thumbhash_id, uri_to_thumbhash};
+
+    const THUMBHASHES: [&str; 2] = [
+        "thumbhash:1QcSHQRnh493V4dIh4eXh1h4kJUI",
+        "thumbhash:3PcNNYSFeXh/d3eld0iHZoZgVwh2",
+    ];
+
+    #[test]
+    fn queued_once() {
+        let mut state = DecodeState::default();
+        assert!(state.enqueue(1, vec![1]));
+        // Already queued
+        assert!(!state.enqueue(1, vec![1]));
+        // The worker is already running
+        assert!(!state.enqueue(2, vec![2]));
+        assert_eq!(state.queue.len(), 2);
+    }
+
+    #[test]
+    fn upload_budget_per_pass() {
+        let mut state = DecodeState::default();
+        assert!(state.try_upload(1, 2));
+        assert!(state.try_upload(1, 2));
+        assert!(!state.try_upload(1, 2));
+        assert!(state.try_upload(2, 2));
+    }
+
+    #[test]
+    fn decodes_queue_and_uploads_within_budget() {
+        let ctx = Context::default();
+        let loader = AsyncThumbhashImageLoader::new().upload_budget(1);
+        // Pretend the worker is running, so the queue can be decoded on this thread
+        loader.state.lock().worker_running = true;
+
+        for uri in THUMBHASHES.iter().chain(&THUMBHASHES) {
+            let poll = loader.load(&ctx, uri, SizeHint::default());
+            assert!(matches!(poll, Ok(ImagePoll::Pending { .. })));
+        }
+        assert_eq!(loader.state.lock().queue.len(), 2);
+
+        decode_queue(&loader.state, &ctx);
+        {
+            let state = loader.state.lock();
+            assert!(state.queue.is_empty() && state.queued.is_empty());
+            assert!(!state.worker_running);
+            assert_eq!(state.decoded.len(), 2);
+        }
+
+        // Only one image is handed to egui per pass
+        let first = loader.load(&ctx, THUMBHASHES[0], SizeHint::default());
+        assert!(matches!(first, Ok(ImagePoll::Ready { .. })));
+        let second = loader.load(&ctx, THUMBHASHES[1], SizeHint::default());
+        assert!(matches!(second, Ok(ImagePoll::Pending { .. })));
+        let _ = ctx.run(egui::RawInput::default(), |_| {});
+        let second = loader.load(&ctx, THUMBHASHES[1], SizeHint::default());
+        assert!(matches!(second, Ok(ImagePoll::Ready { .. })));
+    }
+
+    #[test]
+    fn forget_removes_images() {
+        let loader = AsyncThumbhashImageLoader::new();
+        let image = Arc::new(ColorImage::new([1, 1], egui::Color32::RED));
+        let id = thumbhash_id(&uri_to_thumbhash(THUMBHASHES[0]).unwrap());
+        loader.state.lock().images.insert(id, Some(image));
+        assert_eq!(loader.byte_size(), 4);
+        loader.forget(THUMBHASHES[0]);
+        assert_eq!(loader.byte_size(), 0);
+    }
+}
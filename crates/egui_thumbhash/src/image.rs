@@ -96,13 +96,14 @@ impl<'a, 'h> ThumbhashImage<'a, 'h> {
                 TextureOptions::LINEAR,
                 SizeHint::default(),
             );
+            let rect = Rect::from_min_size(
+                response.rect.min,
+                self.fit_to_exact_size.unwrap_or(response.rect.size()),
+            );
             if let Ok(TexturePoll::Ready { texture, .. }) = image {
                 ui.painter().add(
                     RectShape::filled(
-                        Rect::from_min_size(
-                            response.rect.min,
-                            self.fit_to_exact_size.unwrap_or(response.rect.size()),
-                        ),
+                        rect,
                         self.rounding.unwrap_or_default(),
                         Color32::from_rgba_premultiplied(i, i, i, i),
                     )
@@ -111,6 +112,20 @@ impl<'a, 'h> ThumbhashImage<'a, 'h> {
                         Rect::from_min_size(Pos2::default(), Vec2::new(1.0, 1.0)),
                     ),
                 );
+            } else if let Ok((r, g, b, a)) = thumbhash::thumb_hash_to_average_rgba(self.thumbhash) {
+                // The thumbhash is still being decoded (see `AsyncThumbhashImageLoader`), so
+                // show its average color in the meantime
+                let color = Color32::from_rgba_unmultiplied(
+                    (r * 255.0) as u8,
+                    (g * 255.0) as u8,
+                    (b * 255.0) as u8,
+                    (a * 255.0) as u8,
+                );
+                ui.painter().rect_filled(
+                    rect,
+                    self.rounding.unwrap_or_default(),
+                    color.gamma_multiply(t),
+                );
             }
         }
 
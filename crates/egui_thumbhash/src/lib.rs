@@ -12,8 +12,12 @@ use egui::load::{ImageLoadResult, ImageLoader, ImagePoll, LoadError};
 use egui::mutex::Mutex;
 use egui::{ahash, ColorImage, Context, SizeHint};
 
+#[cfg(feature = "async")]
+pub use async_loader::AsyncThumbhashImageLoader;
 pub use image::ThumbhashImage;
 
+#[cfg(feature = "async")]
+mod async_loader;
 mod image;
 
 /// Register the thumbhash image loader with the given egui context.
@@ -22,6 +26,14 @@ pub fn register(ctx: &Context) {
     ctx.add_image_loader(Arc::new(ThumbhashImageLoader::new()));
 }
 
+/// Register the [`AsyncThumbhashImageLoader`] with the given egui context, which decodes the
+/// thumbhashes in the background. Do this once while the app is initializing, instead of
+/// [`register`].
+#[cfg(feature = "async")]
+pub fn register_async(ctx: &Context) {
+    ctx.add_image_loader(Arc::new(AsyncThumbhashImageLoader::new()));
+}
+
 /// The `ImageLoader` implementation for thumbhash images.
 #[derive(Clone, Default)]
 pub struct ThumbhashImageLoader {
@@ -42,11 +54,7 @@ impl ImageLoader for ThumbhashImageLoader {
 
     fn load(&self, _ctx: &Context, uri: &str, _size_hint: SizeHint) -> ImageLoadResult {
         if let Some(bytes) = uri_to_thumbhash(uri) {
-            let mut hasher = ahash::AHasher::default();
-
-            bytes.hash(&mut hasher);
-
-            let id = hasher.finish();
+            let id = thumbhash_id(&bytes);
 
             let mut images = self.images.lock();
 
@@ -55,15 +63,12 @@ impl ImageLoader for ThumbhashImageLoader {
             if let Some(image) = image {
                 Ok(ImagePoll::Ready { image })
             } else {
-                let result = thumbhash::thumb_hash_to_rgba(&bytes);
-
-                match result {
-                    Ok((w, h, vec)) => {
-                        let image = Arc::new(ColorImage::from_rgba_unmultiplied([w, h], &vec));
+                match decode_thumbhash(&bytes) {
+                    Some(image) => {
                         images.insert(id, image.clone());
                         Ok(ImagePoll::Ready { image })
                     }
-                    Err(()) => Err(LoadError::Loading("Invalid thumbhash".to_string())),
+                    None => Err(invalid_thumbhash()),
                 }
             }
         } else {
@@ -73,11 +78,8 @@ impl ImageLoader for ThumbhashImageLoader {
 
     fn forget(&self, uri: &str) {
         if let Some(bytes) = uri_to_thumbhash(uri) {
-            let mut hasher = ahash::AHasher::default();
-            bytes.hash(&mut hasher);
-            let id = hasher.finish();
             let mut images = self.images.lock();
-            images.remove(&id);
+            images.remove(&thumbhash_id(&bytes));
         }
     }
 
@@ -95,6 +97,23 @@ impl ImageLoader for ThumbhashImageLoader {
     }
 }
 
+/// The key of a thumbhash in the loader caches
+fn thumbhash_id(bytes: &[u8]) -> u64 {
+    let mut hasher = ahash::AHasher::default();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns None if the thumbhash is invalid
+fn decode_thumbhash(bytes: &[u8]) -> Option<Arc<ColorImage>> {
+    let (w, h, rgba) = thumbhash::thumb_hash_to_rgba(bytes).ok()?;
+    Some(Arc::new(ColorImage::from_rgba_unmultiplied([w, h], &rgba)))
+}
+
+fn invalid_thumbhash() -> LoadError {
+    LoadError::Loading("Invalid thumbhash".to_string())
+}
+
 /// Convert a thumbhash to a URI that can be loaded by the image loader.
 pub fn thumbhash_to_uri(thumbhash: &[u8]) -> String {
     format!("thumbhash:{}", BASE64_STANDARD_NO_PAD.encode(thumbhash))
@@ -0,0 +1,89 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use egui::{CentralPanel, Context, RawInput, Rect, ScrollArea, Sense, Vec2};
+use egui_virtual_list::VirtualList;
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::Range;
+
+const LENGTHS: [usize; 2] = [100_000, 1_000_000];
+
+/// Shows one frame of a list with `length` items of varying height, scrolled to `offset`
+fn show(ctx: &Context, list: &mut VirtualList, length: usize, offset: f32) -> Range<usize> {
+    let input = RawInput {
+        screen_rect: Some(Rect::from_min_size(Default::default(), Vec2::new(400.0, 800.0))),
+        ..Default::default()
+    };
+    let mut item_range = 0..0;
+    let _ = ctx.run(input, |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            item_range = list
+                .ui_scroll_area(
+                    ui,
+                    ScrollArea::vertical()
+                        .vertical_scroll_offset(offset)
+                        .animated(false),
+                    length,
+                    |ui, index| {
+                        let height = 20.0 + (index % 5) as f32 * 4.0;
+                        ui.allocate_exact_size(Vec2::new(300.0, height), Sense::hover());
+                        1
+                    },
+                )
+                .inner
+                .item_range;
+        });
+    });
+    item_range
+}
+
+/// Returns a list where all rows were measured, by scrolling to the end until no new rows are
+/// laid out anymore
+fn measured_list(ctx: &Context, length: usize) -> VirtualList {
+    let mut list = VirtualList::new();
+    list.hide_on_resize(None);
+    let mut end = 0;
+    loop {
+        let item_range = show(ctx, &mut list, length, length as f32 * 100.0);
+        if item_range.end == end {
+            break list;
+        }
+        end = item_range.end;
+    }
+}
+
+/// Jumping to random offsets, e.g. by dragging the scroll bar
+fn jump(c: &mut Criterion) {
+    let mut group = c.benchmark_group("jump");
+    for length in LENGTHS {
+        let ctx = Context::default();
+        let mut list = measured_list(&ctx, length);
+        let mut rng = StdRng::seed_from_u64(0);
+        let max_offset = length as f32 * 20.0;
+
+        group.bench_with_input(BenchmarkId::from_parameter(length), &length, |b, &length| {
+            b.iter(|| show(&ctx, &mut list, length, rng.random_range(0.0..max_offset)));
+        });
+    }
+    group.finish();
+}
+
+/// Scrolling down from the middle of the list, a few rows per frame
+fn scroll(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scroll");
+    for length in LENGTHS {
+        let ctx = Context::default();
+        let mut list = measured_list(&ctx, length);
+        let mut offset = length as f32 * 10.0;
+
+        group.bench_with_input(BenchmarkId::from_parameter(length), &length, |b, &length| {
+            b.iter(|| {
+                offset += 50.0;
+                show(&ctx, &mut list, length, offset)
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, jump, scroll);
+criterion_main!(benches);
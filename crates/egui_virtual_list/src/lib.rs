@@ -2,11 +2,21 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod rows;
+mod table;
+
+use std::collections::VecDeque;
 use std::ops::Range;
 
-use egui::{Align, Pos2, Rect, Ui, UiBuilder, Vec2};
+use egui::scroll_area::ScrollAreaOutput;
+use egui::style::ScrollAnimation;
+use egui::{Align, Id, Rect, ScrollArea, Ui, UiBuilder, Vec2};
 use web_time::{Duration, SystemTime};
 
+use crate::rows::Rows;
+
+pub use table::{Column, VirtualTable};
+
 /// The response from a call to [`VirtualList::ui_custom_layout`]
 pub struct VirtualListResponse {
     /// The range of items that was displayed
@@ -16,24 +26,23 @@ pub struct VirtualListResponse {
     pub newly_visible_items: Range<usize>,
     /// Any items in this range are no longer visible
     pub hidden_items: Range<usize>,
-}
 
-#[derive(Debug)]
-struct RowData {
-    range: Range<usize>,
-    pos: Pos2,
+    /// Items that entered the rendered window this frame and weren't kept via
+    /// [`VirtualList::keep_recently_visible`]. Use this to lazily create expensive per-item
+    /// state, e.g. textures or decoded previews.
+    pub entered_items: Vec<usize>,
+    /// Items that left the rendered window (or were evicted from the recently visible items)
+    /// this frame. Use this to release the state created for [`Self::entered_items`].
+    pub left_items: Vec<usize>,
 }
 
 /// Virtual list widget for egui.
 #[derive(Debug)]
 pub struct VirtualList {
-    rows: Vec<RowData>,
+    rows: Rows,
 
     previous_item_range: Range<usize>,
 
-    // The index of the first item that has an unknown rect
-    last_known_row_index: Option<usize>,
-
     average_row_size: Option<Vec2>,
     average_items_per_row: Option<f32>,
 
@@ -48,6 +57,9 @@ pub struct VirtualList {
     // Useful when items at the top are added, and the scroll position should be maintained.
     // The value should be the number of items that were added at the top.
     items_inserted_at_start: Option<usize>,
+    // If set, this many items were removed from the top of the list, and the scroll position
+    // should be offset by their height.
+    items_removed_at_start: Option<usize>,
 
     check_for_resize: bool,
     scroll_position_sync_on_resize: bool,
@@ -55,6 +67,15 @@ pub struct VirtualList {
     /// Stores the index and visibility percentage of the last item that was at the top of the list
     last_top_most_item: Option<(usize, f32)>,
     last_resize: SystemTime,
+    // The widget that had focus last frame, so we can scroll to newly focused widgets
+    last_focused: Option<Id>,
+
+    // Items that are no longer rendered but haven't left yet, most recently visible first
+    recently_visible_items: VecDeque<usize>,
+    keep_recently_visible: usize,
+
+    // Set via `VirtualList::scroll_to_item`, kept until the item was laid out
+    scroll_to_item: Option<usize>,
 }
 
 impl Default for VirtualList {
@@ -68,27 +89,56 @@ impl VirtualList {
     pub fn new() -> Self {
         Self {
             previous_item_range: usize::MAX..usize::MAX,
-            last_known_row_index: None,
             last_width: None,
             average_row_size: None,
-            rows: vec![],
+            rows: Rows::default(),
             average_items_per_row: None,
             max_rows_calculated_per_frame: 1000,
             over_scan: 200.0,
             items_inserted_at_start: None,
+            items_removed_at_start: None,
             check_for_resize: true,
             scroll_position_sync_on_resize: true,
             hide_on_resize: Some(Duration::from_millis(100)),
             last_top_most_item: None,
             last_resize: SystemTime::now(),
+            last_focused: None,
+            recently_visible_items: VecDeque::new(),
+            keep_recently_visible: 0,
+            scroll_to_item: None,
         }
     }
 
     /// Call this when you insert items at the start of the list.
     /// The list will offset the scroll position by the height of these items, so that for the user,
     /// the scroll position stays the same.
+    /// If you call this multiple times before the next frame, the counts are added up.
     pub fn items_inserted_at_start(&mut self, scroll_top_items: usize) {
-        self.items_inserted_at_start = Some(scroll_top_items);
+        *self.items_inserted_at_start.get_or_insert(0) += scroll_top_items;
+    }
+
+    /// Call this when you remove items from the start of the list.
+    /// The list will offset the scroll position by the height of these items, so that for the user,
+    /// the visible items stay in place.
+    /// If you call this multiple times before the next frame, the counts are added up.
+    pub fn items_removed_at_start(&mut self, removed_items: usize) {
+        *self.items_removed_at_start.get_or_insert(0) += removed_items;
+    }
+
+    /// Scroll the item at `index` to the top of the list, on the next frame.
+    /// If the item is far away, this may take a few frames, since the rows in between
+    /// have to be measured first.
+    pub fn scroll_to_item(&mut self, index: usize) {
+        self.scroll_to_item = Some(index);
+    }
+
+    /// Keep up to this many items that were recently visible before reporting them in
+    /// [`VirtualListResponse::left_items`]. If they are scrolled back into view before that,
+    /// they won't be reported in [`VirtualListResponse::entered_items`] again.
+    /// This avoids recreating expensive per-item state when scrolling back and forth.
+    /// The default is 0.
+    pub fn keep_recently_visible(&mut self, count: usize) {
+        self.keep_recently_visible = count;
     }
 
     /// Set the overscan, or how much the list should render outside of the visible area.
@@ -117,6 +167,27 @@ impl VirtualList {
         self.hide_on_resize = hide_on_resize.into();
     }
 
+    /// Show the list in `scroll_area`, like [`Self::ui_custom_layout`] wrapped in
+    /// [`ScrollArea::show`]. Configure the scroll area as usual, e.g. via
+    /// [`ScrollArea::stick_to_bottom`], [`ScrollArea::scroll_bar_visibility`] or
+    /// [`ScrollArea::drag_to_scroll`].
+    ///
+    /// The returned [`ScrollAreaOutput`] contains the scroll offset, content size and viewport
+    /// next to the [`VirtualListResponse`], e.g. to sync an external scroll bar or minimap with
+    /// the list.
+    pub fn ui_scroll_area(
+        &mut self,
+        ui: &mut Ui,
+        scroll_area: ScrollArea,
+        length: usize,
+        layout: impl FnMut(&mut Ui, usize) -> usize,
+    ) -> ScrollAreaOutput<VirtualListResponse> {
+        scroll_area.show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            self.ui_custom_layout(ui, length, layout)
+        })
+    }
+
     /// The layout closure gets called with the index of the first item that should be displayed.
     /// It should return the number of items that were displayed.
     #[allow(clippy::too_many_lines)] // TODO: refactor this to reduce the number of lines
@@ -126,14 +197,14 @@ impl VirtualList {
         length: usize,
         mut layout: impl FnMut(&mut Ui, usize) -> usize,
     ) -> VirtualListResponse {
-        let mut scroll_to_item_index_visibility = None;
+        let mut scroll_to_item_index_visibility =
+            self.scroll_to_item.take().map(|index| (index, 0.0));
         {
             let available_width_rounded = (ui.available_width() * 10.0).round() / 10.0;
             if let Some(last_width) = self.last_width {
                 if available_width_rounded != last_width {
                     self.last_width = Some(available_width_rounded);
                     if self.check_for_resize {
-                        self.last_known_row_index = None;
                         self.rows.clear();
                         self.last_resize = SystemTime::now();
                         if self.scroll_position_sync_on_resize {
@@ -153,17 +224,29 @@ impl VirtualList {
             }
         }
 
+        if let Some(removed_items) = self.items_removed_at_start.take() {
+            let removed_height = self.remove_rows_at_start(removed_items);
+
+            // Keep the removed height as space for this frame, so nothing moves until the
+            // scroll offset is updated in the next frame
+            ui.add_space(removed_height);
+            ui.scroll_with_delta_animation(Vec2::new(0.0, removed_height), ScrollAnimation::none());
+            ui.ctx().request_repaint();
+        }
+
         // Start of the scroll area (basically scroll_offset + whatever is above the scroll area)
         let min = ui.next_widget_position().to_vec2();
 
-        let mut row_start_index = self.last_known_row_index.unwrap_or(0);
-
         // This calculates the visible rect inside the scroll area
         // Should be equivalent to to viewport from ScrollArea::show_viewport(), offset by whatever is above the scroll area
         let visible_rect = ui.clip_rect().translate(-min);
 
         let visible_rect = visible_rect.expand2(Vec2::new(0.0, self.over_scan));
 
+        let list_id = ui.id().with("virtual_list");
+        #[cfg(feature = "accesskit")]
+        Self::accesskit_list(ui, list_id, length);
+
         let mut index_offset = 0;
 
         // Calculate the added_height for items that were added at the top and scroll by that amount
@@ -188,6 +271,7 @@ impl VirtualList {
                 ui.scroll_to_rect(ui.clip_rect().translate(Vec2::new(0.0, added_height)), None);
 
                 index_offset = scroll_top_items;
+                self.shift_tracked_items(scroll_top_items);
 
                 ui.ctx().request_repaint();
 
@@ -197,31 +281,21 @@ impl VirtualList {
             };
 
         // Find the first row that is visible
-        loop {
-            if row_start_index == 0 {
-                break;
-            }
-
-            if let Some(row) = self.rows.get(row_start_index) {
-                let skip = if let Some((idx, _)) = scroll_to_item_index_visibility {
-                    row.range.start >= idx
-                } else {
-                    false
-                };
-
-                if row.pos.y <= visible_rect.min.y && !skip {
-                    ui.add_space(row.pos.y);
-                    break;
-                }
-            }
-            row_start_index -= 1;
+        // When scrolling to an item, we start at the closest known row before it
+        let row_start_index = match scroll_to_item_index_visibility {
+            Some((index, _)) => self.rows.row_before_item(index),
+            None => self.rows.row_at(visible_rect.min.y),
+        };
+        if row_start_index > 0 {
+            ui.add_space(self.rows.position(row_start_index));
         }
         let mut current_row = row_start_index;
+        let layout_start = ui.next_widget_position();
 
         let item_start_index = self
             .rows
-            .get(row_start_index)
-            .map_or(0, |row| row.range.start)
+            .range(row_start_index)
+            .map_or(0, |range| range.start)
             + index_offset;
 
         let mut current_item_index = item_start_index;
@@ -243,7 +317,7 @@ impl VirtualList {
             // let item = self.items.get_mut(current_row);
             if current_item_index < length {
                 let pos = ui.next_widget_position() - min;
-                let count = layout(ui, current_item_index);
+                let count = Self::layout_row(ui, list_id, current_item_index, length, &mut layout);
                 let size = ui.next_widget_position() - min - pos;
                 let rect = Rect::from_min_size(pos, size);
 
@@ -270,14 +344,10 @@ impl VirtualList {
                         Some((visible_rect.min.y - rect.min.y) / (rect.max.y - rect.min.y));
                 }
 
-                if let Some(row) = self.rows.get_mut(current_row) {
-                    row.range = range;
-                    row.pos = pos;
+                if current_row < self.rows.len() {
+                    self.rows.set(current_row, range, size.y);
                 } else {
-                    self.rows.push(RowData {
-                        range: current_item_index..current_item_index + count,
-                        pos,
-                    });
+                    self.rows.push(range, size.y);
 
                     let size_with_space = size;
 
@@ -292,8 +362,6 @@ impl VirtualList {
                                 / (current_row as f32 + 1.0)
                         },
                     ));
-
-                    self.last_known_row_index = Some(current_row);
                 }
 
                 current_item_index += count;
@@ -310,6 +378,33 @@ impl VirtualList {
 
         let item_range = first_visible_item_index.unwrap_or(item_start_index)..current_item_index;
 
+        // If we bailed out before reaching the item, we continue on the next frame
+        if let Some((index, _)) = scroll_to_item_index_visibility {
+            if index < length {
+                self.scroll_to_item = Some(index);
+            }
+        }
+
+        // Scroll to a widget in the list when it gains focus, e.g. via tab or a screen reader
+        let focused = ui.memory(egui::Memory::focused);
+        if focused != self.last_focused {
+            self.last_focused = focused;
+            let rows_rect = Rect::from_x_y_ranges(
+                ui.max_rect().x_range(),
+                layout_start.y..=ui.next_widget_position().y,
+            );
+            if let Some(focused_rect) = focused
+                .and_then(|id| ui.ctx().read_response(id))
+                .map(|response| response.rect)
+            {
+                if rows_rect.contains_rect(focused_rect)
+                    && !ui.clip_rect().contains_rect(focused_rect)
+                {
+                    ui.scroll_to_rect(focused_rect, None);
+                }
+            }
+        }
+
         // If we scrolled this frame, don't store the last top most item
         if !did_scroll
             && self.last_resize.elapsed().unwrap_or_default() > Duration::from_millis(1000)
@@ -342,13 +437,14 @@ impl VirtualList {
                 self.previous_item_range.start..item_range.start.min(self.previous_item_range.end);
         }
 
+        let (entered_items, left_items) = self.update_lifecycle(&item_range, length);
+
         self.previous_item_range = item_range.clone();
 
         if let Some(added_height) = scroll_items_top_step_2 {
             // We need to add the height at the bottom or else we might not be able to scroll
             ui.add_space(added_height);
             self.rows.clear();
-            self.last_known_row_index = None;
             self.average_items_per_row = None;
             self.average_row_size = None;
         }
@@ -357,13 +453,187 @@ impl VirtualList {
             item_range,
             newly_visible_items: visible_range,
             hidden_items: hidden_range,
+            entered_items,
+            left_items,
+        }
+    }
+
+    /// Compares the new item range with the previous one and returns the entered and left items.
+    fn update_lifecycle(
+        &mut self,
+        item_range: &Range<usize>,
+        length: usize,
+    ) -> (Vec<usize>, Vec<usize>) {
+        let mut entered_items = Vec::new();
+        let mut left_items = Vec::new();
+
+        for index in item_range.clone() {
+            if !self.previous_item_range.contains(&index) {
+                if let Some(pos) = self
+                    .recently_visible_items
+                    .iter()
+                    .position(|recent| *recent == index)
+                {
+                    self.recently_visible_items.remove(pos);
+                } else {
+                    entered_items.push(index);
+                }
+            }
+        }
+
+        for index in self.previous_item_range.clone() {
+            if !item_range.contains(&index) {
+                if index < length {
+                    self.recently_visible_items.push_front(index);
+                } else {
+                    left_items.push(index);
+                }
+            }
+        }
+
+        self.recently_visible_items.retain(|index| {
+            if *index < length {
+                true
+            } else {
+                left_items.push(*index);
+                false
+            }
+        });
+        while self.recently_visible_items.len() > self.keep_recently_visible {
+            left_items.extend(self.recently_visible_items.pop_back());
+        }
+
+        (entered_items, left_items)
+    }
+
+    /// Offsets the visible and recently visible items after items were inserted at the start.
+    fn shift_tracked_items(&mut self, inserted_items: usize) {
+        if self.previous_item_range.start != usize::MAX {
+            self.previous_item_range = self.previous_item_range.start + inserted_items
+                ..self.previous_item_range.end + inserted_items;
+        }
+        for index in &mut self.recently_visible_items {
+            *index += inserted_items;
+        }
+    }
+
+    /// Calls the layout closure for the row starting at `index`.
+    /// With the `accesskit` feature, the row's widgets are grouped in a list item node.
+    fn layout_row(
+        ui: &mut Ui,
+        list_id: Id,
+        index: usize,
+        length: usize,
+        layout: &mut impl FnMut(&mut Ui, usize) -> usize,
+    ) -> usize {
+        #[cfg(feature = "accesskit")]
+        {
+            use egui::accesskit;
+
+            let ctx = ui.ctx().clone();
+            let row_id = list_id.with(index);
+            let start = ui.next_widget_position();
+
+            ctx.with_accessibility_parent(list_id, || {
+                ctx.accesskit_node_builder(row_id, |node| {
+                    node.set_role(accesskit::Role::ListItem);
+                    node.set_position_in_set(index + 1);
+                    node.set_size_of_set(length);
+                    node.add_action(accesskit::Action::ScrollIntoView);
+                });
+            });
+
+            let count = ctx.with_accessibility_parent(row_id, || layout(ui, index));
+
+            let rect = Rect::from_x_y_ranges(
+                ui.max_rect().x_range(),
+                start.y..=ui.next_widget_position().y,
+            );
+            ctx.accesskit_node_builder(row_id, |node| {
+                node.set_bounds(accesskit::Rect {
+                    x0: rect.min.x.into(),
+                    y0: rect.min.y.into(),
+                    x1: rect.max.x.into(),
+                    y1: rect.max.y.into(),
+                });
+            });
+            if ui.input(|input| {
+                input.has_accesskit_action_request(row_id, accesskit::Action::ScrollIntoView)
+            }) {
+                ui.scroll_to_rect(rect, None);
+            }
+
+            count
+        }
+        #[cfg(not(feature = "accesskit"))]
+        {
+            let _ = (list_id, length);
+            layout(ui, index)
+        }
+    }
+
+    /// Adds the list node. Only the visible rows are added as children, but each row
+    /// knows its position in the list, so screen readers can still announce e.g. "item 5 of 100".
+    #[cfg(feature = "accesskit")]
+    fn accesskit_list(ui: &Ui, list_id: Id, length: usize) {
+        use egui::accesskit;
+
+        let clip_rect = ui.clip_rect();
+        ui.ctx().accesskit_node_builder(list_id, |node| {
+            node.set_role(accesskit::Role::List);
+            node.set_size_of_set(length);
+            node.add_action(accesskit::Action::ScrollUp);
+            node.add_action(accesskit::Action::ScrollDown);
+            node.set_bounds(accesskit::Rect {
+                x0: clip_rect.min.x.into(),
+                y0: clip_rect.min.y.into(),
+                x1: clip_rect.max.x.into(),
+                y1: clip_rect.max.y.into(),
+            });
+        });
+
+        let (scroll_up, scroll_down) = ui.input(|input| {
+            (
+                input.has_accesskit_action_request(list_id, accesskit::Action::ScrollUp),
+                input.has_accesskit_action_request(list_id, accesskit::Action::ScrollDown),
+            )
+        });
+        if scroll_up {
+            ui.scroll_with_delta(Vec2::new(0.0, clip_rect.height()));
+        }
+        if scroll_down {
+            ui.scroll_with_delta(Vec2::new(0.0, -clip_rect.height()));
+        }
+    }
+
+    /// Removes the cached rows of the first `removed_items` items and shifts the remaining rows
+    /// up. Returns the height of the removed rows.
+    fn remove_rows_at_start(&mut self, removed_items: usize) -> f32 {
+        self.previous_item_range = self.previous_item_range.start.saturating_sub(removed_items)
+            ..self.previous_item_range.end.saturating_sub(removed_items);
+        self.last_top_most_item = self
+            .last_top_most_item
+            .map(|(index, visibility)| (index.saturating_sub(removed_items), visibility));
+        // The removed items are gone, so they won't be reported as left
+        self.recently_visible_items
+            .retain(|index| *index >= removed_items);
+        for index in &mut self.recently_visible_items {
+            *index -= removed_items;
+        }
+
+        if let Some(removed_height) = self.rows.remove_items_at_start(removed_items) {
+            removed_height
+        } else {
+            // The removed items don't end at a known row boundary, so we have to estimate
+            self.rows.clear();
+            removed_items as f32 / self.average_items_per_row.unwrap_or(1.0)
+                * self.average_row_size.unwrap_or(Vec2::ZERO).y
         }
     }
 
     /// Resets the list, clearing all cached data. Call this if items changed size, items were replaced, etc.
     /// The heights will be recalculated on the next frame.
     pub fn reset(&mut self) {
-        self.last_known_row_index = None;
         self.last_width = None;
         self.average_row_size = None;
         self.rows.clear();
@@ -0,0 +1,220 @@
+use std::ops::Range;
+
+/// The measured rows of a [`crate::VirtualList`]: the items of each row and its height.
+///
+/// The heights are stored in a Fenwick tree (binary indexed tree), so the position of a row and
+/// the row at a scroll offset can be looked up in O(log n), and changing the height of a row
+/// moves all rows below it without touching them. Sums are kept as `f64`, so lists with millions
+/// of rows don't drift because of rounding errors.
+#[derive(Debug, Default)]
+pub(crate) struct Rows {
+    ranges: Vec<Range<usize>>,
+    heights: Vec<f64>,
+    /// `tree[i]` is the sum of the heights of the rows `(i + 1 - lowbit(i + 1))..=i`
+    tree: Vec<f64>,
+}
+
+/// The lowest set bit of `i`
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl Rows {
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+        self.heights.clear();
+        self.tree.clear();
+    }
+
+    /// The items of the row at `index`
+    pub fn range(&self, index: usize) -> Option<&Range<usize>> {
+        self.ranges.get(index)
+    }
+
+    /// Add a row below the last one
+    pub fn push(&mut self, range: Range<usize>, height: f32) {
+        let index = self.len();
+        let height = f64::from(height);
+        let node = index + 1 - lowbit(index + 1);
+        let sum = self.prefix_sum(index) - self.prefix_sum(node) + height;
+        self.ranges.push(range);
+        self.heights.push(height);
+        self.tree.push(sum);
+    }
+
+    /// Update the items and height of the row at `index`
+    pub fn set(&mut self, index: usize, range: Range<usize>, height: f32) {
+        self.ranges[index] = range;
+        let height = f64::from(height);
+        let delta = height - self.heights[index];
+        if delta == 0.0 {
+            return;
+        }
+        self.heights[index] = height;
+        let mut i = index + 1;
+        while i <= self.tree.len() {
+            self.tree[i - 1] += delta;
+            i += lowbit(i);
+        }
+    }
+
+    /// The sum of the heights of the rows before `index`
+    fn prefix_sum(&self, index: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut i = index;
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i -= lowbit(i);
+        }
+        sum
+    }
+
+    /// The y position of the row at `index`, relative to the first row
+    pub fn position(&self, index: usize) -> f32 {
+        self.prefix_sum(index) as f32
+    }
+
+    /// The index of the last row that starts at or above `y` (or 0 if there are no rows)
+    pub fn row_at(&self, y: f32) -> usize {
+        let mut remaining = f64::from(y);
+        let mut index = 0;
+        let mut step = self.tree.len().checked_ilog2().map_or(0, |log| 1 << log);
+        // Find the largest number of rows whose heights sum up to at most `y`
+        while step > 0 {
+            let next = index + step;
+            if next <= self.tree.len() && self.tree[next - 1] <= remaining {
+                index = next;
+                remaining -= self.tree[next - 1];
+            }
+            step >>= 1;
+        }
+        index.min(self.len().saturating_sub(1))
+    }
+
+    /// The index of the last row that starts before `item` (or 0 if there are no rows)
+    pub fn row_before_item(&self, item: usize) -> usize {
+        self.ranges
+            .partition_point(|range| range.start < item)
+            .saturating_sub(1)
+    }
+
+    /// Removes the rows of the first `removed_items` items and shifts the remaining rows up.
+    /// Returns the height of the removed rows, or `None` if the removed items don't end at a row
+    /// boundary (in which case the rows are left as they are).
+    pub fn remove_items_at_start(&mut self, removed_items: usize) -> Option<f32> {
+        let first_kept_row = self
+            .ranges
+            .binary_search_by_key(&removed_items, |range| range.start)
+            .ok()?;
+        let removed_height = self.position(first_kept_row);
+
+        self.ranges.drain(..first_kept_row);
+        for range in &mut self.ranges {
+            *range = range.start - removed_items..range.end - removed_items;
+        }
+        self.heights.drain(..first_kept_row);
+        self.rebuild_tree();
+
+        Some(removed_height)
+    }
+
+    /// Builds the tree from the heights in O(n)
+    fn rebuild_tree(&mut self) {
+        self.tree.clone_from(&self.heights);
+        for i in 1..=self.tree.len() {
+            let parent = i + lowbit(i);
+            if parent <= self.tree.len() {
+                self.tree[parent - 1] += self.tree[i - 1];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rows;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Whole numbers, so the sums are exact and can be compared with `==`
+    fn random_height(rng: &mut StdRng) -> f32 {
+        f32::from(rng.random_range(0..200_u8))
+    }
+
+    /// Compares the tree with prefix sums calculated naively from `heights`
+    fn assert_matches(rows: &Rows, heights: &[f32], rng: &mut StdRng) {
+        assert_eq!(rows.len(), heights.len());
+        let mut positions = vec![0.0];
+        for height in heights {
+            positions.push(positions.last().unwrap() + height);
+        }
+        for (index, position) in positions.iter().enumerate() {
+            assert_eq!(rows.position(index), *position, "position of row {index}");
+        }
+
+        let total = *positions.last().unwrap();
+        for _ in 0..50 {
+            let y = rng.random_range(-10.0..total + 10.0);
+            let expected = (0..heights.len())
+                .rev()
+                .find(|index| positions[*index] <= y)
+                .unwrap_or(0);
+            assert_eq!(rows.row_at(y), expected, "row at {y}");
+        }
+    }
+
+    #[test]
+    fn matches_naive_prefix_sums() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for len in [0, 1, 2, 7, 8, 9, 100, 1000] {
+            let mut rows = Rows::default();
+            let mut heights = Vec::new();
+            for index in 0..len {
+                let height = random_height(&mut rng);
+                rows.push(index..index + 1, height);
+                heights.push(height);
+            }
+            assert_matches(&rows, &heights, &mut rng);
+
+            for _ in 0..len {
+                let index = rng.random_range(0..len);
+                let height = random_height(&mut rng);
+                rows.set(index, index..index + 1, height);
+                heights[index] = height;
+            }
+            assert_matches(&rows, &heights, &mut rng);
+        }
+    }
+
+    #[test]
+    fn remove_items_at_start() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut rows = Rows::default();
+        let mut heights = Vec::new();
+        // Rows of 2 items each
+        for index in 0..100 {
+            let height = random_height(&mut rng);
+            rows.push(index * 2..index * 2 + 2, height);
+            heights.push(height);
+        }
+
+        // Not a row boundary
+        assert_eq!(rows.remove_items_at_start(3), None);
+        assert_matches(&rows, &heights, &mut rng);
+
+        let removed: f32 = heights.drain(..10).sum();
+        assert_eq!(rows.remove_items_at_start(20), Some(removed));
+        assert_eq!(rows.range(0), Some(&(0..2)));
+        assert_matches(&rows, &heights, &mut rng);
+
+        rows.set(5, 10..12, 1.0);
+        heights[5] = 1.0;
+        rows.push(180..182, 2.0);
+        heights.push(2.0);
+        assert_matches(&rows, &heights, &mut rng);
+    }
+}
@@ -0,0 +1,189 @@
+use egui::scroll_area::ScrollAreaOutput;
+use egui::{Align, Layout, Rect, ScrollArea, Shape, Ui, UiBuilder, Vec2};
+
+use crate::{VirtualList, VirtualListResponse};
+
+/// The width of a [`VirtualTable`] column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    /// A column with a fixed width in points.
+    Exact(f32),
+    /// The column takes up the remaining width.
+    /// If there are multiple remainder columns, the width is split evenly between them.
+    Remainder,
+}
+
+impl Column {
+    /// A column with a fixed width in points.
+    pub fn exact(width: f32) -> Self {
+        Self::Exact(width)
+    }
+
+    /// A column that takes up the remaining width.
+    pub fn remainder() -> Self {
+        Self::Remainder
+    }
+}
+
+/// A table on top of [`VirtualList`].
+/// It shows a header row, followed by a virtualized, scrollable list of rows with a consistent
+/// column layout. Like the [`VirtualList`], rows can have varying heights.
+///
+/// Example:
+/// ```no_run
+/// # use egui::Ui;
+/// # use egui_virtual_list::{Column, VirtualTable};
+/// fn ui(ui: &mut Ui, table: &mut VirtualTable, rows: &[(String, u32)]) {
+///     table.ui(
+///         ui,
+///         rows.len(),
+///         |ui, col| {
+///             ui.strong(["Name", "Age"][col]);
+///         },
+///         |ui, row, col| match col {
+///             0 => {
+///                 ui.label(&rows[row].0);
+///             }
+///             _ => {
+///                 ui.label(rows[row].1.to_string());
+///             }
+///         },
+///     );
+/// }
+///
+/// let table = VirtualTable::new(vec![Column::remainder(), Column::exact(60.0)]);
+/// ```
+#[derive(Debug)]
+pub struct VirtualTable {
+    list: VirtualList,
+    columns: Vec<Column>,
+    striped: bool,
+    scroll_area: ScrollArea,
+}
+
+impl VirtualTable {
+    /// Create a new `VirtualTable` with the given columns.
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            list: VirtualList::new(),
+            columns,
+            striped: true,
+            scroll_area: ScrollArea::vertical().auto_shrink([false, false]),
+        }
+    }
+
+    /// Set the columns of the table.
+    pub fn columns(&mut self, columns: Vec<Column>) {
+        self.columns = columns;
+    }
+
+    /// Show every other row with a slightly different background color.
+    /// The default is true.
+    pub fn striped(&mut self, striped: bool) {
+        self.striped = striped;
+    }
+
+    /// Set the [`ScrollArea`] the rows are shown in, e.g. to configure
+    /// [`ScrollArea::stick_to_bottom`] or [`ScrollArea::scroll_bar_visibility`].
+    /// The default is a vertical scroll area that doesn't shrink.
+    pub fn scroll_area(&mut self, scroll_area: ScrollArea) {
+        self.scroll_area = scroll_area;
+    }
+
+    /// Access the underlying [`VirtualList`], e.g. to configure it or to [`VirtualList::reset`] it.
+    pub fn list_mut(&mut self) -> &mut VirtualList {
+        &mut self.list
+    }
+
+    fn column_widths(&self, available_width: f32, spacing: f32) -> Vec<f32> {
+        let total_spacing = spacing * self.columns.len().saturating_sub(1) as f32;
+        let exact_width: f32 = self
+            .columns
+            .iter()
+            .map(|column| match column {
+                Column::Exact(width) => *width,
+                Column::Remainder => 0.0,
+            })
+            .sum();
+        let remainder_count = self
+            .columns
+            .iter()
+            .filter(|column| matches!(column, Column::Remainder))
+            .count();
+        let remainder_width = if remainder_count > 0 {
+            (available_width - exact_width - total_spacing).max(0.0) / remainder_count as f32
+        } else {
+            0.0
+        };
+
+        self.columns
+            .iter()
+            .map(|column| match column {
+                Column::Exact(width) => *width,
+                Column::Remainder => remainder_width,
+            })
+            .collect()
+    }
+
+    fn row_ui(ui: &mut Ui, widths: &[f32], striped: bool, mut cell: impl FnMut(&mut Ui, usize)) {
+        // Reserve a shape for the background, so we can paint it once we know the row height
+        let background = ui.painter().add(Shape::Noop);
+
+        let response = ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
+            for (col, width) in widths.iter().enumerate() {
+                let rect = Rect::from_min_size(
+                    ui.next_widget_position(),
+                    Vec2::new(*width, ui.spacing().interact_size.y),
+                );
+                ui.scope_builder(
+                    UiBuilder::new()
+                        .max_rect(rect)
+                        .layout(Layout::top_down(Align::Min)),
+                    |ui| {
+                        ui.set_width(*width);
+                        ui.shrink_clip_rect(Rect::from_min_size(
+                            rect.min,
+                            Vec2::new(*width, f32::INFINITY),
+                        ));
+                        cell(ui, col);
+                    },
+                );
+            }
+        });
+
+        if striped {
+            ui.painter().set(
+                background,
+                Shape::rect_filled(response.response.rect, 0.0, ui.visuals().faint_bg_color),
+            );
+        }
+    }
+
+    /// Show the table. The header closure is called with the column index, the cell closure
+    /// with the row and column index.
+    ///
+    /// The rows are shown in a vertical [`ScrollArea`] (see [`Self::scroll_area`]), the header
+    /// stays at the top. The returned [`ScrollAreaOutput`] contains the scroll offset and
+    /// content size next to the [`VirtualListResponse`].
+    pub fn ui(
+        &mut self,
+        ui: &mut Ui,
+        length: usize,
+        mut header: impl FnMut(&mut Ui, usize),
+        mut cell: impl FnMut(&mut Ui, usize, usize),
+    ) -> ScrollAreaOutput<VirtualListResponse> {
+        let widths = self.column_widths(ui.available_width(), ui.spacing().item_spacing.x);
+        let striped = self.striped;
+
+        Self::row_ui(ui, &widths, false, &mut header);
+        ui.separator();
+
+        self.list
+            .ui_scroll_area(ui, self.scroll_area.clone(), length, |ui, row| {
+                Self::row_ui(ui, &widths, striped && row % 2 == 1, |ui, col| {
+                    cell(ui, row, col);
+                });
+                1
+            })
+    }
+}